@@ -0,0 +1,306 @@
+//! Comparison of two traces: structural diff of their decoded bodies and byte diff of their payloads
+//!
+//! The decoded body is the text of the trace, as written by Amarisoft:
+//! ```text
+//! {
+//!   message c1: systemInformation: {
+//!     criticalExtensions systemInformation-r8: {
+//!       sib-TypeAndInfo {
+//!         sib2: {
+//!           preambleInfo {
+//!             numberOfRA-Preambles n52
+//!           },
+//! ```
+//! It is flattened into fields identified by the names of their blocks, such as
+//! `...sib-TypeAndInfo.sib2.preambleInfo.numberOfRA-Preambles`, the elements of a list being identified by their index.
+use std::ops::Range;
+
+use crate::data::{AdditionalInfos, Trace};
+
+/// Separator of the names in the path of a field
+const PATH_SEPARATOR: char = '.';
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Field of a decoded message
+pub struct DecodedField {
+    /// Path of the field
+    pub path: String,
+
+    /// Value of the field
+    pub value: String,
+}
+
+/// Flatten the decoded body of a message into its fields, in order
+///
+/// The lines before the first `{`, such as the hexadecimal dump, are skipped.
+pub fn decoded_fields<T: AsRef<str>>(text: &[T]) -> Vec<DecodedField> {
+    let mut fields = vec![];
+    // names of the open blocks, with the number of elements read in each of them
+    let mut stack: Vec<(String, usize)> = vec![];
+    for line in text {
+        let line = line.as_ref().trim().trim_end_matches(',').trim_end();
+        if line.is_empty() {
+            continue;
+        }
+        if line.starts_with('}') {
+            stack.pop();
+            continue;
+        }
+        if let Some(name) = line.strip_suffix('{') {
+            let name = name.trim().trim_end_matches(':').to_owned();
+            let name = match (name.is_empty(), stack.last_mut()) {
+                (_, None) => String::new(),
+                (true, Some((_, count))) => count.to_string(),
+                (false, Some(_)) => name,
+            };
+            if let Some((_, count)) = stack.last_mut() {
+                *count += 1;
+            }
+            stack.push((name, 0));
+            continue;
+        }
+        let Some((_, count)) = stack.last_mut() else {
+            continue;
+        };
+        let (name, value) = match line.split_once(char::is_whitespace) {
+            Some((name, value)) => (name.to_owned(), value.trim().to_owned()),
+            None => (count.to_string(), line.to_owned()),
+        };
+        *count += 1;
+        let mut path: Vec<&str> = stack
+            .iter()
+            .map(|(block, _)| block.as_str())
+            .filter(|block| !block.is_empty())
+            .collect();
+        path.push(&name);
+        fields.push(DecodedField {
+            path: path.join(&PATH_SEPARATOR.to_string()),
+            value,
+        });
+    }
+    fields
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// Kind of difference of a field
+pub enum DiffKind {
+    /// Same value in both messages
+    Same,
+
+    /// Field only in the second message
+    Added,
+
+    /// Field only in the first message
+    Removed,
+
+    /// Different values
+    Changed,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Difference of a field between two messages
+pub struct FieldDiff {
+    /// Path of the field
+    pub path: String,
+
+    /// Kind of difference
+    pub kind: DiffKind,
+
+    /// Value in the first message
+    pub left: Option<String>,
+
+    /// Value in the second message
+    pub right: Option<String>,
+}
+
+impl FieldDiff {
+    /// Line of a text report: `+`, `-` or `~` followed by the path and the values
+    pub fn report_line(&self) -> String {
+        let left = self.left.as_deref().unwrap_or_default();
+        let right = self.right.as_deref().unwrap_or_default();
+        match self.kind {
+            DiffKind::Same => format!("  {} {}", self.path, left),
+            DiffKind::Added => format!("+ {} {}", self.path, right),
+            DiffKind::Removed => format!("- {} {}", self.path, left),
+            DiffKind::Changed => format!("~ {} {} -> {}", self.path, left, right),
+        }
+    }
+}
+
+/// Difference of a field present in both messages
+fn common_diff(left: &DecodedField, right: &DecodedField) -> FieldDiff {
+    FieldDiff {
+        path: left.path.clone(),
+        kind: match left.value == right.value {
+            true => DiffKind::Same,
+            false => DiffKind::Changed,
+        },
+        left: Some(left.value.clone()),
+        right: Some(right.value.clone()),
+    }
+}
+
+/// Structural diff of two decoded bodies, in the order of the fields
+///
+/// The fields are matched by their path with a longest common subsequence, so a field added in the middle of a
+/// message doesn't change the fields after it.
+pub fn diff_decoded(left: &[DecodedField], right: &[DecodedField]) -> Vec<FieldDiff> {
+    // the fields at the start and at the end are usually the same, they are matched without the table
+    let prefix = left.iter().zip(right).take_while(|(l, r)| l.path == r.path).count();
+    let suffix = left[prefix..]
+        .iter()
+        .rev()
+        .zip(right[prefix..].iter().rev())
+        .take_while(|(l, r)| l.path == r.path)
+        .count();
+    let mut diffs: Vec<FieldDiff> = left[..prefix].iter().zip(right).map(|(l, r)| common_diff(l, r)).collect();
+    let (left_middle, right_middle) = (&left[prefix..left.len() - suffix], &right[prefix..right.len() - suffix]);
+
+    // lengths of the longest common subsequences of the paths of left_middle[i..] and right_middle[j..]
+    let mut lengths = vec![vec![0usize; right_middle.len() + 1]; left_middle.len() + 1];
+    for i in (0..left_middle.len()).rev() {
+        for j in (0..right_middle.len()).rev() {
+            lengths[i][j] = match left_middle[i].path == right_middle[j].path {
+                true => lengths[i + 1][j + 1] + 1,
+                false => lengths[i + 1][j].max(lengths[i][j + 1]),
+            };
+        }
+    }
+    let (mut i, mut j) = (0, 0);
+    while i < left_middle.len() || j < right_middle.len() {
+        if i < left_middle.len() && j < right_middle.len() && left_middle[i].path == right_middle[j].path {
+            diffs.push(common_diff(&left_middle[i], &right_middle[j]));
+            i += 1;
+            j += 1;
+        } else if j < right_middle.len() && (i == left_middle.len() || lengths[i][j + 1] >= lengths[i + 1][j]) {
+            diffs.push(FieldDiff {
+                path: right_middle[j].path.clone(),
+                kind: DiffKind::Added,
+                left: None,
+                right: Some(right_middle[j].value.clone()),
+            });
+            j += 1;
+        } else {
+            diffs.push(FieldDiff {
+                path: left_middle[i].path.clone(),
+                kind: DiffKind::Removed,
+                left: Some(left_middle[i].value.clone()),
+                right: None,
+            });
+            i += 1;
+        }
+    }
+    diffs.extend(
+        left[left.len() - suffix..]
+            .iter()
+            .zip(&right[right.len() - suffix..])
+            .map(|(l, r)| common_diff(l, r)),
+    );
+    diffs
+}
+
+/// Ranges of the offsets where two payloads differ, the bytes after the end of the shorter payload are different
+pub fn byte_diff(left: &[u8], right: &[u8]) -> Vec<Range<usize>> {
+    let mut ranges: Vec<Range<usize>> = vec![];
+    for offset in 0..left.len().max(right.len()) {
+        if left.get(offset) == right.get(offset) {
+            continue;
+        }
+        match ranges.last_mut() {
+            Some(range) if range.end == offset => range.end += 1,
+            _ => ranges.push(offset..offset + 1),
+        }
+    }
+    ranges
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Comparison of two traces
+pub struct TraceDiff {
+    /// Differences of the fields of the decoded bodies, including the fields with the same value
+    pub fields: Vec<FieldDiff>,
+
+    /// Ranges of the offsets where the payloads differ
+    pub bytes: Vec<Range<usize>>,
+
+    /// Size of the payloads
+    pub sizes: (usize, usize),
+}
+
+impl TraceDiff {
+    /// Compare two traces
+    pub fn new(left: &Trace, right: &Trace) -> Self {
+        let fields_of = |trace: &Trace| decoded_fields(trace.text.as_deref().unwrap_or_default());
+        Self {
+            fields: diff_decoded(&fields_of(left), &fields_of(right)),
+            bytes: byte_diff(&left.hexa, &right.hexa),
+            sizes: (left.hexa.len(), right.hexa.len()),
+        }
+    }
+
+    /// Differences of the fields, without the fields with the same value
+    pub fn changes(&self) -> impl Iterator<Item = &FieldDiff> {
+        self.fields.iter().filter(|field| field.kind != DiffKind::Same)
+    }
+
+    /// Number of fields with a kind of difference
+    pub fn count(&self, kind: DiffKind) -> usize {
+        self.fields.iter().filter(|field| field.kind == kind).count()
+    }
+
+    /// Number of bytes that differ
+    pub fn different_bytes(&self) -> usize {
+        self.bytes.iter().map(|range| range.len()).sum()
+    }
+
+    /// Check if the decoded bodies and the payloads are the same
+    pub fn is_identical(&self) -> bool {
+        self.bytes.is_empty() && self.changes().next().is_none()
+    }
+
+    /// Text report of the differences, to paste in a bug report
+    pub fn report(&self, left: &Trace, right: &Trace) -> String {
+        let title = |trace: &Trace| {
+            let AdditionalInfos::RRCInfos(infos) = &trace.additional_infos;
+            format!(
+                "{:?} {} {} at {}{}",
+                trace.layer,
+                infos.canal,
+                infos.canal_msg,
+                trace.timestamp,
+                trace
+                    .source
+                    .as_deref()
+                    .map(|source| format!(" ({source})"))
+                    .unwrap_or_default()
+            )
+        };
+        let mut lines = vec![
+            format!("--- {}", title(left)),
+            format!("+++ {}", title(right)),
+            format!(
+                "{} changed, {} added, {} removed fields",
+                self.count(DiffKind::Changed),
+                self.count(DiffKind::Added),
+                self.count(DiffKind::Removed)
+            ),
+        ];
+        lines.extend(self.changes().map(FieldDiff::report_line));
+        let ranges: Vec<String> = self
+            .bytes
+            .iter()
+            .map(|range| format!("{:04x}..{:04x}", range.start, range.end))
+            .collect();
+        lines.push(format!(
+            "{} different bytes ({} and {} bytes){}",
+            self.different_bytes(),
+            self.sizes.0,
+            self.sizes.1,
+            match ranges.is_empty() {
+                true => String::new(),
+                false => format!(": {}", ranges.join(", ")),
+            }
+        ));
+        lines.join("\n")
+    }
+}
@@ -0,0 +1,5 @@
+//! Analysis module
+
+pub mod compare;
+pub mod procedures;
+pub mod rrc_state;
@@ -0,0 +1,418 @@
+//! Procedure detection and KPIs
+//!
+//! The procedures are recognized from the names of the RRC messages. The attach (registration for NR) is
+//! recognized from the type of the NAS messages carried in the `dedicatedInfoNAS` (`dedicatedNAS-Message` for NR)
+//! of the RRC messages, the NAS messages ciphered with a non null algorithm are not readable.
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fmt::Display;
+use std::fmt::Formatter;
+
+use crate::data::{AdditionalInfos, Trace};
+
+#[derive(serde::Serialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+/// Kind of procedure
+pub enum ProcedureKind {
+    /// RRC connection establishment (RRC setup for NR)
+    RrcConnectionEstablishment,
+
+    /// Security mode
+    SecurityMode,
+
+    /// UE capability enquiry
+    UeCapabilityEnquiry,
+
+    /// RRC connection reconfiguration (without mobility)
+    Reconfiguration,
+
+    /// RRC connection reestablishment
+    Reestablishment,
+
+    /// Handover (reconfiguration with mobility)
+    Handover,
+
+    /// Attach (registration for NR)
+    Attach,
+}
+
+impl ProcedureKind {
+    /// All the procedure kinds
+    pub const ALL: [ProcedureKind; 7] = [
+        ProcedureKind::RrcConnectionEstablishment,
+        ProcedureKind::SecurityMode,
+        ProcedureKind::UeCapabilityEnquiry,
+        ProcedureKind::Reconfiguration,
+        ProcedureKind::Reestablishment,
+        ProcedureKind::Handover,
+        ProcedureKind::Attach,
+    ];
+}
+
+impl Display for ProcedureKind {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let str = match self {
+            ProcedureKind::RrcConnectionEstablishment => "RRC connection establishment",
+            ProcedureKind::SecurityMode => "Security mode",
+            ProcedureKind::UeCapabilityEnquiry => "UE capability enquiry",
+            ProcedureKind::Reconfiguration => "Reconfiguration",
+            ProcedureKind::Reestablishment => "Reestablishment",
+            ProcedureKind::Handover => "Handover",
+            ProcedureKind::Attach => "Attach / registration",
+        };
+        write!(f, "{str}")
+    }
+}
+
+#[derive(serde::Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+/// Outcome of a procedure
+pub enum ProcedureOutcome {
+    /// The procedure completed
+    Success,
+
+    /// The procedure was rejected, failed or interrupted by a release
+    Failure,
+
+    /// The end of the procedure was not found
+    Incomplete,
+}
+
+impl Display for ProcedureOutcome {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            ProcedureOutcome::Success => write!(f, "success"),
+            ProcedureOutcome::Failure => write!(f, "failure"),
+            ProcedureOutcome::Incomplete => write!(f, "incomplete"),
+        }
+    }
+}
+
+#[derive(serde::Serialize, Debug, Clone)]
+/// A procedure found in the traces
+pub struct Procedure {
+    /// Kind of procedure
+    pub kind: ProcedureKind,
+
+    /// UE of the procedure
+    pub ue_id: u64,
+
+    /// Index of the first trace of the procedure
+    pub start_index: usize,
+
+    /// Timestamp of the first trace of the procedure
+    pub start_timestamp: u64,
+
+    /// Index of the last trace of the procedure
+    pub end_index: Option<usize>,
+
+    /// Timestamp of the last trace of the procedure
+    pub end_timestamp: Option<u64>,
+
+    /// Outcome of the procedure
+    pub outcome: ProcedureOutcome,
+}
+
+impl Procedure {
+    /// Duration of the procedure in milliseconds, None if it is not finished
+    pub fn duration(&self) -> Option<u64> {
+        self.end_timestamp
+            .map(|end_timestamp| end_timestamp.saturating_sub(self.start_timestamp))
+    }
+}
+
+#[derive(serde::Serialize, Debug, Clone, PartialEq)]
+/// KPIs of a kind of procedure
+pub struct ProcedureKpi {
+    /// Kind of procedure
+    pub kind: ProcedureKind,
+
+    /// Number of procedures
+    pub total: usize,
+
+    /// Number of successful procedures
+    pub success: usize,
+
+    /// Number of failed procedures
+    pub failure: usize,
+
+    /// Number of incomplete procedures
+    pub incomplete: usize,
+
+    /// Success rate of the finished procedures (between 0 and 1)
+    pub success_rate: Option<f64>,
+
+    /// Median latency of the successful procedures (milliseconds)
+    pub latency_p50: Option<u64>,
+
+    /// 90th percentile latency of the successful procedures (milliseconds)
+    pub latency_p90: Option<u64>,
+
+    /// 99th percentile latency of the successful procedures (milliseconds)
+    pub latency_p99: Option<u64>,
+}
+
+/// What a trace means for a procedure
+enum Step {
+    /// The trace starts a procedure
+    Start(ProcedureKind),
+
+    /// The trace ends a procedure
+    End(ProcedureKind, ProcedureOutcome),
+
+    /// The trace ends every running procedure of the UE
+    Release,
+}
+
+/// Find what a trace means for the procedures
+fn trace_steps(trace: &Trace) -> Vec<Step> {
+    use ProcedureKind::*;
+    use ProcedureOutcome::*;
+    let AdditionalInfos::RRCInfos(infos) = &trace.additional_infos;
+    let msg = infos.canal_msg.trim().to_lowercase();
+    let mut steps = match msg.as_str() {
+        "rrc connection request" | "rrc setup request" => vec![Step::Start(RrcConnectionEstablishment)],
+        "rrc connection setup complete" | "rrc setup complete" => vec![Step::End(RrcConnectionEstablishment, Success)],
+        "rrc connection reject" | "rrc reject" => vec![Step::End(RrcConnectionEstablishment, Failure)],
+        "security mode command" => vec![Step::Start(SecurityMode)],
+        "security mode complete" => vec![Step::End(SecurityMode, Success)],
+        "security mode failure" => vec![Step::End(SecurityMode, Failure)],
+        "ue capability enquiry" => vec![Step::Start(UeCapabilityEnquiry)],
+        "ue capability information" => vec![Step::End(UeCapabilityEnquiry, Success)],
+        "rrc connection reconfiguration" | "rrc reconfiguration" => {
+            if is_handover_command(trace) {
+                vec![Step::Start(Handover)]
+            } else {
+                vec![Step::Start(Reconfiguration)]
+            }
+        }
+        "rrc connection reconfiguration complete" | "rrc reconfiguration complete" => {
+            vec![Step::End(Reconfiguration, Success), Step::End(Handover, Success)]
+        }
+        "rrc connection reestablishment request" | "rrc reestablishment request" => {
+            vec![Step::End(Handover, Failure), Step::Start(Reestablishment)]
+        }
+        "rrc connection reestablishment complete" | "rrc reestablishment complete" => {
+            vec![Step::End(Reestablishment, Success)]
+        }
+        "rrc connection reestablishment reject" => vec![Step::End(Reestablishment, Failure)],
+        "rrc connection release" | "rrc release" => vec![Step::Release],
+        _ => vec![],
+    };
+    let nas_steps = nas_message_types(trace)
+        .into_iter()
+        .filter_map(|message_type| match message_type {
+            NAS_ATTACH_REQUEST => Some(Step::Start(Attach)),
+            NAS_ATTACH_COMPLETE => Some(Step::End(Attach, Success)),
+            NAS_ATTACH_REJECT => Some(Step::End(Attach, Failure)),
+            _ => None,
+        });
+    steps.extend(nas_steps);
+    steps
+}
+
+/// Type of the attach request (registration request for NR) NAS message
+const NAS_ATTACH_REQUEST: u8 = 0x41;
+
+/// Type of the attach complete (registration complete for NR) NAS message
+const NAS_ATTACH_COMPLETE: u8 = 0x43;
+
+/// Type of the attach reject (registration reject for NR) NAS message
+const NAS_ATTACH_REJECT: u8 = 0x44;
+
+/// Get the types of the EPS and 5GS mobility management NAS messages carried by a RRC message
+fn nas_message_types(trace: &Trace) -> Vec<u8> {
+    let Some(lines) = &trace.text else {
+        return vec![];
+    };
+    let mut types = vec![];
+    let mut in_list = false;
+    for line in lines.iter().map(|line| line.trim()) {
+        if line.contains("dedicatedInfoNASList") || line.contains("dedicatedNAS-MessageList") {
+            in_list = true;
+            continue;
+        }
+        if in_list && line.starts_with('}') {
+            in_list = false;
+            continue;
+        }
+        if in_list || line.contains("dedicatedInfoNAS") || line.contains("dedicatedNAS-Message") {
+            if let Some(message_type) = octet_string(line).as_deref().and_then(nas_message_type) {
+                types.push(message_type);
+            }
+        }
+    }
+    types
+}
+
+/// Decode the hexadecimal string (`'0741...'H`) of a line
+fn octet_string(line: &str) -> Option<Vec<u8>> {
+    let start = line.find('\'')? + 1;
+    let len = line[start..].find("'H")?;
+    let hex = &line[start..start + len];
+    if hex.len() % 2 != 0 {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|idx| u8::from_str_radix(hex.get(idx..idx + 2)?, 16).ok())
+        .collect()
+}
+
+/// Get the type of a mobility management NAS message, after its security header if any
+fn nas_message_type(nas: &[u8]) -> Option<u8> {
+    match nas.first()? {
+        // 5GS: extended protocol discriminator, security header type and message type
+        0x7e => match nas.get(1)? & 0x0f {
+            0 => nas.get(2).copied(),
+            // header of 7 bytes (with the MAC and the sequence number) before the plain message
+            _ => nas_message_type(nas.get(7..)?),
+        },
+        // EPS: security header type in the high nibble, protocol discriminator 7 for the EMM
+        header if header & 0x0f == 0x07 => match header >> 4 {
+            0 => nas.get(1).copied(),
+            // header of 6 bytes (with the MAC and the sequence number) before the plain message
+            1..=4 => nas_message_type(nas.get(6..)?),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Check if a reconfiguration carries a mobility information (handover command)
+fn is_handover_command(trace: &Trace) -> bool {
+    match &trace.text {
+        Some(lines) => lines
+            .iter()
+            .any(|line| line.contains("mobilityControlInfo") || line.contains("reconfigurationWithSync")),
+        None => false,
+    }
+}
+
+/// Get a percentile (nearest rank) of sorted values
+fn percentile(sorted: &[u64], percent: usize) -> Option<u64> {
+    if sorted.is_empty() {
+        return None;
+    }
+    let rank = (percent * sorted.len()).div_ceil(100).max(1);
+    sorted.get(rank - 1).copied()
+}
+
+#[derive(Debug, Default)]
+/// Analyzer that recognizes procedures in the traces
+pub struct ProcedureAnalyzer {
+    /// Procedures found, in start order
+    pub procedures: Vec<Procedure>,
+
+    /// Running procedures: index in `procedures` by UE and kind
+    running: BTreeMap<(u64, ProcedureKind), usize>,
+
+    /// Number of traces already analyzed
+    analyzed: usize,
+}
+
+impl ProcedureAnalyzer {
+    /// Create a new ProcedureAnalyzer
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Analyze the traces not analyzed yet, restart from the beginning if the traces were cleared
+    pub fn analyze(&mut self, events: &[Trace]) {
+        if events.len() < self.analyzed {
+            self.clear();
+        }
+        for (idx, trace) in events.iter().enumerate().skip(self.analyzed) {
+            self.process(idx, trace);
+        }
+        self.analyzed = events.len();
+    }
+
+    /// Clear the analyzer
+    pub fn clear(&mut self) {
+        self.procedures.clear();
+        self.running.clear();
+        self.analyzed = 0;
+    }
+
+    /// Process one trace
+    fn process(&mut self, idx: usize, trace: &Trace) {
+        let AdditionalInfos::RRCInfos(infos) = &trace.additional_infos;
+        let Some(ue_id) = infos.ue_id else {
+            return;
+        };
+        for step in trace_steps(trace) {
+            match step {
+                Step::Start(kind) => {
+                    // a procedure started again before its end stays incomplete
+                    self.running.insert((ue_id, kind), self.procedures.len());
+                    self.procedures.push(Procedure {
+                        kind,
+                        ue_id,
+                        start_index: idx,
+                        start_timestamp: trace.timestamp,
+                        end_index: None,
+                        end_timestamp: None,
+                        outcome: ProcedureOutcome::Incomplete,
+                    });
+                }
+                Step::End(kind, outcome) => {
+                    if let Some(running) = self.running.remove(&(ue_id, kind)) {
+                        self.end(running, idx, trace, outcome);
+                    }
+                }
+                Step::Release => {
+                    let ue_running: Vec<(u64, ProcedureKind)> =
+                        self.running.keys().filter(|(ue, _)| *ue == ue_id).copied().collect();
+                    for key in ue_running {
+                        if let Some(running) = self.running.remove(&key) {
+                            self.end(running, idx, trace, ProcedureOutcome::Failure);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// End a running procedure
+    fn end(&mut self, procedure_idx: usize, idx: usize, trace: &Trace, outcome: ProcedureOutcome) {
+        let procedure = &mut self.procedures[procedure_idx];
+        procedure.end_index = Some(idx);
+        procedure.end_timestamp = Some(trace.timestamp);
+        procedure.outcome = outcome;
+    }
+
+    /// Compute the KPIs of each kind of procedure found
+    pub fn kpis(&self) -> Vec<ProcedureKpi> {
+        let mut kpis = vec![];
+        for kind in ProcedureKind::ALL {
+            let of_kind: Vec<&Procedure> = self.procedures.iter().filter(|p| p.kind == kind).collect();
+            if of_kind.is_empty() {
+                continue;
+            }
+            let count = |outcome: ProcedureOutcome| of_kind.iter().filter(|p| p.outcome == outcome).count();
+            let success = count(ProcedureOutcome::Success);
+            let failure = count(ProcedureOutcome::Failure);
+            let mut latencies: Vec<u64> = of_kind
+                .iter()
+                .filter(|p| p.outcome == ProcedureOutcome::Success)
+                .filter_map(|p| p.duration())
+                .collect();
+            latencies.sort_unstable();
+            kpis.push(ProcedureKpi {
+                kind,
+                total: of_kind.len(),
+                success,
+                failure,
+                incomplete: count(ProcedureOutcome::Incomplete),
+                success_rate: if success + failure > 0 {
+                    Some(success as f64 / (success + failure) as f64)
+                } else {
+                    None
+                },
+                latency_p50: percentile(&latencies, 50),
+                latency_p90: percentile(&latencies, 90),
+                latency_p99: percentile(&latencies, 99),
+            });
+        }
+        kpis
+    }
+}
@@ -0,0 +1,123 @@
+//! RRC state machine tracked per UE
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fmt::Display;
+use std::fmt::Formatter;
+
+use crate::data::{AdditionalInfos, Trace};
+use crate::interface::layer::Layer;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+/// RRC state of a UE
+pub enum RrcState {
+    #[default]
+    /// No RRC connection
+    Idle,
+
+    /// Connection requested, waiting for the setup to complete
+    Connecting,
+
+    /// RRC connection established
+    Connected,
+
+    /// Reestablishment requested, waiting for it to complete
+    Reestablishing,
+
+    /// Connection suspended (NR only)
+    Inactive,
+}
+
+impl Display for RrcState {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            RrcState::Idle => write!(f, "IDLE"),
+            RrcState::Connecting => write!(f, "CONNECTING"),
+            RrcState::Connected => write!(f, "CONNECTED"),
+            RrcState::Reestablishing => write!(f, "REESTABLISHING"),
+            RrcState::Inactive => write!(f, "INACTIVE"),
+        }
+    }
+}
+
+impl RrcState {
+    /// Compute the state reached after a trace, the state is unchanged if the trace is not a state transition
+    ///
+    /// A change of the ID of the UE (see [`changed_ue_id`]) doesn't change the state, it moves it to the new ID.
+    pub fn next(self, trace: &Trace) -> RrcState {
+        if trace.layer != Layer::RRC {
+            return self;
+        }
+        let AdditionalInfos::RRCInfos(infos) = &trace.additional_infos;
+        let msg = infos.canal_msg.trim().to_lowercase();
+        match msg.as_str() {
+            // LTE
+            "rrc connection request" | "rrc connection setup" => RrcState::Connecting,
+            "rrc connection setup complete" => RrcState::Connected,
+            "rrc connection reestablishment request" | "rrc connection reestablishment" => RrcState::Reestablishing,
+            "rrc connection reestablishment complete" => RrcState::Connected,
+            "rrc connection reject" | "rrc connection reestablishment reject" | "rrc connection release" => RrcState::Idle,
+            // NR
+            "rrc setup request" | "rrc setup" => RrcState::Connecting,
+            "rrc setup complete" => RrcState::Connected,
+            "rrc reestablishment request" | "rrc reestablishment" => RrcState::Reestablishing,
+            "rrc reestablishment complete" => RrcState::Connected,
+            "rrc resume request" | "rrc resume request1" | "rrc resume" => match self {
+                RrcState::Connected => RrcState::Connected,
+                _ => RrcState::Connecting,
+            },
+            "rrc resume complete" => RrcState::Connected,
+            "rrc reject" => match self {
+                RrcState::Inactive => RrcState::Inactive,
+                _ => RrcState::Idle,
+            },
+            "rrc release" => {
+                if has_suspend_config(trace) {
+                    RrcState::Inactive
+                } else {
+                    RrcState::Idle
+                }
+            }
+            _ => self,
+        }
+    }
+}
+
+/// New ID of the UE of a "Changing UE_ID to 0x…" information, logged by the eNB when a UE reestablished under a new
+/// ID gets back the ID it had before the reestablishment
+pub fn changed_ue_id(trace: &Trace) -> Option<u64> {
+    let AdditionalInfos::RRCInfos(infos) = &trace.additional_infos;
+    let new_id = infos.canal_msg.trim().strip_prefix("Changing UE_ID to 0x")?;
+    u64::from_str_radix(new_id, 16).ok()
+}
+
+/// Check if a NR RRC release carries a suspend configuration (transition to INACTIVE)
+fn has_suspend_config(trace: &Trace) -> bool {
+    match &trace.text {
+        Some(lines) => lines.iter().any(|line| line.contains("suspendConfig")),
+        None => false,
+    }
+}
+
+/// Compute the RRC state of each UE after the trace at `index` (included)
+///
+/// The states are computed by replaying the traces from the beginning, so the result only depends on `index`
+/// and not on the navigation history.
+pub fn rrc_states_at(events: &[Trace], index: usize) -> BTreeMap<u64, RrcState> {
+    let mut states = BTreeMap::new();
+    let end = (index + 1).min(events.len());
+    for trace in &events[..end] {
+        let AdditionalInfos::RRCInfos(infos) = &trace.additional_infos;
+        let Some(ue_id) = infos.ue_id else {
+            continue;
+        };
+        if let Some(new_id) = changed_ue_id(trace) {
+            if let Some(state) = states.remove(&ue_id) {
+                states.insert(new_id, state);
+            }
+            continue;
+        }
+        let state: &mut RrcState = states.entry(ue_id).or_default();
+        *state = state.next(trace);
+    }
+    states
+}
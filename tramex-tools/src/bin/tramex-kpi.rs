@@ -0,0 +1,170 @@
+//! Command line tool to compute the procedure KPIs of Amarisoft log files
+//!
+//! Usage: `tramex-kpi [--json] [--procedures] [--total] <file.log>...`
+//!
+//! The KPIs are computed for each file, `--total` adds the KPIs of the procedures of all the files.
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use tramex_tools::analysis::procedures::{ProcedureAnalyzer, ProcedureKpi};
+use tramex_tools::data::Data;
+use tramex_tools::errors::ErrorCode;
+use tramex_tools::interface::interface_file::file_handler::File;
+use tramex_tools::interface::interface_types::InterfaceTrait;
+use tramex_tools::interface::layer::Layers;
+
+/// Options of the command line
+#[derive(Default)]
+struct Options {
+    /// Print the KPIs as JSON
+    json: bool,
+
+    /// Print every procedure found
+    procedures: bool,
+
+    /// Print the KPIs of all the files together
+    total: bool,
+
+    /// Files to analyze
+    files: Vec<PathBuf>,
+}
+
+/// Parse the command line arguments
+fn parse_args() -> Result<Options, String> {
+    let mut options = Options::default();
+    for arg in std::env::args().skip(1) {
+        match arg.as_str() {
+            "--json" => options.json = true,
+            "--procedures" => options.procedures = true,
+            "--total" => options.total = true,
+            "-h" | "--help" => return Err(String::new()),
+            _ if arg.starts_with('-') => return Err(format!("Unknown option {arg}")),
+            _ => options.files.push(arg.into()),
+        }
+    }
+    if options.files.is_empty() {
+        return Err("No file given".to_owned());
+    }
+    Ok(options)
+}
+
+/// Read and parse a whole log file, and find its procedures
+fn analyze_file(path: &PathBuf) -> Result<ProcedureAnalyzer, String> {
+    let content = std::fs::read_to_string(path).map_err(|err| format!("{}: {err}", path.display()))?;
+    let mut file = File::new(path.clone(), content);
+    let mut data = Data::default();
+    while !file.full_read {
+        if let Err(errors) = file.get_more_data(Layers::all_debug(), &mut data) {
+            for one_error in errors {
+                if !matches!(one_error.get_code(), ErrorCode::ParsingLayerNotImplemented) {
+                    eprintln!("{}: {}", path.display(), one_error.message);
+                }
+            }
+        }
+    }
+    let mut analyzer = ProcedureAnalyzer::new();
+    analyzer.analyze(&data.events);
+    Ok(analyzer)
+}
+
+/// Format an optional value
+fn opt<T: ToString>(value: Option<T>) -> String {
+    value.map(|v| v.to_string()).unwrap_or_else(|| "-".to_owned())
+}
+
+/// Print the procedures found, one per line
+fn print_procedures(analyzer: &ProcedureAnalyzer) {
+    for procedure in &analyzer.procedures {
+        println!(
+            "#{:<6} UE {:<4} {:<30} {:<10} {} ms",
+            procedure.start_index + 1,
+            procedure.ue_id,
+            procedure.kind.to_string(),
+            procedure.outcome.to_string(),
+            opt(procedure.duration())
+        );
+    }
+    println!();
+}
+
+/// Print the KPIs as a table
+fn print_kpis(kpis: &[ProcedureKpi]) {
+    println!(
+        "{:<30} {:>6} {:>7} {:>7} {:>10} {:>8} {:>7} {:>7} {:>7}",
+        "Procedure", "Total", "Success", "Failure", "Incomplete", "Rate", "p50 ms", "p90 ms", "p99 ms"
+    );
+    for kpi in kpis {
+        println!(
+            "{:<30} {:>6} {:>7} {:>7} {:>10} {:>8} {:>7} {:>7} {:>7}",
+            kpi.kind.to_string(),
+            kpi.total,
+            kpi.success,
+            kpi.failure,
+            kpi.incomplete,
+            opt(kpi.success_rate.map(|rate| format!("{:.1}%", rate * 100.0))),
+            opt(kpi.latency_p50),
+            opt(kpi.latency_p90),
+            opt(kpi.latency_p99),
+        );
+    }
+}
+
+fn main() -> ExitCode {
+    let options = match parse_args() {
+        Ok(options) => options,
+        Err(err) => {
+            if !err.is_empty() {
+                eprintln!("{err}");
+            }
+            eprintln!("Usage: tramex-kpi [--json] [--procedures] [--total] <file.log>...");
+            return ExitCode::FAILURE;
+        }
+    };
+    let mut analyzed = vec![];
+    for path in &options.files {
+        match analyze_file(path) {
+            Ok(analyzer) => analyzed.push((path.display().to_string(), analyzer)),
+            Err(err) => {
+                eprintln!("{err}");
+                return ExitCode::FAILURE;
+            }
+        }
+    }
+    // the procedures of the files are independent, the total only gathers them
+    let total = options.total.then(|| {
+        let mut total = ProcedureAnalyzer::new();
+        for (_, analyzer) in &analyzed {
+            total.procedures.extend(analyzer.procedures.iter().cloned());
+        }
+        total
+    });
+    if options.json {
+        let mut files = serde_json::Map::new();
+        for (name, analyzer) in &analyzed {
+            let mut output = serde_json::json!({ "kpis": analyzer.kpis() });
+            if options.procedures {
+                output["procedures"] = serde_json::json!(analyzer.procedures);
+            }
+            files.insert(name.clone(), output);
+        }
+        let mut output = serde_json::json!({ "files": files });
+        if let Some(total) = &total {
+            output["total"] = serde_json::json!({ "kpis": total.kpis() });
+        }
+        println!("{output:#}");
+        return ExitCode::SUCCESS;
+    }
+    for (name, analyzer) in &analyzed {
+        println!("== {name}");
+        if options.procedures {
+            print_procedures(analyzer);
+        }
+        print_kpis(&analyzer.kpis());
+        println!();
+    }
+    if let Some(total) = &total {
+        println!("== Total ({} files)", analyzed.len());
+        print_kpis(&total.kpis());
+    }
+    ExitCode::SUCCESS
+}
@@ -0,0 +1,90 @@
+//! Mock of the Amarisoft remote API serving the logs of a log file
+//!
+//! Usage: `tramex-mock-server [--name NAME] [--type ENB|MME] [--host HOST] [--port PORT] [--batch N] <file.log>`
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use tramex_tools::mock_server::{MockConfig, MockServer};
+
+/// Usage of the command line
+const USAGE: &str =
+    "Usage: tramex-mock-server [--name NAME] [--type ENB|MME] [--host HOST] [--port PORT] [--batch N] <file.log>";
+
+/// Options of the command line
+struct Options {
+    /// Configuration of the server
+    config: MockConfig,
+
+    /// Address to listen on
+    host: String,
+
+    /// Port to listen on
+    port: u16,
+
+    /// File of the logs served
+    file: PathBuf,
+}
+
+/// Parse the command line arguments
+fn parse_args() -> Result<Options, String> {
+    let mut config = MockConfig::default();
+    let mut host = "127.0.0.1".to_owned();
+    let mut port = 9001;
+    let mut file = None;
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or_else(|| format!("Missing value of {arg}"));
+        match arg.as_str() {
+            "--name" => config.name = value()?,
+            "--type" => config.server_type = value()?,
+            "--host" => host = value()?,
+            "--port" => port = value()?.parse().map_err(|err| format!("Invalid port: {err}"))?,
+            "--batch" => config.batch_size = value()?.parse().map_err(|err| format!("Invalid batch size: {err}"))?,
+            "-h" | "--help" => return Err(String::new()),
+            _ if arg.starts_with('-') => return Err(format!("Unknown option {arg}")),
+            _ if file.is_some() => return Err("Only one file can be served".to_owned()),
+            _ => file = Some(PathBuf::from(arg)),
+        }
+    }
+    let Some(file) = file else {
+        return Err("No file given".to_owned());
+    };
+    Ok(Options {
+        config,
+        host,
+        port,
+        file,
+    })
+}
+
+fn main() -> ExitCode {
+    let options = match parse_args() {
+        Ok(options) => options,
+        Err(err) => {
+            if !err.is_empty() {
+                eprintln!("{err}");
+            }
+            eprintln!("{USAGE}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let content = match std::fs::read_to_string(&options.file) {
+        Ok(content) => content,
+        Err(err) => {
+            eprintln!("{}: {err}", options.file.display());
+            return ExitCode::FAILURE;
+        }
+    };
+    let server = MockServer::from_log_file(options.config, content);
+    let nb_logs = server.nb_logs();
+    let handle = match server.start(&format!("{}:{}", options.host, options.port)) {
+        Ok(handle) => handle,
+        Err(err) => {
+            eprintln!("{}", err.message);
+            return ExitCode::FAILURE;
+        }
+    };
+    println!("Serving {nb_logs} logs of {} on {}", options.file.display(), handle.url());
+    handle.wait();
+    ExitCode::SUCCESS
+}
@@ -1,5 +1,10 @@
 //! This module contains the data structures used to store the data of the application.
-use crate::interface::{layer::Layer, parser::parser_rrc::RRCInfos};
+use crate::interface::{
+    layer::{Layer, Layers},
+    parser::parser_rrc::RRCInfos,
+    remote_api::RemoteApi,
+};
+use crate::search::Search;
 use core::fmt::Debug;
 
 #[derive(Debug)]
@@ -9,6 +14,24 @@ pub struct Data {
     pub events: Vec<Trace>,
     /// Current index of the vector.
     pub current_index: usize,
+
+    /// Filter shared by the panels.
+    pub filter: TraceFilter,
+
+    /// Requests to the remote API and their responses.
+    pub remote: RemoteApi,
+
+    /// Revision of the events, incremented when they are changed other than by appending.
+    pub revision: u64,
+
+    /// Revision of the bookmarks, incremented when a trace is bookmarked or its bookmark removed.
+    pub bookmarks_revision: u64,
+
+    /// Traces of the layers hidden by the layer filter, ordered by timestamp.
+    pub hidden_events: Vec<Trace>,
+
+    /// Search shared by the panels, its matches are highlighted.
+    pub search: Option<Search>,
 }
 
 impl Data {
@@ -25,24 +48,260 @@ impl Data {
         self.current_index != index
     }
 
+    /// insert a trace keeping the events ordered by timestamp
+    ///
+    /// Traces of several sources are merged: a trace older than the last one is inserted after the traces
+    /// with the same timestamp, the current index keeps pointing to the same trace and the revision is incremented.
+    pub fn push_ordered(&mut self, trace: Trace) {
+        if self.events.last().is_none_or(|last| last.timestamp <= trace.timestamp) {
+            self.events.push(trace);
+            return;
+        }
+        let position = self.events.partition_point(|event| event.timestamp <= trace.timestamp);
+        self.events.insert(position, trace);
+        if position <= self.current_index {
+            self.current_index += 1;
+        }
+        self.revision += 1;
+    }
+
+    /// check if a trace of the same source, with the same time, layer and content is already loaded (shown or hidden)
+    pub fn contains(&self, trace: &Trace) -> bool {
+        find_trace(&self.events, trace).is_some() || find_trace(&self.hidden_events, trace).is_some()
+    }
+
+    /// index of the shown trace of the same source, with the same time, layer and content
+    pub fn position(&self, trace: &Trace) -> Option<usize> {
+        find_trace(&self.events, trace)
+    }
+
+    /// remove the traces of a source, the current index is reset if its trace is removed
+    pub fn remove_source(&mut self, source: &str) {
+        let current = self.get_current_trace().map(|trace| (trace.timestamp, trace.source.clone()));
+        self.events.retain(|event| event.source.as_deref() != Some(source));
+        self.hidden_events.retain(|event| event.source.as_deref() != Some(source));
+        self.current_index = match current {
+            Some((timestamp, current_source)) if current_source.as_deref() != Some(source) => self
+                .events
+                .iter()
+                .position(|event| event.timestamp == timestamp && event.source == current_source)
+                .unwrap_or(0),
+            _ => 0,
+        };
+        self.revision += 1;
+    }
+
+    /// shift the timestamps of the traces of a source and order the events again, the current trace is kept
+    pub fn shift_source(&mut self, source: &str, delta_ms: i64) {
+        for event in self.events.iter_mut().chain(self.hidden_events.iter_mut()) {
+            if event.source.as_deref() == Some(source) {
+                event.timestamp = (event.timestamp as i64 + delta_ms).max(0) as u64;
+            }
+        }
+        self.hidden_events.sort_by_key(|event| event.timestamp);
+        let mut order: Vec<usize> = (0..self.events.len()).collect();
+        order.sort_by_key(|idx| self.events[*idx].timestamp);
+        self.current_index = order.iter().position(|idx| *idx == self.current_index).unwrap_or(0);
+        let mut events: Vec<Option<Trace>> = self.events.drain(..).map(Some).collect();
+        self.events = order.iter().filter_map(|idx| events[*idx].take()).collect();
+        self.revision += 1;
+    }
+
+    /// keep a trace of a hidden layer, it is shown by `filter_layers` once its layer is shown
+    pub fn push_hidden(&mut self, trace: Trace) {
+        let position = self.hidden_events.partition_point(|event| event.timestamp <= trace.timestamp);
+        self.hidden_events.insert(position, trace);
+    }
+
+    /// show the traces of the layers shown and hide the others
+    ///
+    /// The current trace is kept if it is still shown, otherwise the previous trace shown becomes the current one.
+    pub fn filter_layers(&mut self, layers: &Layers) {
+        if self.events.iter().all(|event| layers.shows(&event.layer))
+            && self.hidden_events.iter().all(|event| !layers.shows(&event.layer))
+        {
+            return;
+        }
+        let current_index = self.current_index;
+        let mut shown = std::mem::take(&mut self.events).into_iter().enumerate().peekable();
+        let mut hidden = std::mem::take(&mut self.hidden_events).into_iter().peekable();
+        let mut current = None;
+        loop {
+            // merge by timestamp, the traces shown first
+            let (is_current, trace) = match (shown.peek(), hidden.peek()) {
+                (Some((_, trace)), Some(hidden_trace)) if hidden_trace.timestamp < trace.timestamp => (false, hidden.next()),
+                (Some((index, _)), _) => (*index == current_index, shown.next().map(|(_, trace)| trace)),
+                (None, _) => (false, hidden.next()),
+            };
+            let Some(trace) = trace else {
+                break;
+            };
+            if layers.shows(&trace.layer) {
+                if is_current {
+                    current = Some(self.events.len());
+                }
+                self.events.push(trace);
+            } else {
+                if is_current {
+                    current = Some(self.events.len().saturating_sub(1));
+                }
+                self.hidden_events.push(trace);
+            }
+        }
+        self.current_index = current.unwrap_or(0).min(self.events.len().saturating_sub(1));
+        self.revision += 1;
+    }
+
+    /// bookmark a trace or remove its bookmark
+    pub fn toggle_bookmark(&mut self, index: usize) {
+        if let Some(trace) = self.events.get_mut(index) {
+            trace.bookmark = match trace.bookmark {
+                Some(_) => None,
+                None => Some(Bookmark::default()),
+            };
+            self.bookmarks_revision += 1;
+        }
+    }
+
+    /// index of the first bookmarked trace after the current one
+    pub fn next_bookmark(&self) -> Option<usize> {
+        self.events
+            .iter()
+            .enumerate()
+            .skip(self.current_index + 1)
+            .find(|(_, trace)| trace.bookmark.is_some())
+            .map(|(index, _)| index)
+    }
+
+    /// index of the last bookmarked trace before the current one
+    pub fn previous_bookmark(&self) -> Option<usize> {
+        self.events
+            .iter()
+            .enumerate()
+            .take(self.current_index)
+            .rfind(|(_, trace)| trace.bookmark.is_some())
+            .map(|(index, _)| index)
+    }
+
+    /// bookmarked traces shown, with their index
+    pub fn bookmarks(&self) -> impl Iterator<Item = (usize, &Trace, &Bookmark)> {
+        self.events
+            .iter()
+            .enumerate()
+            .filter_map(|(index, trace)| trace.bookmark.as_ref().map(|bookmark| (index, trace, bookmark)))
+    }
+
+    /// export the bookmarked traces shown as CSV: index, timestamp (milliseconds), source, layer, message, tags and note
+    pub fn export_bookmarks(&self) -> String {
+        let mut csv = String::from("index,timestamp,source,layer,message,tags,note\n");
+        for (index, trace, bookmark) in self.bookmarks() {
+            let AdditionalInfos::RRCInfos(infos) = &trace.additional_infos;
+            let fields = [
+                (index + 1).to_string(),
+                trace.timestamp.to_string(),
+                trace.source.clone().unwrap_or_default(),
+                format!("{:?}", trace.layer),
+                infos.canal_msg.clone(),
+                bookmark.tags.join(";"),
+                bookmark.note.clone(),
+            ];
+            let fields: Vec<String> = fields.iter().map(|field| csv_field(field)).collect();
+            csv.push_str(&fields.join(","));
+            csv.push('\n');
+        }
+        csv
+    }
+
     /// clear the data
     pub fn clear(&mut self) {
+        self.revision += 1;
         self.events.clear();
+        self.hidden_events.clear();
         self.current_index = 0;
+        self.filter = TraceFilter::default();
+        self.search = None;
+        self.remote.clear();
     }
 }
 
+/// index of a trace of the same source, with the same time, layer and content in traces ordered by timestamp
+fn find_trace(events: &[Trace], trace: &Trace) -> Option<usize> {
+    let start = events.partition_point(|event| event.timestamp < trace.timestamp);
+    events[start..]
+        .iter()
+        .take_while(|event| event.timestamp == trace.timestamp)
+        .position(|event| {
+            event.source == trace.source
+                && event.layer == trace.layer
+                && event.hexa == trace.hexa
+                && event.text == trace.text
+        })
+        .map(|offset| start + offset)
+}
+
 impl Default for Data {
     fn default() -> Self {
         let default_data_size = 2048;
         Self {
             events: Vec::with_capacity(default_data_size),
             current_index: 0,
+            filter: TraceFilter::default(),
+            remote: RemoteApi::default(),
+            revision: 0,
+            bookmarks_revision: 0,
+            hidden_events: Vec::new(),
+            search: None,
         }
     }
 }
 
-#[derive(Debug, Clone)]
+/// Quote a CSV field if needed
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        return format!("\"{}\"", field.replace('"', "\"\""));
+    }
+    field.to_owned()
+}
+
+#[derive(serde::Deserialize, serde::Serialize, Debug, Default, Clone, PartialEq)]
+#[serde(default)]
+/// Filter shared by the panels to restrict the displayed traces.
+pub struct TraceFilter {
+    /// Time range (start and end timestamps, inclusive).
+    pub time_range: Option<(u64, u64)>,
+
+    /// UE identifier.
+    pub ue_id: Option<u64>,
+}
+
+impl TraceFilter {
+    /// return if the trace matches the filter
+    pub fn matches(&self, trace: &Trace) -> bool {
+        self.matches_time(trace) && self.matches_ue(trace)
+    }
+
+    /// return if the trace is in the time range of the filter
+    pub fn matches_time(&self, trace: &Trace) -> bool {
+        self.time_range
+            .is_none_or(|(start, end)| start <= trace.timestamp && trace.timestamp <= end)
+    }
+
+    /// return if the trace matches the UE of the filter
+    pub fn matches_ue(&self, trace: &Trace) -> bool {
+        let AdditionalInfos::RRCInfos(infos) = &trace.additional_infos;
+        match self.ue_id {
+            Some(ue_id) => infos.ue_id == Some(ue_id),
+            None => true,
+        }
+    }
+
+    /// return if the filter keeps every trace
+    pub fn is_empty(&self) -> bool {
+        self.time_range.is_none() && self.ue_id.is_none()
+    }
+}
+
+#[derive(serde::Deserialize, serde::Serialize, Debug, Clone)]
 /// Data structure to store Trace of the application.
 pub struct Trace {
     /// Message type.
@@ -60,10 +319,46 @@ pub struct Trace {
 
     /// Text representation of the message from the API
     pub text: Option<Vec<String>>,
+
+    /// Source of the message (interface name or file name).
+    pub source: Option<String>,
+
+    /// Bookmark set by the user.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bookmark: Option<Bookmark>,
+}
+
+#[derive(serde::Deserialize, serde::Serialize, Debug, Default, Clone, PartialEq)]
+/// Bookmark of a trace, with a note and tags.
+pub struct Bookmark {
+    /// Free text note.
+    #[serde(default)]
+    pub note: String,
+
+    /// Tags, without duplicates.
+    #[serde(default)]
+    pub tags: Vec<String>,
+}
+
+impl Bookmark {
+    /// set the tags from a list separated by commas, empty tags and duplicates are removed
+    pub fn set_tags(&mut self, tags: &str) {
+        self.tags.clear();
+        for tag in tags.split(',').map(str::trim).filter(|tag| !tag.is_empty()) {
+            if !self.tags.iter().any(|one_tag| one_tag == tag) {
+                self.tags.push(tag.to_owned());
+            }
+        }
+    }
+
+    /// tags separated by commas
+    pub fn tags_text(&self) -> String {
+        self.tags.join(", ")
+    }
 }
 
 /// Data structure to store custom messages (from the amarisoft API)
-#[derive(Debug, Clone)]
+#[derive(serde::Deserialize, serde::Serialize, Debug, Clone)]
 pub enum AdditionalInfos {
     /// RRC message
     RRCInfos(RRCInfos),
@@ -30,6 +30,9 @@ pub enum ErrorCode {
     /// WebSocket: Error closing
     WebSocketErrorClosing,
 
+    /// WebSocket: Authentication failed
+    WebSocketAuthenticationFailed,
+
     /// File: No file selected
     FileNotSelected,
 
@@ -56,6 +59,24 @@ pub enum ErrorCode {
 
     /// ParsingLayerNotImplemented
     ParsingLayerNotImplemented,
+
+    /// Recording: Error writing the recording
+    RecordingErrorWriting,
+
+    /// Recording: Invalid recording
+    RecordingInvalid,
+
+    /// Mock server: Error starting the server
+    MockServerError,
+
+    /// Session: Error saving the session
+    SessionErrorWriting,
+
+    /// Session: Invalid session
+    SessionInvalid,
+
+    /// Search: Invalid pattern
+    SearchInvalidPattern,
 }
 
 impl Default for ErrorCode {
@@ -76,6 +97,7 @@ impl std::fmt::Display for ErrorCode {
             Self::WebSocketError => "WebSocket: Error",
             Self::WebSocketClosed => "WebSocket: Closed",
             Self::WebSocketErrorClosing => "WebSocket: Error closing",
+            Self::WebSocketAuthenticationFailed => "WebSocket: Authentication failed",
             Self::FileNotSelected => "File: No file selected",
             Self::FileErrorReadingFile => "File: Error reading file",
             Self::FileNotReady => "File: Not ready",
@@ -86,6 +108,12 @@ impl std::fmt::Display for ErrorCode {
             Self::FileParsing => "File: Parsing error",
             Self::RequestError => "Request error",
             Self::ParsingLayerNotImplemented => "Parsing layer not implemented",
+            Self::RecordingErrorWriting => "Recording: Error writing the recording",
+            Self::RecordingInvalid => "Recording: Invalid recording",
+            Self::MockServerError => "Mock server: Error starting the server",
+            Self::SessionErrorWriting => "Session: Error saving the session",
+            Self::SessionInvalid => "Session: Invalid session",
+            Self::SearchInvalidPattern => "Search: Invalid pattern",
         };
         write!(f, "{str}")
     }
@@ -94,7 +122,10 @@ impl std::fmt::Display for ErrorCode {
 impl ErrorCode {
     /// Check if the error is recoverable
     pub fn is_recoverable(&self) -> bool {
-        !matches!(self, Self::FileInvalidEncoding | Self::WebSocketClosed)
+        !matches!(
+            self,
+            Self::FileInvalidEncoding | Self::WebSocketClosed | Self::WebSocketAuthenticationFailed
+        )
     }
 }
 
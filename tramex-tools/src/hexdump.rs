@@ -0,0 +1,110 @@
+//! Hexadecimal dump of the payload of the traces
+//!
+//! Formatting of bytes (offset/hex/ASCII rows, hexadecimal, C array, base64) and bit-level access for PER-encoded
+//! fields, which are not aligned on bytes.
+use std::ops::Range;
+
+use base64::Engine;
+
+/// Number of bytes displayed per row
+pub const DEFAULT_WIDTH: usize = 16;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+/// Format of the bytes copied to the clipboard
+pub enum CopyFormat {
+    /// Hexadecimal bytes separated by spaces: `0a 1b 3c`
+    #[default]
+    Hex,
+
+    /// C array: `const uint8_t data[3] = { 0x0a, 0x1b, 0x3c };`
+    CArray,
+
+    /// Base64 (standard alphabet, padded)
+    Base64,
+}
+
+impl CopyFormat {
+    /// All the formats
+    pub const ALL: [CopyFormat; 3] = [CopyFormat::Hex, CopyFormat::CArray, CopyFormat::Base64];
+
+    /// Name of the format
+    pub fn label(&self) -> &'static str {
+        match self {
+            CopyFormat::Hex => "Hex",
+            CopyFormat::CArray => "C array",
+            CopyFormat::Base64 => "Base64",
+        }
+    }
+
+    /// Format bytes
+    pub fn format(&self, bytes: &[u8]) -> String {
+        match self {
+            CopyFormat::Hex => to_hex(bytes),
+            CopyFormat::CArray => {
+                let values: Vec<String> = bytes.iter().map(|byte| format!("0x{byte:02x}")).collect();
+                format!("const uint8_t data[{}] = {{ {} }};", bytes.len(), values.join(", "))
+            }
+            CopyFormat::Base64 => base64::engine::general_purpose::STANDARD.encode(bytes),
+        }
+    }
+}
+
+/// Hexadecimal bytes separated by spaces
+pub fn to_hex(bytes: &[u8]) -> String {
+    let values: Vec<String> = bytes.iter().map(|byte| format!("{byte:02x}")).collect();
+    values.join(" ")
+}
+
+/// Character of a byte in the ASCII column, `.` if not printable
+pub fn ascii_char(byte: u8) -> char {
+    match byte {
+        0x20..=0x7e => byte as char,
+        _ => '.',
+    }
+}
+
+/// Rows of a dump: offset of the first byte and bytes of each row
+pub fn rows(bytes: &[u8], width: usize) -> impl Iterator<Item = (usize, &[u8])> {
+    let width = width.max(1);
+    bytes.chunks(width).enumerate().map(move |(index, row)| (index * width, row))
+}
+
+/// Text dump of bytes, one row per line: offset, hexadecimal bytes and ASCII column
+pub fn dump(bytes: &[u8], width: usize) -> String {
+    let width = width.max(1);
+    rows(bytes, width)
+        .map(|(offset, row)| {
+            let ascii: String = row.iter().map(|byte| ascii_char(*byte)).collect();
+            format!(
+                "{offset:04x}:  {:<hex_width$}  {ascii}",
+                to_hex(row),
+                hex_width = width * 3 - 1
+            )
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+/// Value of a bit, bits are numbered from the most significant bit of the first byte as in PER
+pub fn bit(bytes: &[u8], index: usize) -> Option<bool> {
+    bytes.get(index / 8).map(|byte| byte & (0x80 >> (index % 8)) != 0)
+}
+
+/// Bits of a range as `0` and `1`, None if the range is out of the bytes
+pub fn bits_string(bytes: &[u8], bits: Range<usize>) -> Option<String> {
+    if bits.end > bytes.len() * 8 {
+        return None;
+    }
+    bits.map(|index| bit(bytes, index).map(|set| if set { '1' } else { '0' }))
+        .collect()
+}
+
+/// Unsigned value of a range of bits (most significant bit first), None if the range is out of the bytes or longer
+/// than 64 bits
+pub fn read_bits(bytes: &[u8], bits: Range<usize>) -> Option<u64> {
+    if bits.len() > 64 || bits.end > bytes.len() * 8 {
+        return None;
+    }
+    bits.map(|index| bit(bytes, index))
+        .try_fold(0u64, |value, set| set.map(|set| (value << 1) | u64::from(set)))
+}
@@ -2,43 +2,180 @@
 
 use crate::{
     errors::{ErrorCode, TramexError},
+    hexdump::ascii_char,
+    interface::parser::ParsingError,
     tramex_error,
 };
 
-/// Extract hexadecimal data from a vector of strings.
-/// # Errors
-/// Returns a TramexError if the hexe representation could not be extracted.
-pub fn extract_hexe<T: AsRef<str>>(data: &[T]) -> Result<Vec<u8>, TramexError> {
-    let iter = data.iter().filter(|one_string| {
-        if let Some(first_char) = one_string.as_ref().trim().chars().next() {
-            return first_char.is_numeric();
+/// Row of a hexadecimal dump: `0010:  73 39 4f 52 d5 42 48 00  18 01 2e 38 03 84 28 c5  s9OR.BH....8..(.`
+struct HexRow<'a> {
+    /// Index of the line in the dump
+    line: usize,
+
+    /// Line, without its trailing spaces
+    text: &'a str,
+
+    /// Offset of the first byte of the row
+    offset: usize,
+
+    /// Position of the offset in the line
+    offset_start: usize,
+
+    /// Position of the bytes in the line, after the offset
+    bytes_start: usize,
+}
+
+impl<'a> HexRow<'a> {
+    /// Read the offset of a row, None if the line is not a row of a dump (such as the numbers of the decoded message
+    /// or the time of the log)
+    fn new(line: usize, text: &'a str) -> Option<Self> {
+        let text = text.trim_end();
+        let offset_start = text.len() - text.trim_start().len();
+        let (offset, rest) = text[offset_start..].split_once(':')?;
+        if !offset.chars().next().is_some_and(|c| c.is_numeric())
+            || !offset.chars().all(|c| c.is_ascii_hexdigit())
+            || rest.chars().next().is_some_and(|c| !c.is_whitespace())
+        {
+            return None;
         }
-        false
-    });
-    let mut data: Vec<String> = Vec::new();
-    for one_string in iter {
-        let trimmed = one_string.as_ref().trim();
-        if trimmed.len() > 57 {
-            let str_piece = &trimmed[7..56];
-            let chars_only: String = str_piece.chars().filter(|c| !c.is_whitespace()).collect();
-            data.push(chars_only);
-        } else {
-            return Err(tramex_error!(
-                format!("Error decoding hexe {:?} ({})", trimmed, trimmed.len()),
-                ErrorCode::HexeDecodingError
-            ));
+        Some(Self {
+            line,
+            text,
+            offset: usize::from_str_radix(offset, 16).ok()?,
+            offset_start,
+            bytes_start: offset_start + offset.len() + 1,
+        })
+    }
+
+    /// Error at a position of the row
+    fn error(&self, message: String, position: usize) -> ParsingError {
+        ParsingError::new(message, self.line as u64).with_column(position as u64 + 1)
+    }
+
+    /// Words of the row after the offset, with their position in the line
+    fn words(&self) -> Vec<(usize, &'a str)> {
+        let mut words = vec![];
+        let mut start = None;
+        for (position, c) in self
+            .text
+            .char_indices()
+            .skip_while(|(position, _)| *position < self.bytes_start)
+        {
+            match (c.is_whitespace(), start) {
+                (true, Some(word_start)) => {
+                    words.push((word_start, &self.text[word_start..position]));
+                    start = None;
+                }
+                (false, None) => start = Some(position),
+                _ => (),
+            }
         }
+        if let Some(word_start) = start {
+            words.push((word_start, &self.text[word_start..]));
+        }
+        words
     }
-    let mut hexe: Vec<u8> = Vec::new();
-    for one_string in data {
-        let mut i = 0;
-        while i < one_string.len() {
-            let hex = &one_string[i..i + 2];
-            if let Ok(hexa) = u8::from_str_radix(hex, 16) {
-                hexe.push(hexa);
+
+    /// Read the bytes of the row and check them against its ASCII column
+    ///
+    /// The number of bytes of a row is not fixed: it is the biggest number of hexadecimal words rendered as the rest
+    /// of the row.
+    fn bytes(&self) -> Result<Vec<u8>, ParsingError> {
+        let words = self.words();
+        let bytes: Vec<u8> = words
+            .iter()
+            .map_while(|(_, word)| match word.len() {
+                2 => u8::from_str_radix(word, 16).ok(),
+                _ => None,
+            })
+            .collect();
+        let ascii_of = |nb_bytes: usize| match words.get(nb_bytes) {
+            Some((position, _)) => (*position, &self.text[*position..]),
+            None => (self.text.len(), ""),
+        };
+        for nb_bytes in (1..=bytes.len()).rev() {
+            let rendered: String = bytes[..nb_bytes].iter().map(|byte| ascii_char(*byte)).collect();
+            if rendered.trim() == ascii_of(nb_bytes).1 {
+                return Ok(bytes[..nb_bytes].to_vec());
+            }
+        }
+        let (position, ascii) = ascii_of(bytes.len());
+        match words.get(bytes.len()) {
+            _ if bytes.is_empty() && words.is_empty() => Err(self.error("No byte in the row".to_string(), position)),
+            Some((position, word)) if word.len() == 2 && words.len() > bytes.len() + 1 => {
+                Err(self.error(format!("Invalid hexadecimal byte {word:?}"), *position))
+            }
+            None => Err(self.error("Missing ASCII column".to_string(), position)),
+            Some(_) => {
+                let rendered: Vec<char> = bytes.iter().map(|byte| ascii_char(*byte)).collect();
+                let skipped = rendered.iter().take_while(|c| c.is_whitespace()).count();
+                let mismatch = ascii
+                    .char_indices()
+                    .zip(&rendered[skipped..])
+                    .find(|((_, found), expected)| found != *expected);
+                match mismatch {
+                    Some(((index, found), _)) => {
+                        let byte = bytes[skipped + ascii[..index].chars().count()];
+                        Err(self.error(
+                            format!("ASCII column {found:?} doesn't match the byte {byte:02x}"),
+                            position + index,
+                        ))
+                    }
+                    None => Err(self.error(
+                        format!(
+                            "ASCII column of {} characters for {} bytes",
+                            ascii.chars().count(),
+                            bytes.len()
+                        ),
+                        position,
+                    )),
+                }
             }
-            i += 2;
         }
     }
+}
+
+/// Parse a hexadecimal dump, checking the offsets of its rows and their ASCII column.
+///
+/// The lines which are not rows of the dump, starting with an offset such as `0010:`, are skipped. The rows can have
+/// any number of bytes, but each offset must follow the bytes of the previous rows.
+/// # Errors
+/// Returns a ParsingError with the index of the line in `data` and the column of the first invalid character.
+pub fn parse_hexe<T: AsRef<str>>(data: &[T]) -> Result<Vec<u8>, ParsingError> {
+    let mut hexe: Vec<u8> = Vec::new();
+    for (line, text) in data.iter().enumerate() {
+        let Some(row) = HexRow::new(line, text.as_ref()) else {
+            continue;
+        };
+        if row.offset != hexe.len() {
+            return Err(row.error(
+                format!(
+                    "Offset {:04x} doesn't follow the {} bytes read, expected {:04x}",
+                    row.offset,
+                    hexe.len(),
+                    hexe.len()
+                ),
+                row.offset_start,
+            ));
+        }
+        hexe.extend(row.bytes()?);
+    }
     Ok(hexe)
 }
+
+/// Extract hexadecimal data from a vector of strings.
+/// # Errors
+/// Returns a TramexError if the hexe representation could not be extracted, with the line and the column of the error.
+pub fn extract_hexe<T: AsRef<str>>(data: &[T]) -> Result<Vec<u8>, TramexError> {
+    parse_hexe(data).map_err(|err| {
+        tramex_error!(
+            format!(
+                "Error decoding hexe: {} (line {}, column {})",
+                err.message,
+                err.line_idx + 1,
+                err.column.unwrap_or_default()
+            ),
+            ErrorCode::HexeDecodingError
+        )
+    })
+}
@@ -9,10 +9,17 @@ use crate::interface::layer::Layers;
 use crate::tramex_error;
 use std::path::PathBuf;
 
-use super::utils_file::parse_one_block;
+use super::utils_file::{block_layer, parse_one_block};
 
 /// The default number of log processed by batch
 const DEFAULT_NB: usize = 50;
+
+/// Number of batches of hidden logs skipped at most in a single read, so a file mostly hidden is still read over
+/// several frames
+const HIDDEN_BATCHES: usize = 4;
+
+/// Number of milliseconds in a day
+pub const MS_IN_DAY: u64 = 86_400_000;
 #[derive(Debug, Clone)]
 /// Data structure to store the file.
 pub struct File {
@@ -33,6 +40,18 @@ pub struct File {
 
     /// Available
     pub available: bool,
+
+    /// Offset added to the timestamps to correct the clock of the machine that wrote the file (milliseconds)
+    pub clock_offset_ms: i64,
+
+    /// Days elapsed since the start of the file (milliseconds), the log times only give the time of the day
+    day_offset_ms: u64,
+
+    /// Time of the day of the last log read (milliseconds)
+    last_time_of_day: Option<u64>,
+
+    /// Name of the source when the file name is already used by another file
+    source_name: Option<String>,
 }
 
 impl Default for File {
@@ -44,17 +63,30 @@ impl Default for File {
             nb_read: DEFAULT_NB,
             index_line: 0,
             available: true,
+            clock_offset_ms: 0,
+            day_offset_ms: 0,
+            last_time_of_day: None,
+            source_name: None,
         }
     }
 }
 
 impl InterfaceTrait for File {
-    fn get_more_data(&mut self, _layer_list: Layers, data: &mut Data) -> Result<(), Vec<TramexError>> {
+    fn get_more_data(&mut self, layer_list: Layers, data: &mut Data) -> Result<(), Vec<TramexError>> {
         if self.full_read {
             return Ok(());
         }
-        let (mut traces, err_processed) = self.process();
-        data.events.append(&mut traces);
+        let (traces, err_processed) = self.process_layers(&layer_list);
+        let source = self.source();
+        for mut trace in traces {
+            trace.timestamp = self.absolute_timestamp(trace.timestamp);
+            trace.source = source.clone();
+            if layer_list.shows(&trace.layer) {
+                data.push_ordered(trace);
+            } else {
+                data.push_hidden(trace);
+            }
+        }
         if !err_processed.is_empty() {
             let filtered = err_processed
                 .iter()
@@ -81,6 +113,7 @@ impl File {
             nb_read: DEFAULT_NB,
             index_line: 0,
             available: true,
+            ..Default::default()
         }
     }
 
@@ -93,6 +126,50 @@ impl File {
             nb_read: DEFAULT_NB,
             index_line: 0,
             available: true,
+            ..Default::default()
+        }
+    }
+
+    /// Source of the traces: name of the file, None if the path has no file name
+    pub fn source(&self) -> Option<String> {
+        if let Some(source_name) = &self.source_name {
+            return Some(source_name.clone());
+        }
+        self.file_path.file_name().map(|name| name.to_string_lossy().into_owned())
+    }
+
+    /// Rename the source if it is already used, adding a number such as `enb.log #2`
+    pub fn make_source_unique(&mut self, sources: &[String]) {
+        let Some(source) = self.source() else {
+            return;
+        };
+        if !sources.contains(&source) {
+            return;
+        }
+        self.source_name = (2..)
+            .map(|number| format!("{source} #{number}"))
+            .find(|name| !sources.contains(name));
+    }
+
+    /// Timestamp of a log from its time of the day, with the days elapsed since the start of the file and the clock offset
+    ///
+    /// A time more than 12 hours before the previous one means the log was written after midnight.
+    pub fn absolute_timestamp(&mut self, time_of_day: u64) -> u64 {
+        if let Some(last_time_of_day) = self.last_time_of_day {
+            if time_of_day + MS_IN_DAY / 2 < last_time_of_day {
+                self.day_offset_ms += MS_IN_DAY;
+            }
+        }
+        self.last_time_of_day = Some(time_of_day);
+        (time_of_day as i64 + self.day_offset_ms as i64 + self.clock_offset_ms).max(0) as u64
+    }
+
+    /// Change the clock offset, shifting the traces of the file already loaded
+    pub fn set_clock_offset(&mut self, clock_offset_ms: i64, data: &mut Data) {
+        let delta_ms = clock_offset_ms - self.clock_offset_ms;
+        self.clock_offset_ms = clock_offset_ms;
+        if let (Some(source), true) = (self.source(), delta_ms != 0) {
+            data.shift_source(&source, delta_ms);
         }
     }
 
@@ -104,23 +181,64 @@ impl File {
     /// To process the file and parse a batch of log
     pub fn process(&mut self) -> (Vec<Trace>, Vec<TramexError>) {
         let (vec_trace, opt_err) = File::process_string(&self.file_content, self.nb_read, &mut self.index_line);
-        for one_error in &opt_err {
+        self.update_full_read(&opt_err);
+        (vec_trace, opt_err)
+    }
+
+    /// To process the file and parse a batch of log of the layers shown, see `process_string_layers`
+    pub fn process_layers(&mut self, layers: &Layers) -> (Vec<Trace>, Vec<TramexError>) {
+        let (vec_trace, opt_err) =
+            File::process_string_layers(&self.file_content, self.nb_read, &mut self.index_line, Some(layers));
+        self.update_full_read(&opt_err);
+        (vec_trace, opt_err)
+    }
+
+    /// Set the full read status if the end of the file is reached
+    fn update_full_read(&mut self, opt_err: &[TramexError]) {
+        for one_error in opt_err {
             if matches!(one_error.get_code(), ErrorCode::EndOfFile) {
                 self.full_read = true;
             }
         }
-        (vec_trace, opt_err)
     }
+
     /// To process a string passed in argument, with index and batch to read
     pub fn process_string(lines: &[String], nb_to_read: usize, ix: &mut usize) -> (Vec<Trace>, Vec<TramexError>) {
+        File::process_string_layers(lines, nb_to_read, ix, None)
+    }
+
+    /// To process a string passed in argument, with index, batch to read and the layers shown
+    ///
+    /// The logs of the hidden layers are not counted in the batch: they are returned when they can be parsed,
+    /// to be shown once their layer is, and their errors are ignored. At most [`HIDDEN_BATCHES`] batches of them are
+    /// skipped in a call.
+    pub fn process_string_layers(
+        lines: &[String],
+        nb_to_read: usize,
+        ix: &mut usize,
+        layers: Option<&Layers>,
+    ) -> (Vec<Trace>, Vec<TramexError>) {
         let mut traces = vec![];
         let mut errors = vec![];
-        for _ in 0..nb_to_read {
+        let mut nb_read = 0;
+        let mut nb_hidden = 0;
+        while nb_read < nb_to_read && nb_hidden < nb_to_read * HIDDEN_BATCHES {
             if *ix >= lines.len() {
                 errors.push(tramex_error!("End of file".to_string(), ErrorCode::EndOfFile));
                 break;
             }
-            match parse_one_block(&lines[*ix..], ix) {
+            let hidden = layers.is_some_and(|layers| block_layer(&lines[*ix..]).is_some_and(|layer| !layers.shows(&layer)));
+            let previous_ix = *ix;
+            let result = parse_one_block(&lines[*ix..], ix);
+            if hidden && *ix > previous_ix {
+                nb_hidden += 1;
+                if let Ok(trace) = result {
+                    traces.push(trace);
+                }
+                continue;
+            }
+            nb_read += 1;
+            match result {
                 Ok(trace) => {
                     traces.push(trace);
                 }
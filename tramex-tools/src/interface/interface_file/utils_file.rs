@@ -96,3 +96,12 @@ pub fn parse_one_block(lines: &[String], ix: &mut usize) -> Result<Trace, Tramex
         None => Err(eof_error(copy_ix as u64)),
     }
 }
+
+/// Layer of the next log, None if its first line has no known layer
+pub fn block_layer(lines: &[String]) -> Option<Layer> {
+    let first_line = lines.iter().find(|line| {
+        !(line.starts_with('#') || line.starts_with(' ') || line.starts_with('\t') || line.trim().is_empty())
+    })?;
+    let layer = first_line.split_whitespace().nth(1)?;
+    Layer::from_str(layer.trim_start_matches('[').trim_end_matches(']')).ok()
+}
@@ -11,7 +11,7 @@ use crate::interface::websocket::ws_connection::WsConnection;
 pub enum Interface {
     /// WebSocket connection
     #[cfg(feature = "websocket")]
-    Ws(WsConnection),
+    Ws(Box<WsConnection>),
 
     /// File
     File(File),
@@ -1,4 +1,5 @@
 //! Layer enum and Layers struct
+use std::collections::BTreeMap;
 use std::fmt;
 use std::fmt::Display;
 use std::fmt::Formatter;
@@ -77,104 +78,210 @@ impl FromStr for Layer {
     }
 }
 
-#[derive(Debug, serde::Deserialize, Clone, Default)]
-/// LayerLogLevel enum
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "lowercase")]
+/// LayerLogLevel enum, from the least to the most verbose
 pub enum LayerLogLevel {
-    /// Debug log level
-    Debug,
+    /// No log
+    #[serde(alias = "None")]
+    None,
+
+    /// Error log level
+    #[serde(alias = "Error")]
+    Error,
 
     #[default]
     /// Warn log level
+    #[serde(alias = "Warn")]
     Warn,
+
+    /// Info log level
+    #[serde(alias = "Info")]
+    Info,
+
+    /// Debug log level
+    #[serde(alias = "Debug")]
+    Debug,
 }
 
-impl serde::Serialize for LayerLogLevel {
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: serde::Serializer,
-    {
-        match *self {
-            LayerLogLevel::Debug => serializer.serialize_str("debug"),
-            LayerLogLevel::Warn => serializer.serialize_str("warn"),
-        }
-    }
+impl LayerLogLevel {
+    /// Every log level, from the least to the most verbose
+    pub const ALL: [LayerLogLevel; 5] = [
+        LayerLogLevel::None,
+        LayerLogLevel::Error,
+        LayerLogLevel::Warn,
+        LayerLogLevel::Info,
+        LayerLogLevel::Debug,
+    ];
 }
 
 impl Display for LayerLogLevel {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
         match self {
-            LayerLogLevel::Debug => write!(f, "debug"),
+            LayerLogLevel::None => write!(f, "none"),
+            LayerLogLevel::Error => write!(f, "error"),
             LayerLogLevel::Warn => write!(f, "warn"),
+            LayerLogLevel::Info => write!(f, "info"),
+            LayerLogLevel::Debug => write!(f, "debug"),
         }
     }
 }
 
-#[derive(serde::Serialize, serde::Deserialize, Default, Debug, Clone)]
-/// Layers struct
+/// Default maximum size of the hexadecimal dump of a log (bytes)
+pub const DEFAULT_MAX_SIZE: u32 = 1;
+
+#[derive(serde::Serialize, Debug, Clone, Copy, PartialEq)]
+/// Log configuration of a layer
+pub struct LayerConfig {
+    /// Log level
+    pub level: LayerLogLevel,
+
+    /// Maximum size of the hexadecimal dump of a log (bytes)
+    pub max_size: u32,
+
+    /// Log the payload of the messages
+    pub payload: bool,
+}
+
+impl Default for LayerConfig {
+    fn default() -> Self {
+        Self {
+            level: LayerLogLevel::default(),
+            max_size: DEFAULT_MAX_SIZE,
+            payload: false,
+        }
+    }
+}
+
+impl LayerConfig {
+    /// Configuration at a log level, with the default max size and without payload
+    pub fn with_level(level: LayerLogLevel) -> Self {
+        Self {
+            level,
+            ..Default::default()
+        }
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for LayerConfig {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        /// Full configuration, every field is optional
+        #[derive(serde::Deserialize)]
+        #[serde(default)]
+        struct FullConfig {
+            /// Log level
+            level: LayerLogLevel,
+
+            /// Maximum size of the hexadecimal dump
+            max_size: u32,
+
+            /// Log the payload
+            payload: bool,
+        }
+
+        impl Default for FullConfig {
+            fn default() -> Self {
+                let config = LayerConfig::default();
+                Self {
+                    level: config.level,
+                    max_size: config.max_size,
+                    payload: config.payload,
+                }
+            }
+        }
+
+        /// A layer is configured by its level only or by a full configuration
+        #[derive(serde::Deserialize)]
+        #[serde(untagged)]
+        enum Repr {
+            /// Level only
+            Level(LayerLogLevel),
+
+            /// Full configuration
+            Full(FullConfig),
+        }
+
+        Ok(match Repr::deserialize(deserializer)? {
+            Repr::Level(level) => LayerConfig::with_level(level),
+            Repr::Full(full) => LayerConfig {
+                level: full.level,
+                max_size: full.max_size,
+                payload: full.payload,
+            },
+        })
+    }
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Default, Debug, Clone, PartialEq)]
+#[serde(default)]
+/// Log configuration of every layer
 pub struct Layers {
-    #[serde(rename(serialize = "PHY"))]
+    #[serde(rename = "PHY")]
     /// Physical layer
-    pub phy: LayerLogLevel,
+    pub phy: LayerConfig,
 
-    #[serde(rename(serialize = "MAC"))]
+    #[serde(rename = "MAC")]
     /// Medium Access Control layer
-    pub mac: LayerLogLevel,
+    pub mac: LayerConfig,
 
-    #[serde(rename(serialize = "RLC"))]
+    #[serde(rename = "RLC")]
     /// Radio Link Control layer
-    pub rlc: LayerLogLevel,
+    pub rlc: LayerConfig,
 
-    #[serde(rename(serialize = "PDCP"))]
+    #[serde(rename = "PDCP")]
     /// Packet Data Convergence Protocol layer
-    pub pdcp: LayerLogLevel,
+    pub pdcp: LayerConfig,
 
-    #[serde(rename(serialize = "RRC"))]
+    #[serde(rename = "RRC")]
     /// Radio Resource Control layer
-    pub rrc: LayerLogLevel,
+    pub rrc: LayerConfig,
 
-    #[serde(rename(serialize = "NAS"))]
+    #[serde(rename = "NAS")]
     /// Non Access Stratum layer
-    pub nas: LayerLogLevel,
+    pub nas: LayerConfig,
 
-    #[serde(rename(serialize = "S72"))]
+    #[serde(rename = "S72")]
     /// S1 Application Protocol layer
-    pub s72: LayerLogLevel,
+    pub s72: LayerConfig,
 
-    #[serde(rename(serialize = "S1AP"))]
+    #[serde(rename = "S1AP")]
     /// S1 Application Protocol layer
-    pub s1ap: LayerLogLevel,
+    pub s1ap: LayerConfig,
 
-    #[serde(rename(serialize = "NGAP"))]
+    #[serde(rename = "NGAP")]
     /// Next Generation Application Protocol layer
-    pub ngap: LayerLogLevel,
+    pub ngap: LayerConfig,
 
-    #[serde(rename(serialize = "GTPU"))]
+    #[serde(rename = "GTPU")]
     /// GTPU layer
-    pub gtpu: LayerLogLevel,
+    pub gtpu: LayerConfig,
 
-    #[serde(rename(serialize = "X2AP"))]
+    #[serde(rename = "X2AP")]
     /// X2 Application Protocol layer
-    pub x2ap: LayerLogLevel,
+    pub x2ap: LayerConfig,
 
-    #[serde(rename(serialize = "XnAP"))]
+    #[serde(rename = "XnAP")]
     /// Xn Application Protocol layer
-    pub xnap: LayerLogLevel,
+    pub xnap: LayerConfig,
 
-    #[serde(rename(serialize = "M2AP"))]
+    #[serde(rename = "M2AP")]
     /// M2 Application Protocol layer
-    pub m2ap: LayerLogLevel,
+    pub m2ap: LayerConfig,
 
-    #[serde(rename(serialize = "LPPa"))]
+    #[serde(rename = "LPPa")]
     /// LTE Positioning Protocol A layer
-    pub lppa: LayerLogLevel,
+    pub lppa: LayerConfig,
 
-    #[serde(rename(serialize = "NRPPa"))]
+    #[serde(rename = "NRPPa")]
     /// NR Positioning Protocol A layer
-    pub nrppa: LayerLogLevel,
+    pub nrppa: LayerConfig,
 
-    #[serde(rename(serialize = "TRX"))]
+    #[serde(rename = "TRX")]
     /// TRX layer
-    pub trx: LayerLogLevel,
+    pub trx: LayerConfig,
 }
 
 impl Layers {
@@ -186,29 +293,141 @@ impl Layers {
     /// Create new Layers but in an optiniated way
     pub fn new_optiniated() -> Self {
         let mut layers = Layers::new();
-        layers.rrc = LayerLogLevel::Debug;
+        layers.rrc = LayerConfig::with_level(LayerLogLevel::Debug);
         layers
     }
 
     /// Create new Layers struct with all debug
     pub fn all_debug() -> Self {
+        let mut layers = Layers::new();
+        for (_, config) in layers.iter_mut() {
+            config.level = LayerLogLevel::Debug;
+        }
+        layers
+    }
+
+    /// Configuration of every layer, with the name used by the remote API
+    pub fn iter(&self) -> [(&'static str, &LayerConfig); 16] {
+        [
+            ("PHY", &self.phy),
+            ("MAC", &self.mac),
+            ("RLC", &self.rlc),
+            ("PDCP", &self.pdcp),
+            ("RRC", &self.rrc),
+            ("NAS", &self.nas),
+            ("S72", &self.s72),
+            ("S1AP", &self.s1ap),
+            ("NGAP", &self.ngap),
+            ("GTPU", &self.gtpu),
+            ("X2AP", &self.x2ap),
+            ("XnAP", &self.xnap),
+            ("M2AP", &self.m2ap),
+            ("LPPa", &self.lppa),
+            ("NRPPa", &self.nrppa),
+            ("TRX", &self.trx),
+        ]
+    }
+
+    /// Mutable configuration of every layer, with the name used by the remote API
+    pub fn iter_mut(&mut self) -> [(&'static str, &mut LayerConfig); 16] {
+        [
+            ("PHY", &mut self.phy),
+            ("MAC", &mut self.mac),
+            ("RLC", &mut self.rlc),
+            ("PDCP", &mut self.pdcp),
+            ("RRC", &mut self.rrc),
+            ("NAS", &mut self.nas),
+            ("S72", &mut self.s72),
+            ("S1AP", &mut self.s1ap),
+            ("NGAP", &mut self.ngap),
+            ("GTPU", &mut self.gtpu),
+            ("X2AP", &mut self.x2ap),
+            ("XnAP", &mut self.xnap),
+            ("M2AP", &mut self.m2ap),
+            ("LPPa", &mut self.lppa),
+            ("NRPPa", &mut self.nrppa),
+            ("TRX", &mut self.trx),
+        ]
+    }
+
+    /// Configuration of a layer, None for the layers without configuration
+    pub fn get(&self, layer: &Layer) -> Option<&LayerConfig> {
+        match layer {
+            Layer::PHY => Some(&self.phy),
+            Layer::MAC => Some(&self.mac),
+            Layer::RLC => Some(&self.rlc),
+            Layer::PDCP => Some(&self.pdcp),
+            Layer::RRC => Some(&self.rrc),
+            Layer::NAS => Some(&self.nas),
+            Layer::S1AP => Some(&self.s1ap),
+            Layer::NGAP => Some(&self.ngap),
+            Layer::X2AP => Some(&self.x2ap),
+            Layer::XNAP => Some(&self.xnap),
+            Layer::M2AP => Some(&self.m2ap),
+            Layer::LPPA => Some(&self.lppa),
+            Layer::NRPPA => Some(&self.nrppa),
+            Layer::GTPU => Some(&self.gtpu),
+            Layer::PROD => None,
+        }
+    }
+
+    /// Check if the logs of a layer are shown: its level is not none
+    pub fn shows(&self, layer: &Layer) -> bool {
+        self.get(layer).is_none_or(|config| config.level != LayerLogLevel::None)
+    }
+
+    /// Log level of every layer, the layer filter of a log_get
+    pub fn levels(&self) -> BTreeMap<String, LayerLogLevel> {
+        self.iter()
+            .into_iter()
+            .map(|(name, config)| (name.to_owned(), config.level))
+            .collect()
+    }
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq)]
+/// Named log configuration
+pub struct LayersPreset {
+    /// Name of the preset
+    pub name: String,
+
+    /// Log configuration
+    pub layers: Layers,
+}
+
+impl LayersPreset {
+    /// Create a new preset
+    pub fn new(name: &str, layers: Layers) -> Self {
         Self {
-            phy: LayerLogLevel::Debug,
-            mac: LayerLogLevel::Debug,
-            rlc: LayerLogLevel::Debug,
-            pdcp: LayerLogLevel::Debug,
-            rrc: LayerLogLevel::Debug,
-            nas: LayerLogLevel::Debug,
-            s72: LayerLogLevel::Debug,
-            s1ap: LayerLogLevel::Debug,
-            ngap: LayerLogLevel::Debug,
-            gtpu: LayerLogLevel::Debug,
-            x2ap: LayerLogLevel::Debug,
-            xnap: LayerLogLevel::Debug,
-            m2ap: LayerLogLevel::Debug,
-            lppa: LayerLogLevel::Debug,
-            nrppa: LayerLogLevel::Debug,
-            trx: LayerLogLevel::Debug,
+            name: name.to_owned(),
+            layers,
+        }
+    }
+
+    /// Presets provided by tramex
+    pub fn builtin() -> Vec<LayersPreset> {
+        let mut rrc_only = Layers::new();
+        for (_, config) in rrc_only.iter_mut() {
+            config.level = LayerLogLevel::None;
+        }
+        rrc_only.rrc.level = LayerLogLevel::Debug;
+
+        let mut attach = Layers::new();
+        for config in [&mut attach.rrc, &mut attach.nas, &mut attach.s1ap, &mut attach.ngap] {
+            config.level = LayerLogLevel::Debug;
         }
+        attach.pdcp.level = LayerLogLevel::Info;
+
+        let mut phy_scheduling = Layers::new();
+        phy_scheduling.phy.level = LayerLogLevel::Debug;
+        phy_scheduling.mac.level = LayerLogLevel::Debug;
+        phy_scheduling.rrc.level = LayerLogLevel::Info;
+
+        vec![
+            LayersPreset::new("RRC only", rrc_only),
+            LayersPreset::new("Attach debugging", attach),
+            LayersPreset::new("PHY scheduling", phy_scheduling),
+            LayersPreset::new("Everything", Layers::all_debug()),
+        ]
     }
 }
@@ -1,5 +1,80 @@
 //! LogGet struct for sending log_get message to the server
-use crate::interface::layer::Layers;
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, NaiveDate, NaiveTime};
+
+use crate::interface::layer::{LayerLogLevel, Layers};
+
+/// Default timeout of a log_get (seconds)
+pub const DEFAULT_TIMEOUT: u64 = 1;
+
+/// Default minimum number of logs of a log_get
+pub const DEFAULT_MIN: u64 = 64;
+
+/// Timestamp (milliseconds since the epoch) of a bound of a range of logs, None if it can't be read
+///
+/// The bound is a date and a time in UTC, `YYYY-MM-DD HH:MM:SS.mmm`, or only a time for the day of `now_ms`.
+pub fn parse_range_bound(text: &str, now_ms: i64) -> Option<u64> {
+    let text = text.trim();
+    let (date, time) = match text.split_once([' ', 'T']) {
+        Some((date, time)) => (NaiveDate::parse_from_str(date, "%Y-%m-%d").ok()?, time.trim()),
+        None => (DateTime::from_timestamp_millis(now_ms)?.date_naive(), text),
+    };
+    let time = NaiveTime::parse_from_str(time, "%H:%M:%S%.f").ok()?;
+    u64::try_from(date.and_time(time).and_utc().timestamp_millis()).ok()
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+/// Options of the log_get requests, set by the user
+pub struct LogGetOptions {
+    /// Time the server waits for `min` logs (seconds)
+    pub timeout: u64,
+
+    /// Minimum number of logs before the server answers (unless the timeout expires)
+    pub min: u64,
+
+    /// Ask for the log headers
+    pub headers: bool,
+
+    /// Ask for the first line of the logs only
+    pub short: bool,
+
+    /// Answer even if there is no log
+    pub allow_empty: bool,
+
+    /// Only logs from this timestamp (milliseconds)
+    pub start_timestamp: Option<u64>,
+
+    /// Only logs until this timestamp (milliseconds)
+    pub end_timestamp: Option<u64>,
+
+    /// Only logs of this UE
+    pub ue_id: Option<u64>,
+
+    /// Only logs of this RNTI
+    pub rnti: Option<u64>,
+
+    /// Only logs of this cell
+    pub cell_id: Option<u64>,
+}
+
+impl Default for LogGetOptions {
+    fn default() -> Self {
+        Self {
+            timeout: DEFAULT_TIMEOUT,
+            min: DEFAULT_MIN,
+            headers: false,
+            short: false,
+            allow_empty: false,
+            start_timestamp: None,
+            end_timestamp: None,
+            ue_id: None,
+            rnti: None,
+            cell_id: None,
+        }
+    }
+}
 
 #[derive(serde::Serialize, serde::Deserialize, Debug)]
 /// LogGet struct
@@ -13,8 +88,8 @@ pub struct LogGet {
     /// Maximum size of the log
     max: u64,
 
-    /// Layers
-    layers: Layers,
+    /// Log level of each layer
+    layers: BTreeMap<String, LayerLogLevel>,
 
     /// Message
     message: String,
@@ -24,20 +99,135 @@ pub struct LogGet {
 
     /// Message ID
     message_id: u64,
+
+    /// Only the first line of the logs
+    #[serde(skip_serializing_if = "std::ops::Not::not", default)]
+    short: bool,
+
+    /// Answer even if there is no log
+    #[serde(skip_serializing_if = "std::ops::Not::not", default)]
+    allow_empty: bool,
+
+    /// Only logs from this timestamp (milliseconds)
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    start_timestamp: Option<u64>,
+
+    /// Only logs until this timestamp (milliseconds)
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    end_timestamp: Option<u64>,
+
+    /// Only logs of this UE
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    ue_id: Option<u64>,
+
+    /// Only logs of this RNTI
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    rnti: Option<u64>,
+
+    /// Only logs of this cell
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    cell_id: Option<u64>,
 }
 
 impl LogGet {
-    /// Create a new LogGet struct
+    /// Create a new LogGet struct, with the default options
     pub fn new(id: u64, layers_list: Layers, max_size: u64) -> Self {
-        let max_size = max_size.clamp(64, 4096);
         Self {
-            timeout: 1,
-            min: 64,
-            max: max_size,
-            layers: layers_list,
+            timeout: DEFAULT_TIMEOUT,
+            min: DEFAULT_MIN,
+            max: max_size.max(DEFAULT_MIN),
+            layers: layers_list.levels(),
             message: "log_get".to_owned(),
             headers: false,
             message_id: id,
+            short: false,
+            allow_empty: false,
+            start_timestamp: None,
+            end_timestamp: None,
+            ue_id: None,
+            rnti: None,
+            cell_id: None,
         }
     }
+
+    /// Apply the options of the user, the start timestamp is left unchanged
+    pub fn with_options(self, options: &LogGetOptions) -> Self {
+        self.timeout(options.timeout)
+            .min(options.min)
+            .headers(options.headers)
+            .short(options.short)
+            .allow_empty(options.allow_empty)
+            .with_end_timestamp(options.end_timestamp)
+            .ue_id(options.ue_id)
+            .rnti(options.rnti)
+            .cell_id(options.cell_id)
+    }
+
+    /// Time the server waits for `min` logs (seconds)
+    pub fn timeout(mut self, timeout: u64) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Minimum number of logs before the server answers, `max` is raised if needed
+    pub fn min(mut self, min: u64) -> Self {
+        self.min = min;
+        self.max = self.max.max(min);
+        self
+    }
+
+    /// Maximum number of logs, `min` is lowered if needed
+    pub fn max(mut self, max: u64) -> Self {
+        self.max = max;
+        self.min = self.min.min(max);
+        self
+    }
+
+    /// Ask for the log headers
+    pub fn headers(mut self, headers: bool) -> Self {
+        self.headers = headers;
+        self
+    }
+
+    /// Ask for the first line of the logs only
+    pub fn short(mut self, short: bool) -> Self {
+        self.short = short;
+        self
+    }
+
+    /// Answer even if there is no log
+    pub fn allow_empty(mut self, allow_empty: bool) -> Self {
+        self.allow_empty = allow_empty;
+        self
+    }
+
+    /// Only ask for the logs from a timestamp (milliseconds)
+    pub fn with_start_timestamp(mut self, start_timestamp: Option<u64>) -> Self {
+        self.start_timestamp = start_timestamp;
+        self
+    }
+
+    /// Only ask for the logs until a timestamp (milliseconds)
+    pub fn with_end_timestamp(mut self, end_timestamp: Option<u64>) -> Self {
+        self.end_timestamp = end_timestamp;
+        self
+    }
+
+    /// Only ask for the logs of a UE
+    pub fn ue_id(mut self, ue_id: Option<u64>) -> Self {
+        self.ue_id = ue_id;
+        self
+    }
+
+    /// Only ask for the logs of a RNTI
+    pub fn rnti(mut self, rnti: Option<u64>) -> Self {
+        self.rnti = rnti;
+        self
+    }
+
+    /// Only ask for the logs of a cell
+    pub fn cell_id(mut self, cell_id: Option<u64>) -> Self {
+        self.cell_id = cell_id;
+        self
+    }
 }
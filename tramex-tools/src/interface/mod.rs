@@ -5,7 +5,10 @@ pub mod interface_types;
 pub mod layer;
 pub mod log_get;
 pub mod onelog;
+pub mod remote_api;
+pub mod stats;
 pub mod types;
+pub mod ue_get;
 
 pub mod interface_file;
 pub mod parser;
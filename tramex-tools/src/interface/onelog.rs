@@ -32,6 +32,10 @@ pub struct OneLog {
 
     /// index
     pub dir: Option<String>,
+
+    /// UE identifier
+    #[serde(default)]
+    pub ue_id: Option<u64>,
 }
 
 impl OneLog {
@@ -76,6 +80,23 @@ impl OneLog {
                     }
                 };
                 let firs_line = self.data[0].split(':').collect::<Vec<&str>>();
+                if dir == Direction::NONE && firs_line.len() < 2 {
+                    // information of the eNB about a UE, such as "Changing UE_ID to 0x1"
+                    return Ok(Trace {
+                        timestamp: self.timestamp.to_owned(),
+                        layer: Layer::RRC,
+                        additional_infos: AdditionalInfos::RRCInfos(RRCInfos {
+                            direction: dir,
+                            canal: String::new(),
+                            canal_msg: self.data[0].to_owned(),
+                            ue_id: self.ue_id,
+                        }),
+                        hexa: vec![],
+                        text: None,
+                        source: None,
+                        bookmark: None,
+                    });
+                }
                 if firs_line.len() < 2 {
                     return Err(tramex_error!(
                         format!("Invalid first line {}", self.data[0]),
@@ -86,6 +107,7 @@ impl OneLog {
                     direction: dir,
                     canal: firs_line[0].to_owned(),
                     canal_msg: firs_line[1][1..].to_owned(),
+                    ue_id: self.ue_id,
                 };
                 let infos = AdditionalInfos::RRCInfos(rrc);
                 let trace = Trace {
@@ -94,6 +116,8 @@ impl OneLog {
                     additional_infos: infos,
                     hexa: self.extract_hexe().unwrap_or_default(),
                     text: Some(self.data[1..].iter().map(|x| x.to_string()).collect()),
+                    source: None,
+                    bookmark: None,
                 };
                 Ok(trace)
             }
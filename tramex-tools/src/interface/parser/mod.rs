@@ -12,18 +12,32 @@ use chrono::NaiveTime;
 use chrono::Timelike;
 
 /// Parsing error
+#[derive(Debug)]
 pub struct ParsingError {
     /// Error message
     pub message: String,
 
     /// Line index
     pub line_idx: u64,
+
+    /// Column of the error in the line, starting at 1 (None if unknown)
+    pub column: Option<u64>,
 }
 
 impl ParsingError {
     /// Create a new parsing error
     pub fn new(message: String, line_idx: u64) -> Self {
-        Self { message, line_idx }
+        Self {
+            message,
+            line_idx,
+            column: None,
+        }
+    }
+
+    /// Set the column of the error
+    pub fn with_column(mut self, column: u64) -> Self {
+        self.column = Some(column);
+        self
     }
 }
 
@@ -31,7 +45,11 @@ impl ParsingError {
 #[inline]
 pub fn parsing_error_to_tramex_error(error: ParsingError, idx: u64) -> TramexError {
     let index = idx + error.line_idx;
-    tramex_error!(format!("{} (line {})", error.message, index), ErrorCode::FileParsing)
+    let position = match error.column {
+        Some(column) => format!("line {index}, column {column}"),
+        None => format!("line {index}"),
+    };
+    tramex_error!(format!("{} ({})", error.message, position), ErrorCode::FileParsing)
 }
 
 /// Trait for file parser
@@ -3,11 +3,11 @@ use super::ParsingError;
 use crate::data::{AdditionalInfos, Trace};
 use std::str::FromStr;
 
-use crate::interface::{functions::extract_hexe, layer::Layer, types::Direction};
+use crate::interface::{functions::parse_hexe, layer::Layer, types::Direction};
 
 use super::FileParser;
 
-#[derive(Debug, Clone)]
+#[derive(serde::Deserialize, serde::Serialize, Debug, Clone)]
 /// Data structure to store the message type (from the amarisoft API)
 pub struct RRCInfos {
     /// Direction of the message.
@@ -18,6 +18,9 @@ pub struct RRCInfos {
 
     /// Message of the canal.
     pub canal_msg: String,
+
+    /// UE identifier of the message (None for broadcast messages).
+    pub ue_id: Option<u64>,
 }
 
 /// RRC Parser
@@ -51,9 +54,13 @@ impl RRCParser {
                 ix as u64,
             ));
         }
-        let hex = match extract_hexe(&hex_str) {
+        let hex = match parse_hexe(&hex_str) {
             Ok(h) => h,
-            Err(e) => return Err(ParsingError::new(e.message, ix as u64)),
+            Err(mut e) => {
+                // the first line of the log is line 1, the dump starts after it
+                e.line_idx += 2;
+                return Err(e);
+            }
         };
 
         let mut end = false;
@@ -96,6 +103,15 @@ impl FileParser for RRCParser {
                 ));
             }
         };
+        // the information of the eNB about a UE, such as "Changing UE_ID to 0x1", has no canal
+        if direction == Direction::NONE && concatenated.len() < 2 {
+            return Ok(AdditionalInfos::RRCInfos(RRCInfos {
+                direction,
+                canal: String::new(),
+                canal_msg: binding,
+                ue_id: u64::from_str_radix(parts[3], 16).ok(),
+            }));
+        }
         if concatenated.len() < 2 || concatenated[0].is_empty() || concatenated[1].is_empty() {
             return Err(ParsingError::new(
                 "The canal and/or canal message could not be parsed".to_string(),
@@ -106,6 +122,7 @@ impl FileParser for RRCParser {
             direction,
             canal: concatenated[0].to_owned(),
             canal_msg: concatenated[1].trim_start().to_owned(),
+            ue_id: u64::from_str_radix(parts[3], 16).ok(),
         }))
     }
 
@@ -116,10 +133,16 @@ impl FileParser for RRCParser {
                 return Err(e);
             }
         };
-        let (hexa, text) = match Self::parse_lines(&lines[1..]) {
-            Ok((h, t)) => (h, t),
-            Err(e) => {
-                return Err(e);
+        let AdditionalInfos::RRCInfos(infos) = &mtype;
+        let (hexa, text) = if infos.canal.is_empty() {
+            // an information has no payload
+            (vec![], None)
+        } else {
+            match Self::parse_lines(&lines[1..]) {
+                Ok((h, t)) => (h, Some(t)),
+                Err(e) => {
+                    return Err(e);
+                }
             }
         };
         let trace = Trace {
@@ -127,7 +150,9 @@ impl FileParser for RRCParser {
             layer: Layer::RRC,
             additional_infos: mtype,
             hexa,
-            text: Some(text),
+            text,
+            source: None,
+            bookmark: None,
         };
         Ok(trace)
     }
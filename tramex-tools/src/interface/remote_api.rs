@@ -0,0 +1,252 @@
+//! Generic requests to the remote API and their responses
+use std::collections::{BTreeSet, VecDeque};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use serde_json::{Map, Value};
+
+use crate::interface::layer::Layers;
+
+/// Messages of the remote API offered by the console, `cell_gain` and `log_set` need parameters
+pub const KNOWN_MESSAGES: [&str; 6] = ["config_get", "stats", "ue_get", "cell_gain", "log_reset", "log_set"];
+
+/// Next message ID, shared by all the connections so a message ID identifies one request
+static NEXT_MESSAGE_ID: AtomicU64 = AtomicU64::new(1);
+
+/// Take a new message ID
+pub fn next_message_id() -> u64 {
+    NEXT_MESSAGE_ID.fetch_add(1, Ordering::Relaxed)
+}
+
+/// Maximum number of exchanges kept in the history
+pub const MAX_EXCHANGES: usize = 500;
+
+/// Delay after which a request without response is not pending anymore (milliseconds)
+pub const REQUEST_TIMEOUT_MS: i64 = 10_000;
+
+#[derive(Debug, Clone, PartialEq)]
+/// Request to the remote API
+pub struct Request {
+    /// Name of the message
+    pub message: String,
+
+    /// Parameters of the message
+    pub params: Map<String, Value>,
+
+    /// Source (interface name) the request is sent to, None for the first one available
+    pub target: Option<String>,
+
+    /// Message ID, given when the request is queued
+    pub message_id: Option<u64>,
+}
+
+impl Request {
+    /// Create a new request without parameters
+    pub fn new(message: &str) -> Self {
+        Self {
+            message: message.to_owned(),
+            params: Map::new(),
+            target: None,
+            message_id: None,
+        }
+    }
+
+    /// Request changing the log configuration of the server (log_set)
+    pub fn log_set(layers: &Layers) -> Self {
+        let layers = serde_json::to_value(layers).unwrap_or_default();
+        Self::new("log_set").param("logs", serde_json::json!({ "layers": layers }))
+    }
+
+    /// Send the request to a given source
+    pub fn to(mut self, target: &str) -> Self {
+        self.target = Some(target.to_owned());
+        self
+    }
+
+    /// Add a parameter to the request
+    pub fn param(mut self, key: &str, value: impl Into<Value>) -> Self {
+        self.params.insert(key.to_owned(), value.into());
+        self
+    }
+
+    /// Build the JSON message with its message ID
+    pub fn to_json(&self, message_id: u64) -> Value {
+        let mut json = self.params.clone();
+        json.insert("message".to_owned(), Value::from(self.message.clone()));
+        json.insert("message_id".to_owned(), Value::from(message_id));
+        Value::Object(json)
+    }
+}
+
+#[derive(Debug, Clone)]
+/// Request sent to the remote API and its response
+pub struct Exchange {
+    /// Message ID of the request
+    pub message_id: u64,
+
+    /// Request sent
+    pub request: Request,
+
+    /// Source (interface name) the request was sent to
+    pub source: String,
+
+    /// Time of the request (milliseconds)
+    pub sent_ms: i64,
+
+    /// Response received (None while pending)
+    pub response: Option<Value>,
+
+    /// Time of the response (milliseconds)
+    pub received_ms: Option<i64>,
+}
+
+impl Exchange {
+    /// Error returned by the server
+    pub fn error(&self) -> Option<&str> {
+        self.response.as_ref()?.get("error")?.as_str()
+    }
+
+    /// Time between the request and the response (milliseconds)
+    pub fn latency_ms(&self) -> Option<i64> {
+        self.received_ms.map(|received_ms| received_ms - self.sent_ms)
+    }
+}
+
+#[derive(Debug, Default)]
+/// Remote API shared between the panels and the interface
+///
+/// Panels queue requests, the interface sends them and stores the responses matched by message ID.
+pub struct RemoteApi {
+    /// Sources (interface names) that can send requests, refreshed by the interfaces on each frame
+    sources: BTreeSet<String>,
+
+    /// Requests waiting to be sent
+    outgoing: VecDeque<Request>,
+
+    /// Requests sent and their responses, oldest first
+    pub exchanges: VecDeque<Exchange>,
+}
+
+impl RemoteApi {
+    /// Forget the sources, each interface declares itself again with `set_connected`
+    pub fn reset_connections(&mut self) {
+        self.sources.clear();
+    }
+
+    /// Declare a source that can send requests
+    pub fn set_connected(&mut self, source: &str) {
+        self.sources.insert(source.to_owned());
+    }
+
+    /// Check if at least one source can send requests
+    pub fn is_connected(&self) -> bool {
+        !self.sources.is_empty()
+    }
+
+    /// Sources that can send requests
+    pub fn sources(&self) -> impl Iterator<Item = &String> {
+        self.sources.iter()
+    }
+
+    /// Queue a request and return its message ID, None if its target (or any source without target) can't send
+    /// requests
+    pub fn request(&mut self, mut request: Request) -> Option<u64> {
+        let available = match &request.target {
+            Some(target) => self.sources.contains(target),
+            None => self.is_connected(),
+        };
+        if !available {
+            return None;
+        }
+        let message_id = next_message_id();
+        request.message_id = Some(message_id);
+        self.outgoing.push_back(request);
+        Some(message_id)
+    }
+
+    /// Take the requests waiting to be sent by a source: the ones targeting it and the ones without target
+    pub fn take_outgoing(&mut self, source: &str) -> Vec<Request> {
+        let (taken, kept): (VecDeque<Request>, VecDeque<Request>) = self
+            .outgoing
+            .drain(..)
+            .partition(|request| request.target.as_ref().is_none_or(|target| target == source));
+        self.outgoing = kept;
+        taken.into()
+    }
+
+    /// Record a request sent by a source
+    pub fn sent(&mut self, message_id: u64, request: Request, source: &str, sent_ms: i64) {
+        if self.exchanges.len() >= MAX_EXCHANGES {
+            self.exchanges.pop_front();
+        }
+        self.exchanges.push_back(Exchange {
+            message_id,
+            request,
+            source: source.to_owned(),
+            sent_ms,
+            response: None,
+            received_ms: None,
+        });
+    }
+
+    /// Record a response if it matches a pending request, return false if it does not
+    pub fn received(&mut self, message_id: u64, response: Value, received_ms: i64) -> bool {
+        match self
+            .exchanges
+            .iter_mut()
+            .rev()
+            .find(|exchange| exchange.message_id == message_id && exchange.response.is_none())
+        {
+            Some(exchange) => {
+                exchange.response = Some(response);
+                exchange.received_ms = Some(received_ms);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Check if a request of a message is waiting to be sent or for its response
+    pub fn is_pending(&self, message: &str, now_ms: i64) -> bool {
+        self.outgoing.iter().any(|request| request.message == message)
+            || self.exchanges.iter().any(|exchange| {
+                exchange.request.message == message
+                    && exchange.response.is_none()
+                    && now_ms - exchange.sent_ms < REQUEST_TIMEOUT_MS
+            })
+    }
+
+    /// Latest exchange of a message that got its response
+    pub fn latest_response(&self, message: &str) -> Option<&Exchange> {
+        self.exchanges
+            .iter()
+            .rev()
+            .find(|exchange| exchange.request.message == message && exchange.response.is_some())
+    }
+
+    /// Check if a request of a message to a source is waiting to be sent or for its response
+    pub fn is_pending_to(&self, message: &str, source: &str, now_ms: i64) -> bool {
+        self.outgoing
+            .iter()
+            .any(|request| request.message == message && request.target.as_deref() == Some(source))
+            || self.exchanges.iter().any(|exchange| {
+                exchange.request.message == message
+                    && exchange.source == source
+                    && exchange.response.is_none()
+                    && now_ms - exchange.sent_ms < REQUEST_TIMEOUT_MS
+            })
+    }
+
+    /// Latest exchange of a message with a source that got its response
+    pub fn latest_response_from(&self, message: &str, source: &str) -> Option<&Exchange> {
+        self.exchanges
+            .iter()
+            .rev()
+            .find(|exchange| exchange.request.message == message && exchange.source == source && exchange.response.is_some())
+    }
+
+    /// Clear the requests and their responses
+    pub fn clear(&mut self) {
+        self.outgoing.clear();
+        self.exchanges.clear();
+    }
+}
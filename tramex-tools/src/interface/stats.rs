@@ -0,0 +1,189 @@
+//! Response of the stats message of the remote API and its history
+use std::collections::{BTreeMap, VecDeque};
+use std::fmt;
+use std::fmt::Display;
+use std::fmt::Formatter;
+
+/// Default number of samples kept in the history
+pub const DEFAULT_MAX_SAMPLES: usize = 600;
+
+#[derive(serde::Deserialize, Debug, Default, Clone)]
+/// Response of stats
+pub struct StatsResponse {
+    /// CPU load
+    pub cpu: Option<CpuStats>,
+
+    /// Statistics of each cell, by cell identifier
+    #[serde(default)]
+    pub cells: BTreeMap<String, CellStats>,
+}
+
+impl StatsResponse {
+    /// Decode a stats response, None if it is not one
+    pub fn from_json(value: &serde_json::Value) -> Option<Self> {
+        serde_json::from_value(value.clone()).ok()
+    }
+}
+
+#[derive(serde::Deserialize, Debug, Default, Clone)]
+/// CPU load in a stats response
+pub struct CpuStats {
+    /// Global CPU load (percent)
+    pub global: Option<f64>,
+}
+
+#[derive(serde::Deserialize, Debug, Default, Clone, PartialEq)]
+/// Statistics of a cell in a stats response
+pub struct CellStats {
+    /// Downlink bitrate (bit/s)
+    pub dl_bitrate: Option<f64>,
+
+    /// Uplink bitrate (bit/s)
+    pub ul_bitrate: Option<f64>,
+
+    /// Average downlink PRB usage (between 0 and 1)
+    pub dl_use_avg: Option<f64>,
+
+    /// Average uplink PRB usage (between 0 and 1)
+    pub ul_use_avg: Option<f64>,
+
+    /// Average number of UEs
+    pub ue_count_avg: Option<f64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// Metric of a cell
+pub enum CellMetric {
+    /// Downlink bitrate (bit/s)
+    DlBitrate,
+
+    /// Uplink bitrate (bit/s)
+    UlBitrate,
+
+    /// Downlink PRB usage (percent)
+    DlPrbUsage,
+
+    /// Uplink PRB usage (percent)
+    UlPrbUsage,
+
+    /// Number of UEs
+    UeCount,
+}
+
+impl CellMetric {
+    /// All the metrics
+    pub const ALL: [CellMetric; 5] = [
+        CellMetric::DlBitrate,
+        CellMetric::UlBitrate,
+        CellMetric::DlPrbUsage,
+        CellMetric::UlPrbUsage,
+        CellMetric::UeCount,
+    ];
+
+    /// Value of the metric in the statistics of a cell
+    pub fn value(&self, stats: &CellStats) -> Option<f64> {
+        match self {
+            CellMetric::DlBitrate => stats.dl_bitrate,
+            CellMetric::UlBitrate => stats.ul_bitrate,
+            CellMetric::DlPrbUsage => stats.dl_use_avg.map(|usage| usage * 100.0),
+            CellMetric::UlPrbUsage => stats.ul_use_avg.map(|usage| usage * 100.0),
+            CellMetric::UeCount => stats.ue_count_avg,
+        }
+    }
+}
+
+impl Display for CellMetric {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let str = match self {
+            CellMetric::DlBitrate => "DL bitrate",
+            CellMetric::UlBitrate => "UL bitrate",
+            CellMetric::DlPrbUsage => "DL PRB usage",
+            CellMetric::UlPrbUsage => "UL PRB usage",
+            CellMetric::UeCount => "UEs",
+        };
+        write!(f, "{str}")
+    }
+}
+
+#[derive(Debug, Clone)]
+/// Statistics received at a given time
+pub struct StatsSample {
+    /// Time of the response (milliseconds)
+    pub time_ms: i64,
+
+    /// Statistics received
+    pub stats: StatsResponse,
+}
+
+#[derive(Debug)]
+/// History of the statistics
+pub struct StatsHistory {
+    /// Samples, oldest first
+    pub samples: VecDeque<StatsSample>,
+
+    /// Maximum number of samples kept
+    pub max_samples: usize,
+}
+
+impl Default for StatsHistory {
+    fn default() -> Self {
+        Self {
+            samples: VecDeque::new(),
+            max_samples: DEFAULT_MAX_SAMPLES,
+        }
+    }
+}
+
+impl StatsHistory {
+    /// Add a sample, the oldest one is dropped when the history is full
+    pub fn push(&mut self, time_ms: i64, stats: StatsResponse) {
+        while self.samples.len() >= self.max_samples.max(1) {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(StatsSample { time_ms, stats });
+    }
+
+    /// Latest sample
+    pub fn latest(&self) -> Option<&StatsSample> {
+        self.samples.back()
+    }
+
+    /// Identifiers of the cells seen in the history
+    pub fn cells(&self) -> Vec<String> {
+        let mut cells: Vec<String> = vec![];
+        for sample in &self.samples {
+            for cell in sample.stats.cells.keys() {
+                if !cells.contains(cell) {
+                    cells.push(cell.clone());
+                }
+            }
+        }
+        cells
+    }
+
+    /// Points (seconds since the first sample, value) of a metric of a cell
+    pub fn cell_series(&self, cell: &str, metric: CellMetric) -> Vec<[f64; 2]> {
+        self.series(|stats| stats.cells.get(cell).and_then(|cell_stats| metric.value(cell_stats)))
+    }
+
+    /// Points (seconds since the first sample, percent) of the CPU load
+    pub fn cpu_series(&self) -> Vec<[f64; 2]> {
+        self.series(|stats| stats.cpu.as_ref().and_then(|cpu| cpu.global))
+    }
+
+    /// Points (seconds since the first sample, value) of a value of the samples
+    fn series(&self, value: impl Fn(&StatsResponse) -> Option<f64>) -> Vec<[f64; 2]> {
+        let Some(first) = self.samples.front() else {
+            return vec![];
+        };
+        self.samples
+            .iter()
+            .filter_map(|sample| value(&sample.stats).map(|value| [(sample.time_ms - first.time_ms) as f64 / 1000.0, value]))
+            .collect()
+    }
+
+    /// Clear the history
+    pub fn clear(&mut self) {
+        self.samples.clear();
+    }
+}
@@ -24,6 +24,13 @@ pub struct WebSocketLog {
     pub logs: Vec<OneLog>,
 }
 
+/// Message ID of a response, to match it with its request
+#[derive(serde::Deserialize, Debug)]
+pub struct MessageId {
+    /// Message ID (same as in request)
+    pub message_id: Option<u64>,
+}
+
 /// LogGet struct
 #[derive(serde::Deserialize, Debug)]
 pub struct BaseMessage {
@@ -69,7 +76,7 @@ pub enum SourceLog {
     MME,
 }
 
-#[derive(serde::Deserialize, Debug, PartialEq, Default, Clone)]
+#[derive(serde::Deserialize, serde::Serialize, Debug, PartialEq, Default, Clone)]
 /// Direction enum
 pub enum Direction {
     #[default]
@@ -84,6 +91,9 @@ pub enum Direction {
 
     /// To direction
     TO,
+
+    /// No direction (`-`), for the information of the eNB about a UE such as a change of its ID
+    NONE,
 }
 
 impl FromStr for Direction {
@@ -95,6 +105,7 @@ impl FromStr for Direction {
             "DL" => Ok(Direction::DL),
             "FROM" => Ok(Direction::FROM),
             "TO" => Ok(Direction::TO),
+            "-" => Ok(Direction::NONE),
             _ => Err(()),
         }
     }
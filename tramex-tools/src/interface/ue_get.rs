@@ -0,0 +1,120 @@
+//! Response of the ue_get message of the remote API
+
+#[derive(serde::Deserialize, Debug, Default)]
+/// Response of ue_get
+pub struct UeGetResponse {
+    /// UEs known by the server
+    #[serde(default)]
+    pub ue_list: Vec<UeInfo>,
+}
+
+impl UeGetResponse {
+    /// Decode a ue_get response, None if it is not one
+    pub fn from_json(value: &serde_json::Value) -> Option<Self> {
+        serde_json::from_value(value.clone()).ok()
+    }
+}
+
+#[derive(serde::Deserialize, Debug, Default, Clone)]
+/// UE in a ue_get response
+pub struct UeInfo {
+    /// UE identifier on the eNB (LTE)
+    pub enb_ue_id: Option<u64>,
+
+    /// UE identifier on the gNB (NR)
+    pub ran_ue_id: Option<u64>,
+
+    /// IMSI (core network only)
+    pub imsi: Option<String>,
+
+    /// 5G-GUTI (core network only)
+    #[serde(rename = "5g_guti")]
+    pub guti_5g: Option<String>,
+
+    /// Cells of the UE
+    #[serde(default)]
+    pub cells: Vec<UeCell>,
+
+    /// E-RABs of the UE (LTE)
+    #[serde(default)]
+    pub erab_list: Vec<Bearer>,
+
+    /// QoS flows of the UE (NR)
+    #[serde(default)]
+    pub qos_flow_list: Vec<Bearer>,
+}
+
+#[derive(serde::Deserialize, Debug, Default, Clone)]
+/// Cell of a UE in a ue_get response
+pub struct UeCell {
+    /// Cell identifier
+    pub cell_id: Option<u64>,
+
+    /// RNTI of the UE in the cell
+    pub rnti: Option<u64>,
+
+    /// Downlink bitrate (bit/s, only with stats)
+    pub dl_bitrate: Option<f64>,
+
+    /// Uplink bitrate (bit/s, only with stats)
+    pub ul_bitrate: Option<f64>,
+}
+
+#[derive(serde::Deserialize, Debug, Default, Clone)]
+/// Bearer (E-RAB or QoS flow) of a UE in a ue_get response
+pub struct Bearer {
+    /// E-RAB identifier (LTE)
+    pub erab_id: Option<u64>,
+
+    /// PDU session identifier (NR)
+    pub pdu_session_id: Option<u64>,
+
+    /// QoS flow identifier (NR)
+    pub qfi: Option<u64>,
+}
+
+impl UeInfo {
+    /// UE identifier, the same as in the logs
+    pub fn ue_id(&self) -> Option<u64> {
+        self.enb_ue_id.or(self.ran_ue_id)
+    }
+
+    /// RNTI of the UE in its first cell
+    pub fn rnti(&self) -> Option<u64> {
+        self.cells.iter().find_map(|cell| cell.rnti)
+    }
+
+    /// Identity of the subscriber: IMSI or 5G-GUTI
+    pub fn identity(&self) -> Option<&str> {
+        self.imsi.as_deref().or(self.guti_5g.as_deref())
+    }
+
+    /// Downlink bitrate of all the cells (bit/s)
+    pub fn dl_bitrate(&self) -> f64 {
+        self.cells.iter().filter_map(|cell| cell.dl_bitrate).sum()
+    }
+
+    /// Uplink bitrate of all the cells (bit/s)
+    pub fn ul_bitrate(&self) -> f64 {
+        self.cells.iter().filter_map(|cell| cell.ul_bitrate).sum()
+    }
+
+    /// Names of the bearers
+    pub fn bearers(&self) -> Vec<String> {
+        let erabs = self
+            .erab_list
+            .iter()
+            .filter_map(|bearer| bearer.erab_id)
+            .map(|erab_id| format!("E-RAB {erab_id}"));
+        let flows = self
+            .qos_flow_list
+            .iter()
+            .map(|bearer| match (bearer.pdu_session_id, bearer.qfi) {
+                (Some(pdu_session_id), Some(qfi)) => format!("PDU {pdu_session_id} QFI {qfi}"),
+                (Some(pdu_session_id), None) => format!("PDU {pdu_session_id}"),
+                (None, Some(qfi)) => format!("QFI {qfi}"),
+                (None, None) => "QoS flow".to_owned(),
+            });
+        erabs.chain(flows).collect()
+    }
+}
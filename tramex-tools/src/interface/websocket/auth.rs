@@ -0,0 +1,67 @@
+//! Authentication to the remote API
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+#[derive(serde::Deserialize, Debug)]
+/// Authenticate message sent by the server: challenge or result of the authentication
+pub struct AuthenticateMessage {
+    /// Always "authenticate"
+    pub message: String,
+
+    /// Type of the server (ENB, MME, ...)
+    #[serde(rename = "type", default)]
+    pub server_type: String,
+
+    /// Name of the server
+    #[serde(default)]
+    pub name: String,
+
+    /// Challenge to answer (only in the first message)
+    pub challenge: Option<String>,
+
+    /// True once the authentication succeeded
+    pub ready: Option<bool>,
+
+    /// Error if the authentication failed
+    pub error: Option<String>,
+}
+
+#[derive(serde::Serialize, Debug)]
+/// Authenticate message sent to the server
+pub struct Authenticate {
+    /// Always "authenticate"
+    message: String,
+
+    /// Answer to the challenge
+    res: String,
+
+    /// Message ID
+    message_id: u64,
+}
+
+impl Authenticate {
+    /// Create the answer to a challenge
+    pub fn new(id: u64, challenge: &AuthenticateMessage, password: &str) -> Self {
+        Self {
+            message: "authenticate".to_owned(),
+            res: compute_response(
+                &challenge.server_type,
+                &challenge.name,
+                password,
+                challenge.challenge.as_deref().unwrap_or_default(),
+            ),
+            message_id: id,
+        }
+    }
+}
+
+/// Compute the answer to a challenge: HMAC-SHA256 of the challenge with `type:password:name` as key, in hexadecimal
+pub fn compute_response(server_type: &str, name: &str, password: &str, challenge: &str) -> String {
+    let key = format!("{server_type}:{password}:{name}");
+    let Ok(mut mac) = Hmac::<Sha256>::new_from_slice(key.as_bytes()) else {
+        // HMAC accepts keys of any size
+        return String::new();
+    };
+    mac.update(challenge.as_bytes());
+    mac.finalize().into_bytes().iter().map(|byte| format!("{byte:02x}")).collect()
+}
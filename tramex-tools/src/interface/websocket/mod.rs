@@ -1,4 +1,9 @@
 //! Websocket module
 
+pub mod auth;
+pub mod reconnect;
+pub mod recording;
+pub mod replay;
+
 #[cfg(feature = "websocket")]
 pub mod ws_connection;
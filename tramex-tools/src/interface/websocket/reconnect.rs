@@ -0,0 +1,122 @@
+//! Reconnection helpers for the websocket interface
+
+/// Default delay before the first reconnection attempt (milliseconds)
+pub const DEFAULT_INITIAL_DELAY_MS: u64 = 500;
+
+/// Default maximum delay between two reconnection attempts (milliseconds)
+pub const DEFAULT_MAX_DELAY_MS: u64 = 30_000;
+
+#[derive(Debug, Clone)]
+/// Exponential backoff between reconnection attempts
+pub struct Backoff {
+    /// Delay before the first attempt (milliseconds)
+    pub initial_delay_ms: u64,
+
+    /// Maximum delay between two attempts (milliseconds)
+    pub max_delay_ms: u64,
+
+    /// Number of attempts already scheduled
+    attempt: u32,
+
+    /// Time of the next attempt (milliseconds), None if no attempt is scheduled
+    next_attempt_ms: Option<i64>,
+}
+
+impl Default for Backoff {
+    fn default() -> Self {
+        Self::new(DEFAULT_INITIAL_DELAY_MS, DEFAULT_MAX_DELAY_MS)
+    }
+}
+
+impl Backoff {
+    /// Create a new Backoff
+    pub fn new(initial_delay_ms: u64, max_delay_ms: u64) -> Self {
+        Self {
+            initial_delay_ms,
+            max_delay_ms,
+            attempt: 0,
+            next_attempt_ms: None,
+        }
+    }
+
+    /// Delay before the next attempt: the initial delay doubled at each attempt, up to the maximum delay
+    pub fn delay_ms(&self) -> u64 {
+        let factor = 1u64.checked_shl(self.attempt).unwrap_or(u64::MAX);
+        self.initial_delay_ms.saturating_mul(factor).min(self.max_delay_ms)
+    }
+
+    /// Schedule the next attempt if none is scheduled, return the delay before it
+    pub fn schedule(&mut self, now_ms: i64) -> u64 {
+        if let Some(next_attempt_ms) = self.next_attempt_ms {
+            return (next_attempt_ms - now_ms).max(0) as u64;
+        }
+        let delay = self.delay_ms();
+        self.next_attempt_ms = Some(now_ms + delay as i64);
+        self.attempt = self.attempt.saturating_add(1);
+        delay
+    }
+
+    /// Check if the scheduled attempt is due, the attempt is then unscheduled
+    pub fn take_due(&mut self, now_ms: i64) -> bool {
+        match self.next_attempt_ms {
+            Some(next_attempt_ms) if next_attempt_ms <= now_ms => {
+                self.next_attempt_ms = None;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Time left before the scheduled attempt (milliseconds), None if no attempt is scheduled
+    pub fn time_left_ms(&self, now_ms: i64) -> Option<u64> {
+        self.next_attempt_ms
+            .map(|next_attempt_ms| (next_attempt_ms - now_ms).max(0) as u64)
+    }
+
+    /// Number of attempts already scheduled
+    pub fn attempt(&self) -> u32 {
+        self.attempt
+    }
+
+    /// Reset the backoff once connected
+    pub fn reset(&mut self) {
+        self.attempt = 0;
+        self.next_attempt_ms = None;
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+/// Last log received, used to resume the logs after a reconnection
+pub struct ResumePoint {
+    /// Timestamp of the log (milliseconds)
+    pub timestamp: u64,
+
+    /// Index of the log on the server
+    pub idx: u64,
+}
+
+impl ResumePoint {
+    /// Check if a log was not received yet
+    ///
+    /// Logs are received in order, so a log older than the resume point, or with the same timestamp and a smaller
+    /// or equal index, was already received.
+    pub fn is_new(&self, timestamp: u64, idx: u64) -> bool {
+        ResumePoint { timestamp, idx } > *self
+    }
+}
+
+/// Current time in milliseconds
+pub fn now_ms() -> i64 {
+    chrono::Utc::now().timestamp_millis()
+}
+
+/// Check if a log was not received yet and move the resume point to it
+pub fn accept_log(resume_point: &mut Option<ResumePoint>, timestamp: u64, idx: u64) -> bool {
+    match resume_point {
+        Some(point) if !point.is_new(timestamp, idx) => false,
+        _ => {
+            *resume_point = Some(ResumePoint { timestamp, idx });
+            true
+        }
+    }
+}
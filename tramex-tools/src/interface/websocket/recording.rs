@@ -0,0 +1,228 @@
+//! Recording of the frames of logs received on a websocket
+//!
+//! A recording is a JSON Lines file: a header followed by one line per frame of logs, with the raw frame and the
+//! time it was received. It is decoded by [`push_logs`], like the frames received live.
+use std::io::Write;
+
+use crate::data::Data;
+use crate::errors::{ErrorCode, TramexError};
+use crate::interface::interface_file::file_handler::MS_IN_DAY;
+use crate::interface::types::WebSocketLog;
+use crate::interface::websocket::reconnect::{ResumePoint, accept_log};
+use crate::tramex_error;
+
+/// Value of the `recording` field of the header
+const RECORDING_KIND: &str = "tramex";
+
+/// Version of the recording format
+pub const RECORDING_VERSION: u32 = 1;
+
+#[derive(serde::Deserialize, serde::Serialize, Debug, Clone, PartialEq)]
+/// First line of a recording
+pub struct RecordingHeader {
+    /// Kind of file, always "tramex"
+    pub recording: String,
+
+    /// Version of the format
+    pub version: u32,
+
+    /// Name of the server recorded
+    pub name: String,
+
+    /// Start of the recording (milliseconds)
+    pub start_ms: i64,
+}
+
+#[derive(serde::Deserialize, serde::Serialize, Debug, Clone, PartialEq)]
+/// Frame of logs received, one line of a recording
+pub struct RecordedFrame {
+    /// Time the frame was received (milliseconds)
+    pub time_ms: i64,
+
+    /// Raw JSON frame
+    pub frame: String,
+}
+
+/// Timestamp of a trace from the timestamp of a log of the remote API (milliseconds since the epoch)
+///
+/// The traces of the files are timed from the midnight of their first log, so the logs of the servers are timed
+/// from the midnight (UTC) of the first log received, `day_start`, to be merged with them.
+pub fn day_timestamp(day_start: &mut Option<u64>, timestamp: u64) -> u64 {
+    let start = *day_start.get_or_insert(timestamp - timestamp % MS_IN_DAY);
+    timestamp.saturating_sub(start)
+}
+
+/// Decode the logs of a frame and add their traces to the data
+///
+/// The logs already received (before `resume_point`) are skipped, the timestamps are converted by [`day_timestamp`].
+/// The logs fetched again, when a range is asked from an earlier time, are skipped if their trace is already loaded.
+/// # Errors
+/// Return the errors of the logs that can't be decoded, the other logs are added
+pub fn push_logs(
+    decoded: WebSocketLog,
+    source: Option<String>,
+    resume_point: &mut Option<ResumePoint>,
+    day_start: &mut Option<u64>,
+    data: &mut Data,
+) -> Result<(), Vec<TramexError>> {
+    let mut errors = vec![];
+    for one_log in decoded.logs {
+        if !accept_log(resume_point, one_log.timestamp, one_log.idx) {
+            log::debug!("Skipping log {} already received", one_log.idx);
+            continue;
+        }
+        match one_log.extract_data() {
+            Ok(mut trace) => {
+                trace.timestamp = day_timestamp(day_start, trace.timestamp);
+                trace.source = source.clone();
+                if data.contains(&trace) {
+                    log::debug!("Skipping log {} already loaded", one_log.idx);
+                    continue;
+                }
+                data.push_ordered(trace);
+            }
+            Err(err) => {
+                log::error!("Error while extracting data: {err:?}");
+                errors.push(err);
+            }
+        }
+    }
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+    Ok(())
+}
+
+/// Writer of a recording
+pub struct Recorder<W: Write> {
+    /// Destination of the recording
+    writer: W,
+
+    /// Number of frames recorded
+    nb_frames: usize,
+}
+
+impl<W: Write> Recorder<W> {
+    /// Start a recording, the header is written immediately
+    /// # Errors
+    /// Return an error if the header can't be written
+    pub fn new(mut writer: W, name: &str, start_ms: i64) -> Result<Self, TramexError> {
+        let header = RecordingHeader {
+            recording: RECORDING_KIND.to_owned(),
+            version: RECORDING_VERSION,
+            name: name.to_owned(),
+            start_ms,
+        };
+        write_line(&mut writer, &header)?;
+        Ok(Self { writer, nb_frames: 0 })
+    }
+
+    /// Record a frame received at `time_ms`
+    /// # Errors
+    /// Return an error if the frame can't be written
+    pub fn record(&mut self, time_ms: i64, frame: &str) -> Result<(), TramexError> {
+        let frame = RecordedFrame {
+            time_ms,
+            frame: frame.to_owned(),
+        };
+        write_line(&mut self.writer, &frame)?;
+        self.nb_frames += 1;
+        Ok(())
+    }
+
+    /// Number of frames recorded
+    pub fn nb_frames(&self) -> usize {
+        self.nb_frames
+    }
+
+    /// Stop the recording and return the writer
+    /// # Errors
+    /// Return an error if the writer can't be flushed
+    pub fn finish(mut self) -> Result<W, TramexError> {
+        match self.writer.flush() {
+            Ok(()) => Ok(self.writer),
+            Err(err) => Err(tramex_error!(err.to_string(), ErrorCode::RecordingErrorWriting)),
+        }
+    }
+}
+
+impl Recorder<Box<dyn Write>> {
+    /// Start a recording in a file
+    /// # Errors
+    /// Return an error if the file can't be created
+    pub fn create(path: &std::path::Path, name: &str, start_ms: i64) -> Result<Self, TramexError> {
+        match std::fs::File::create(path) {
+            Ok(file) => Recorder::new(Box::new(std::io::BufWriter::new(file)) as Box<dyn Write>, name, start_ms),
+            Err(err) => Err(tramex_error!(
+                format!("Can't create {}: {err}", path.display()),
+                ErrorCode::RecordingErrorWriting
+            )),
+        }
+    }
+}
+
+/// Write a value as one JSON line
+/// # Errors
+/// Return an error if the value can't be encoded or written
+fn write_line<W: Write>(writer: &mut W, value: &impl serde::Serialize) -> Result<(), TramexError> {
+    let line = match serde_json::to_string(value) {
+        Ok(line) => line,
+        Err(err) => return Err(tramex_error!(err.to_string(), ErrorCode::RecordingErrorWriting)),
+    };
+    match writeln!(writer, "{line}") {
+        Ok(()) => Ok(()),
+        Err(err) => Err(tramex_error!(err.to_string(), ErrorCode::RecordingErrorWriting)),
+    }
+}
+
+#[derive(Debug, Clone)]
+/// Recording read from a file
+pub struct Recording {
+    /// Header of the recording
+    pub header: RecordingHeader,
+
+    /// Frames, in the order they were received
+    pub frames: Vec<RecordedFrame>,
+}
+
+impl Recording {
+    /// Read a recording
+    /// # Errors
+    /// Return an error with the line number if the content is not a recording
+    pub fn parse(content: &str) -> Result<Self, TramexError> {
+        let mut lines = content.lines().enumerate().filter(|(_, line)| !line.trim().is_empty());
+        let header: RecordingHeader = match lines.next() {
+            Some((_, line)) => match serde_json::from_str::<RecordingHeader>(line) {
+                Ok(header) if header.recording == RECORDING_KIND => header,
+                _ => {
+                    return Err(tramex_error!(
+                        "Not a recording: the first line is not a recording header".to_owned(),
+                        ErrorCode::RecordingInvalid
+                    ));
+                }
+            },
+            None => {
+                return Err(tramex_error!("Empty recording".to_owned(), ErrorCode::RecordingInvalid));
+            }
+        };
+        if header.version > RECORDING_VERSION {
+            return Err(tramex_error!(
+                format!("Unsupported recording version {}", header.version),
+                ErrorCode::RecordingInvalid
+            ));
+        }
+        let mut frames = vec![];
+        for (index, line) in lines {
+            match serde_json::from_str::<RecordedFrame>(line) {
+                Ok(frame) => frames.push(frame),
+                Err(err) => {
+                    return Err(tramex_error!(
+                        format!("Invalid frame (line {}): {err}", index + 1),
+                        ErrorCode::RecordingInvalid
+                    ));
+                }
+            }
+        }
+        Ok(Self { header, frames })
+    }
+}
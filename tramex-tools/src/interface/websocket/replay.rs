@@ -0,0 +1,225 @@
+//! Replay of a recording through the decoding of the websocket logs
+use crate::data::Data;
+use crate::errors::{ErrorCode, TramexError};
+use crate::interface::interface_types::InterfaceTrait;
+use crate::interface::layer::Layers;
+use crate::interface::types::WebSocketLog;
+use crate::interface::websocket::reconnect::ResumePoint;
+use crate::interface::websocket::recording::{Recording, push_logs};
+use crate::tramex_error;
+
+/// Slowest speed of a replay
+const MIN_SPEED: f64 = 0.01;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+/// Speed of a replay
+pub enum ReplaySpeed {
+    /// Every frame at once
+    Instant,
+
+    /// Factor applied to the time between the frames (1.0 is the original speed)
+    Speed(f64),
+}
+
+impl ReplaySpeed {
+    /// Factor applied to the time between the frames, None if instant
+    fn factor(&self) -> Option<f64> {
+        match self {
+            ReplaySpeed::Instant => None,
+            ReplaySpeed::Speed(speed) => Some(speed.max(MIN_SPEED)),
+        }
+    }
+}
+
+#[derive(Debug)]
+/// Replay of a recording
+pub struct Replay {
+    /// Recording played
+    pub recording: Recording,
+
+    /// Index of the next frame to play
+    position: usize,
+
+    /// Speed of the replay
+    speed: ReplaySpeed,
+
+    /// Replay paused
+    paused: bool,
+
+    /// Time in the recording and wall time (milliseconds) of the last start, pause or change of speed
+    anchor: Option<(i64, i64)>,
+
+    /// Last log played, to skip the logs received twice
+    resume_point: Option<ResumePoint>,
+
+    /// Midnight (UTC) of the day of the first log played, base of the timestamps of the traces (milliseconds)
+    day_start: Option<u64>,
+}
+
+impl Replay {
+    /// Create a new replay, it starts on the first call to `poll`
+    pub fn new(recording: Recording, speed: ReplaySpeed) -> Self {
+        Self {
+            recording,
+            position: 0,
+            speed,
+            paused: false,
+            anchor: None,
+            resume_point: None,
+            day_start: None,
+        }
+    }
+
+    /// Source of the traces: name of the server recorded
+    pub fn source(&self) -> Option<String> {
+        match self.recording.header.name.is_empty() {
+            true => None,
+            false => Some(self.recording.header.name.clone()),
+        }
+    }
+
+    /// Number of frames played
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Number of frames of the recording
+    pub fn len(&self) -> usize {
+        self.recording.frames.len()
+    }
+
+    /// Check if the recording has no frame
+    pub fn is_empty(&self) -> bool {
+        self.recording.frames.is_empty()
+    }
+
+    /// Check if every frame was played
+    pub fn is_finished(&self) -> bool {
+        self.position >= self.len()
+    }
+
+    /// Speed of the replay
+    pub fn speed(&self) -> ReplaySpeed {
+        self.speed
+    }
+
+    /// Change the speed, the frames already played are kept
+    pub fn set_speed(&mut self, speed: ReplaySpeed, now_ms: i64) {
+        self.rebase(now_ms);
+        self.speed = speed;
+    }
+
+    /// Check if the replay is paused
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /// Pause the replay
+    pub fn pause(&mut self, now_ms: i64) {
+        self.rebase(now_ms);
+        self.paused = true;
+    }
+
+    /// Resume the replay
+    pub fn play(&mut self, now_ms: i64) {
+        // the time spent paused does not count
+        self.rebase(now_ms);
+        self.paused = false;
+    }
+
+    /// Time in the recording reached at `now_ms` (milliseconds)
+    pub fn recording_time(&self, now_ms: i64) -> Option<i64> {
+        let (recording_ms, wall_ms) = self.anchor?;
+        match (self.paused, self.speed.factor()) {
+            (false, Some(factor)) => Some(recording_ms + ((now_ms - wall_ms) as f64 * factor) as i64),
+            _ => Some(recording_ms),
+        }
+    }
+
+    /// Keep the time reached in the recording, counting from `now_ms`
+    fn rebase(&mut self, now_ms: i64) {
+        if let Some(recording_ms) = self.recording_time(now_ms) {
+            self.anchor = Some((recording_ms, now_ms));
+        }
+    }
+
+    /// Delay before the next frame is due (milliseconds), None if paused or finished
+    pub fn next_frame_in_ms(&self, now_ms: i64) -> Option<u64> {
+        if self.paused {
+            return None;
+        }
+        let next = self.recording.frames.get(self.position)?;
+        let (Some(factor), Some(recording_ms)) = (self.speed.factor(), self.recording_time(now_ms)) else {
+            return Some(0);
+        };
+        Some(((next.time_ms - recording_ms).max(0) as f64 / factor) as u64)
+    }
+
+    /// Play the frames due at `now_ms`
+    /// # Errors
+    /// Return the errors of the logs that can't be decoded, the other logs are added
+    pub fn poll(&mut self, now_ms: i64, data: &mut Data) -> Result<(), Vec<TramexError>> {
+        if self.paused || self.is_finished() {
+            return Ok(());
+        }
+        if self.anchor.is_none() {
+            let first_ms = self.recording.frames[self.position].time_ms;
+            self.anchor = Some((first_ms, now_ms));
+        }
+        let mut errors = vec![];
+        while let Some(frame) = self.recording.frames.get(self.position) {
+            let due = match self.recording_time(now_ms) {
+                Some(recording_ms) if self.speed.factor().is_some() => frame.time_ms <= recording_ms,
+                _ => true,
+            };
+            if !due {
+                break;
+            }
+            if let Err(mut frame_errors) = self.play_frame(data) {
+                errors.append(&mut frame_errors);
+            }
+        }
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+        Ok(())
+    }
+
+    /// Play the next frame, whatever its time
+    /// # Errors
+    /// Return the errors of the logs that can't be decoded
+    pub fn step(&mut self, now_ms: i64, data: &mut Data) -> Result<(), Vec<TramexError>> {
+        let Some(frame) = self.recording.frames.get(self.position) else {
+            return Ok(());
+        };
+        self.anchor = Some((frame.time_ms, now_ms));
+        self.play_frame(data)
+    }
+
+    /// Decode the next frame like a frame received on the websocket
+    /// # Errors
+    /// Return the errors of the frame
+    fn play_frame(&mut self, data: &mut Data) -> Result<(), Vec<TramexError>> {
+        let Some(frame) = self.recording.frames.get(self.position) else {
+            return Ok(());
+        };
+        self.position += 1;
+        match serde_json::from_str::<WebSocketLog>(&frame.frame) {
+            Ok(decoded) => push_logs(decoded, self.source(), &mut self.resume_point, &mut self.day_start, data),
+            Err(err) => Err(vec![tramex_error!(
+                format!("Frame {}: {err}", self.position),
+                ErrorCode::WebSocketErrorDecodingMessage
+            )]),
+        }
+    }
+}
+
+impl InterfaceTrait for Replay {
+    fn get_more_data(&mut self, _layer_list: Layers, data: &mut Data) -> Result<(), Vec<TramexError>> {
+        self.step(crate::interface::websocket::reconnect::now_ms(), data)
+    }
+
+    fn close(&mut self) -> Result<(), TramexError> {
+        Ok(())
+    }
+}
@@ -1,14 +1,31 @@
 //! WsConnection struct
 use core::fmt::{Debug, Formatter};
 use ewebsock::{WsEvent, WsMessage, WsReceiver, WsSender};
+use std::collections::BTreeMap;
+use std::io::Write;
 use std::vec;
 
 use crate::interface::interface_types::InterfaceTrait;
-use crate::interface::types::BaseMessage;
+use crate::interface::remote_api::{Request, next_message_id};
+use crate::interface::types::{BaseMessage, MessageId};
+use crate::interface::websocket::auth::{Authenticate, AuthenticateMessage};
+use crate::interface::websocket::reconnect::{ResumePoint, now_ms};
+use crate::interface::websocket::recording::{Recorder, push_logs};
 use crate::tramex_error;
 use crate::{data::Data, errors::TramexError};
 
-use crate::interface::{layer::Layers, log_get::LogGet, types::WebSocketLog};
+use crate::interface::{
+    layer::Layers,
+    log_get::{LogGet, LogGetOptions},
+    types::WebSocketLog,
+};
+
+/// Default minimum delay between two log_get in streaming mode (milliseconds)
+const DEFAULT_STREAM_INTERVAL_MS: u64 = 500;
+
+/// Delay after which a log_get without response is forgotten in streaming mode (milliseconds)
+const STREAM_RESPONSE_TIMEOUT_MS: i64 = 10_000;
+
 /// WsConnection struct
 pub struct WsConnection {
     /// WebSocket sender
@@ -17,7 +34,7 @@ pub struct WsConnection {
     /// WebSocket receiver
     pub ws_receiver: WsReceiver,
 
-    /// Message ID
+    /// Message ID of the last message sent
     pub msg_id: u64,
 
     /// Connecting flag
@@ -31,6 +48,51 @@ pub struct WsConnection {
 
     /// Name of the receiver
     pub name: String,
+
+    /// Streaming mode: ask for more logs as soon as a response is received
+    pub streaming: bool,
+
+    /// Streaming mode paused
+    pub stream_paused: bool,
+
+    /// Minimum delay between two log_get in streaming mode (milliseconds)
+    pub stream_interval_ms: u64,
+
+    /// log_get sent without response yet: send time (milliseconds) by message ID
+    outstanding: BTreeMap<u64, i64>,
+
+    /// Time of the last log_get sent (milliseconds)
+    last_request_ms: i64,
+
+    /// Layers of the last log_get sent
+    layers: Option<Layers>,
+
+    /// Last log received
+    resume_point: Option<ResumePoint>,
+
+    /// The logs must be resumed from `resume_point` after a reconnection
+    resume_pending: bool,
+
+    /// Password of the remote API (None if not protected)
+    pub password: Option<String>,
+
+    /// The server refused the authentication
+    pub auth_failed: bool,
+
+    /// Recorder of the frames of logs received (None if not recording)
+    recorder: Option<Recorder<Box<dyn Write>>>,
+
+    /// Options of the log_get requests
+    log_options: LogGetOptions,
+
+    /// Start timestamp of the range asked, sent with the next log_get only
+    pending_start: Option<u64>,
+
+    /// URL of the server, it tells apart the servers with the same name
+    pub url: String,
+
+    /// Midnight (UTC) of the day of the first log received, base of the timestamps of the traces (milliseconds)
+    day_start: Option<u64>,
 }
 
 impl WsConnection {
@@ -39,11 +101,26 @@ impl WsConnection {
         Self {
             ws_sender,
             ws_receiver,
-            msg_id: 1,
+            msg_id: 0,
             connecting: true,
             asking_size_max: 1024,
             available: true,
             name: "".to_string(),
+            streaming: false,
+            stream_paused: false,
+            stream_interval_ms: DEFAULT_STREAM_INTERVAL_MS,
+            outstanding: BTreeMap::new(),
+            last_request_ms: 0,
+            layers: None,
+            resume_point: None,
+            resume_pending: false,
+            password: None,
+            auth_failed: false,
+            recorder: None,
+            log_options: LogGetOptions::default(),
+            pending_start: None,
+            url: String::new(),
+            day_start: None,
         }
     }
 
@@ -64,19 +141,160 @@ impl WsConnection {
     /// Return an error if its fail see [`ewebsock::WsSender::close`] for more details
     pub fn close_impl(&mut self) -> Result<(), TramexError> {
         self.ws_sender.close();
-        Ok(())
+        self.stop_recording()
     }
-}
 
-impl InterfaceTrait for WsConnection {
-    fn get_more_data(&mut self, layer_list: Layers, _data: &mut Data) -> Result<(), Vec<TramexError>> {
-        let msg = LogGet::new(self.msg_id, layer_list, self.asking_size_max);
+    /// Record the frames of logs received from now on
+    pub fn start_recording(&mut self, recorder: Recorder<Box<dyn Write>>) {
+        self.recorder = Some(recorder);
+    }
+
+    /// Stop the recording
+    /// # Errors
+    /// Return an error if the end of the recording can't be written
+    pub fn stop_recording(&mut self) -> Result<(), TramexError> {
+        match self.recorder.take() {
+            Some(recorder) => recorder.finish().map(|_| ()),
+            None => Ok(()),
+        }
+    }
+
+    /// Number of frames recorded, None if not recording
+    pub fn nb_recorded_frames(&self) -> Option<usize> {
+        self.recorder.as_ref().map(Recorder::nb_frames)
+    }
+
+    /// Options of the log_get requests
+    pub fn log_options(&self) -> &LogGetOptions {
+        &self.log_options
+    }
+
+    /// Change the options of the log_get requests
+    ///
+    /// With a start timestamp, the next log_get fetches the logs again from this time, even the logs already
+    /// received; the following ones continue from there.
+    pub fn set_log_options(&mut self, options: LogGetOptions) {
+        if options.start_timestamp.is_some() {
+            self.pending_start = options.start_timestamp;
+            self.resume_point = None;
+        }
+        self.log_options = options;
+    }
+
+    /// Replace the websocket after a reconnection, the logs are resumed once the server is ready
+    pub fn reconnect(&mut self, ws_sender: WsSender, ws_receiver: WsReceiver) {
+        self.ws_sender.close();
+        self.ws_sender = ws_sender;
+        self.ws_receiver = ws_receiver;
+        self.connecting = true;
+        self.available = false;
+        self.outstanding.clear();
+        self.resume_pending = true;
+    }
+
+    /// Resume the logs after a reconnection: send again the last layers, from the last log received
+    /// # Errors
+    /// Return an error if the message can't be encoded
+    fn resume(&mut self) -> Result<(), Vec<TramexError>> {
+        match self.layers.clone() {
+            Some(layers) => {
+                log::info!("Resuming logs from {:?}", self.resume_point);
+                self.send_log_get(layers)
+            }
+            None => {
+                self.resume_pending = false;
+                Ok(())
+            }
+        }
+    }
+
+    /// Handle an authenticate message: answer the challenge or check the result
+    /// # Errors
+    /// Return an error if the authentication failed or if the answer can't be encoded
+    fn authenticate(&mut self, auth: AuthenticateMessage) -> Result<(), Vec<TramexError>> {
+        if let Some(error) = auth.error {
+            self.auth_failed = true;
+            return Err(vec![tramex_error!(
+                format!("Authentication refused by the server: {error}"),
+                crate::errors::ErrorCode::WebSocketAuthenticationFailed
+            )]);
+        }
+        if auth.challenge.is_some() {
+            self.name = auth.name.clone();
+            let password = match &self.password {
+                Some(password) if !password.is_empty() => password.clone(),
+                _ => {
+                    self.auth_failed = true;
+                    return Err(vec![tramex_error!(
+                        format!("{} requires a password", auth.name),
+                        crate::errors::ErrorCode::WebSocketAuthenticationFailed
+                    )]);
+                }
+            };
+            let msg = Authenticate::new(self.next_msg_id(), &auth, &password);
+            match serde_json::to_string(&msg) {
+                Ok(msg_stringed) => {
+                    self.ws_sender.send(WsMessage::Text(msg_stringed));
+                }
+                Err(err) => {
+                    return Err(vec![tramex_error!(
+                        err.to_string(),
+                        crate::errors::ErrorCode::WebSocketErrorEncodingMessage
+                    )]);
+                }
+            }
+            return Ok(());
+        }
+        if auth.ready == Some(true) {
+            log::debug!("Authenticated");
+            if self.resume_pending {
+                self.resume()?;
+            }
+            return Ok(());
+        }
+        self.auth_failed = true;
+        Err(vec![tramex_error!(
+            "Wrong password".to_owned(),
+            crate::errors::ErrorCode::WebSocketAuthenticationFailed
+        )])
+    }
+
+    /// Take a new message ID
+    fn next_msg_id(&mut self) -> u64 {
+        self.msg_id = next_message_id();
+        self.msg_id
+    }
+
+    /// Source of the traces and of the requests: name and URL of the server, None until the name is known
+    pub fn source(&self) -> Option<String> {
+        match (self.name.is_empty(), self.url.is_empty()) {
+            (true, _) => None,
+            (false, true) => Some(self.name.clone()),
+            (false, false) => Some(format!("{} ({})", self.name, self.url)),
+        }
+    }
+
+    /// Send a log_get message
+    /// # Errors
+    /// Return an error if the message can't be encoded
+    fn send_log_get(&mut self, layer_list: Layers) -> Result<(), Vec<TramexError>> {
+        let start_timestamp = match self.resume_pending {
+            true => self.resume_point.map(|point| point.timestamp),
+            false => self.pending_start.take(),
+        };
+        let message_id = self.next_msg_id();
+        let msg = LogGet::new(message_id, layer_list.clone(), self.asking_size_max)
+            .with_options(&self.log_options)
+            .with_start_timestamp(start_timestamp);
         log::debug!("Sending message: {msg:?}");
         match serde_json::to_string(&msg) {
             Ok(msg_stringed) => {
                 log::debug!("{msg_stringed}");
                 self.ws_sender.send(WsMessage::Text(msg_stringed));
-                self.msg_id += 1;
+                self.last_request_ms = now_ms();
+                self.outstanding.insert(message_id, self.last_request_ms);
+                self.layers = Some(layer_list);
+                self.resume_pending = false;
             }
             Err(err) => {
                 log::error!("Error encoding message: {err:?}");
@@ -89,6 +307,95 @@ impl InterfaceTrait for WsConnection {
         Ok(())
     }
 
+    /// Number of log_get sent without response yet
+    pub fn nb_outstanding(&self) -> usize {
+        self.outstanding.len()
+    }
+
+    /// Check if a new log_get should be sent in streaming mode
+    ///
+    /// A new log_get is sent when the previous ones got their response and the minimum delay is elapsed.
+    pub fn should_stream(&mut self) -> bool {
+        if !self.streaming || self.stream_paused || !self.available {
+            return false;
+        }
+        let now = now_ms();
+        self.outstanding.retain(|id, sent_ms| {
+            let keep = now - *sent_ms < STREAM_RESPONSE_TIMEOUT_MS;
+            if !keep {
+                log::warn!("No response to log_get {id}, forgetting it");
+            }
+            keep
+        });
+        self.outstanding.is_empty() && self.next_stream_request_in_ms() == Some(0)
+    }
+
+    /// Delay before the next log_get in streaming mode (milliseconds), None if not streaming
+    ///
+    /// While a response is awaited, the delay is the time left before the oldest request is forgotten.
+    pub fn next_stream_request_in_ms(&self) -> Option<u64> {
+        if !self.streaming || self.stream_paused {
+            return None;
+        }
+        let now = now_ms();
+        if let Some(oldest_ms) = self.outstanding.values().min() {
+            return Some((oldest_ms + STREAM_RESPONSE_TIMEOUT_MS - now).max(0) as u64);
+        }
+        let elapsed = (now - self.last_request_ms).max(0) as u64;
+        Some(self.stream_interval_ms.saturating_sub(elapsed))
+    }
+
+    /// Send a request of the remote API
+    /// # Errors
+    /// Return an error if the request can't be encoded
+    fn send_request(&mut self, request: Request, data: &mut Data) -> Result<(), TramexError> {
+        // the message ID given when the request was queued lets the panel find the response
+        let message_id = match request.message_id {
+            Some(message_id) => {
+                self.msg_id = message_id;
+                message_id
+            }
+            None => self.next_msg_id(),
+        };
+        match serde_json::to_string(&request.to_json(message_id)) {
+            Ok(msg_stringed) => {
+                log::debug!("{msg_stringed}");
+                self.ws_sender.send(WsMessage::Text(msg_stringed));
+                let source = self.source().unwrap_or_else(|| self.name.clone());
+                data.remote.sent(message_id, request, &source, now_ms());
+                Ok(())
+            }
+            Err(err) => Err(tramex_error!(
+                err.to_string(),
+                crate::errors::ErrorCode::WebSocketErrorEncodingMessage
+            )),
+        }
+    }
+
+    /// Match a response with its request: log_get or request of the remote API
+    /// Return true if the message is the response of a request of the remote API
+    fn match_response(&mut self, event_text: &str, data: &mut Data) -> bool {
+        let Ok(MessageId {
+            message_id: Some(message_id),
+        }) = serde_json::from_str(event_text)
+        else {
+            return false;
+        };
+        if self.outstanding.remove(&message_id).is_some() {
+            return false;
+        }
+        match serde_json::from_str::<serde_json::Value>(event_text) {
+            Ok(response) => data.remote.received(message_id, response, now_ms()),
+            Err(_) => false,
+        }
+    }
+}
+
+impl InterfaceTrait for WsConnection {
+    fn get_more_data(&mut self, layer_list: Layers, _data: &mut Data) -> Result<(), Vec<TramexError>> {
+        self.send_log_get(layer_list)
+    }
+
     fn close(&mut self) -> Result<(), TramexError> {
         self.close_impl()
     }
@@ -99,6 +406,20 @@ impl WsConnection {
     /// # Errors
     /// Return an error if the data is not received correctly
     pub fn try_recv(&mut self, data: &mut Data) -> Result<(), Vec<TramexError>> {
+        let source = self.source();
+        let connected = self.available && !self.connecting && !self.auth_failed;
+        if let (Some(source), true) = (&source, connected) {
+            data.remote.set_connected(source);
+            let mut errors = vec![];
+            for request in data.remote.take_outgoing(source) {
+                if let Err(err) = self.send_request(request, data) {
+                    errors.push(err);
+                }
+            }
+            if !errors.is_empty() {
+                return Err(errors);
+            }
+        }
         while let Some(event) = self.ws_receiver.try_recv() {
             self.connecting = false;
             match event {
@@ -106,23 +427,28 @@ impl WsConnection {
                     self.available = true;
                     match msg {
                         WsMessage::Text(event_text) => {
+                            if self.match_response(&event_text, data) {
+                                continue;
+                            }
+                            if let Ok(auth) = serde_json::from_str::<AuthenticateMessage>(&event_text) {
+                                if auth.message == "authenticate" {
+                                    self.authenticate(auth)?;
+                                    continue;
+                                }
+                            }
                             let decoded: Result<WebSocketLog, serde_json::Error> = serde_json::from_str(&event_text);
                             match decoded {
                                 Ok(decoded_data) => {
-                                    let mut errors = vec![];
-                                    for one_log in decoded_data.logs {
-                                        match one_log.extract_data() {
-                                            Ok(trace) => {
-                                                data.events.push(trace);
-                                            }
-                                            Err(err) => {
-                                                log::error!("Error while extracting data: {err:?}");
-                                                errors.push(err);
-                                            }
-                                        }
-                                    }
-                                    if !errors.is_empty() {
-                                        return Err(errors);
+                                    let recorded = match &mut self.recorder {
+                                        Some(recorder) => recorder.record(now_ms(), &event_text),
+                                        None => Ok(()),
+                                    };
+                                    let source = self.source();
+                                    push_logs(decoded_data, source, &mut self.resume_point, &mut self.day_start, data)?;
+                                    if let Err(err) = recorded {
+                                        // the logs are kept, the recording is stopped instead of reporting the error for each frame
+                                        self.recorder = None;
+                                        return Err(vec![err]);
                                     }
                                 }
                                 Err(_err) => {
@@ -130,11 +456,14 @@ impl WsConnection {
                                         serde_json::from_str(&event_text);
                                     match decoded_base {
                                         Ok(decoded_data) => {
+                                            log::debug!("Received BaseMessage: {decoded_data:?}");
+                                            self.name = decoded_data.name;
                                             if decoded_data.message == "ready" {
                                                 log::debug!("Received ready message");
+                                                if self.resume_pending {
+                                                    self.resume()?;
+                                                }
                                             }
-                                            log::debug!("Received BaseMessage: {decoded_data:?}");
-                                            self.name = decoded_data.name;
                                         }
                                         Err(err) => {
                                             log::error!("Error decoding message: {err:?}");
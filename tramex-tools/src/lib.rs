@@ -9,6 +9,12 @@
 )]
 #![warn(clippy::multiple_crate_versions)]
 
+pub mod analysis;
 pub mod data;
 pub mod errors;
+pub mod hexdump;
 pub mod interface;
+#[cfg(feature = "mock-server")]
+pub mod mock_server;
+pub mod search;
+pub mod session;
@@ -0,0 +1,376 @@
+//! Mock of the Amarisoft remote API, to test the websocket interface without a server
+//!
+//! The mock answers `log_get` with the logs of a log file (with the `start_timestamp`, `end_timestamp`, `max` and
+//! `ue_id` parameters), `config_get` with a static configuration and accepts `log_set`.
+//! [`MockServer::handle_message`] implements the protocol, [`MockServer::start`] serves it on a websocket.
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+use serde_json::{Value, json};
+
+use crate::data::{AdditionalInfos, Trace};
+use crate::errors::{ErrorCode, TramexError};
+use crate::hexdump::{DEFAULT_WIDTH, dump};
+use crate::interface::interface_file::file_handler::File;
+use crate::interface::types::Direction;
+use crate::interface::websocket::reconnect::now_ms;
+use crate::tramex_error;
+
+/// Default number of logs sent in a log_get response
+pub const DEFAULT_BATCH_SIZE: usize = 50;
+
+/// Delay between two checks of the stop and disconnect requests
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Number of milliseconds in a day
+const MS_IN_DAY: u64 = 86_400_000;
+
+#[derive(Debug, Clone)]
+/// Configuration of the mock server
+pub struct MockConfig {
+    /// Name of the server, sent in the ready message
+    pub name: String,
+
+    /// Type of the server (ENB or MME)
+    pub server_type: String,
+
+    /// Version of the server, sent in the ready message
+    pub version: String,
+
+    /// Maximum number of logs sent in a log_get response (the `max` of the request if smaller)
+    pub batch_size: usize,
+
+    /// Timestamp of the start of the day of the logs (milliseconds), the log file only gives the time of the day
+    pub day_start_ms: u64,
+}
+
+impl Default for MockConfig {
+    fn default() -> Self {
+        let now = now_ms().max(0) as u64;
+        Self {
+            name: "ENB".to_owned(),
+            server_type: "ENB".to_owned(),
+            version: "mock".to_owned(),
+            batch_size: DEFAULT_BATCH_SIZE,
+            day_start_ms: now - now % MS_IN_DAY,
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+/// State of a connection to the mock server
+pub struct Session {
+    /// Index of the next log to send
+    cursor: usize,
+}
+
+#[derive(Debug)]
+/// Mock of the remote API
+pub struct MockServer {
+    /// Configuration
+    pub config: MockConfig,
+
+    /// Logs served, ordered by timestamp
+    logs: Vec<Value>,
+
+    /// Start of the server (milliseconds), for the `time` of the messages
+    start_ms: i64,
+}
+
+impl MockServer {
+    /// Create a mock server serving the given logs (in the format of the remote API)
+    pub fn new(config: MockConfig, logs: Vec<Value>) -> Self {
+        Self {
+            config,
+            logs,
+            start_ms: now_ms(),
+        }
+    }
+
+    /// Create a mock server serving the logs of a log file, the blocks that can't be parsed are skipped
+    pub fn from_log_file(config: MockConfig, content: String) -> Self {
+        let mut file = File::new_file_content("mock.log".into(), content);
+        let mut traces = vec![];
+        while !file.full_read {
+            let (mut batch, errors) = file.process();
+            traces.append(&mut batch);
+            for err in errors {
+                if !matches!(err.get_code(), ErrorCode::EndOfFile | ErrorCode::ParsingLayerNotImplemented) {
+                    log::warn!("Skipping a log: {}", err.get_msg());
+                }
+            }
+        }
+        let logs = traces
+            .iter()
+            .enumerate()
+            .map(|(idx, trace)| log_of(trace, idx as u64, &config))
+            .collect();
+        Self::new(config, logs)
+    }
+
+    /// Number of logs served
+    pub fn nb_logs(&self) -> usize {
+        self.logs.len()
+    }
+
+    /// Timestamps of the logs served (milliseconds)
+    pub fn timestamps(&self) -> Vec<u64> {
+        self.logs.iter().filter_map(log_timestamp).collect()
+    }
+
+    /// Time and UTC fields of the messages
+    fn times(&self) -> (f64, f64) {
+        let now = now_ms();
+        ((now - self.start_ms) as f64 / 1000.0, now as f64 / 1000.0)
+    }
+
+    /// Ready message sent when a client connects
+    pub fn ready_message(&self) -> String {
+        let (time, utc) = self.times();
+        json!({
+            "message": "ready",
+            "type": self.config.server_type,
+            "name": self.config.name,
+            "version": self.config.version,
+            "time": time,
+            "utc": utc,
+        })
+        .to_string()
+    }
+
+    /// Answer a message of a client, None if the message is not a JSON object
+    pub fn handle_message(&self, session: &mut Session, text: &str) -> Option<String> {
+        let Ok(Value::Object(request)) = serde_json::from_str::<Value>(text) else {
+            log::warn!("Invalid message {text:?}");
+            return None;
+        };
+        let message = request.get("message").and_then(Value::as_str).unwrap_or_default();
+        let (time, utc) = self.times();
+        let mut response = json!({
+            "message": message,
+            "message_id": request.get("message_id").cloned().unwrap_or(Value::Null),
+            "time": time,
+            "utc": utc,
+        });
+        match message {
+            "log_get" => {
+                if let Some(start_timestamp) = request.get("start_timestamp").and_then(Value::as_u64) {
+                    session.cursor = self
+                        .logs
+                        .partition_point(|one_log| log_timestamp(one_log).is_some_and(|ts| ts < start_timestamp));
+                }
+                let max = match request.get("max").and_then(Value::as_u64) {
+                    Some(max) => (max as usize).min(self.config.batch_size),
+                    None => self.config.batch_size,
+                };
+                let end_timestamp = request.get("end_timestamp").and_then(Value::as_u64);
+                let ue_id = request.get("ue_id").and_then(Value::as_u64);
+                let mut logs = vec![];
+                while let Some(one_log) = self.logs.get(session.cursor) {
+                    if logs.len() >= max || end_timestamp.is_some_and(|end| log_timestamp(one_log) > Some(end)) {
+                        break;
+                    }
+                    session.cursor += 1;
+                    if ue_id.is_none() || one_log.get("ue_id").and_then(Value::as_u64) == ue_id {
+                        logs.push(one_log.clone());
+                    }
+                }
+                response["logs"] = Value::from(logs);
+            }
+            "config_get" => {
+                response["type"] = Value::from(self.config.server_type.clone());
+                response["name"] = Value::from(self.config.name.clone());
+                response["version"] = Value::from(self.config.version.clone());
+                response["logs"] = json!({
+                    "layers": {"RRC": {"level": "debug", "max_size": 1}},
+                    "bcch": true,
+                    "mib": true,
+                });
+            }
+            "log_set" => {}
+            _ => {
+                response["error"] = Value::from(format!("Unknown message: {message}"));
+            }
+        }
+        Some(response.to_string())
+    }
+
+    /// Serve the remote API on a websocket, in a background thread
+    /// # Errors
+    /// Return an error if the address can't be bound
+    pub fn start(self, addr: &str) -> Result<MockServerHandle, TramexError> {
+        let listener = match TcpListener::bind(addr) {
+            Ok(listener) => listener,
+            Err(err) => {
+                return Err(tramex_error!(
+                    format!("Can't listen on {addr}: {err}"),
+                    ErrorCode::MockServerError
+                ));
+            }
+        };
+        let local_addr = match listener.local_addr().and_then(|local_addr| {
+            listener.set_nonblocking(true)?;
+            Ok(local_addr)
+        }) {
+            Ok(local_addr) => local_addr,
+            Err(err) => return Err(tramex_error!(err.to_string(), ErrorCode::MockServerError)),
+        };
+        let server = Arc::new(self);
+        let stop = Arc::new(AtomicBool::new(false));
+        let generation = Arc::new(AtomicU64::new(0));
+        let thread = {
+            let stop = stop.clone();
+            let generation = generation.clone();
+            std::thread::spawn(move || {
+                while !stop.load(Ordering::Relaxed) {
+                    match listener.accept() {
+                        Ok((stream, peer)) => {
+                            log::info!("Client {peer} connected");
+                            let server = server.clone();
+                            let stop = stop.clone();
+                            let generation = generation.clone();
+                            std::thread::spawn(move || server.serve(stream, &stop, &generation));
+                        }
+                        Err(err) if err.kind() == std::io::ErrorKind::WouldBlock => std::thread::sleep(POLL_INTERVAL),
+                        Err(err) => {
+                            log::error!("Error accepting a client: {err}");
+                            std::thread::sleep(POLL_INTERVAL);
+                        }
+                    }
+                }
+            })
+        };
+        Ok(MockServerHandle {
+            local_addr,
+            stop,
+            generation,
+            thread: Some(thread),
+        })
+    }
+
+    /// Serve one client until it disconnects or the server asks to disconnect it
+    fn serve(&self, stream: TcpStream, stop: &AtomicBool, generation: &AtomicU64) {
+        let client_generation = generation.load(Ordering::Relaxed);
+        if let Err(err) = stream.set_nonblocking(false) {
+            log::error!("Error configuring the client: {err}");
+            return;
+        }
+        let mut socket = match tungstenite::accept(stream) {
+            Ok(socket) => socket,
+            Err(err) => {
+                log::error!("Websocket handshake failed: {err}");
+                return;
+            }
+        };
+        if let Err(err) = socket.get_ref().set_read_timeout(Some(POLL_INTERVAL)) {
+            log::error!("Error configuring the client: {err}");
+            return;
+        }
+        if socket.send(tungstenite::Message::Text(self.ready_message())).is_err() {
+            return;
+        }
+        let mut session = Session::default();
+        loop {
+            if stop.load(Ordering::Relaxed) || generation.load(Ordering::Relaxed) != client_generation {
+                let _ = socket.close(None);
+                let _ = socket.flush();
+                return;
+            }
+            match socket.read() {
+                Ok(tungstenite::Message::Text(text)) => {
+                    if let Some(response) = self.handle_message(&mut session, &text) {
+                        if socket.send(tungstenite::Message::Text(response)).is_err() {
+                            return;
+                        }
+                    }
+                }
+                Ok(tungstenite::Message::Close(_)) => return,
+                Ok(_) => {}
+                Err(tungstenite::Error::Io(err))
+                    if matches!(err.kind(), std::io::ErrorKind::WouldBlock | std::io::ErrorKind::TimedOut) => {}
+                Err(err) => {
+                    log::debug!("Client disconnected: {err}");
+                    return;
+                }
+            }
+        }
+    }
+}
+
+/// Handle of a running mock server, the server stops when the handle is dropped
+pub struct MockServerHandle {
+    /// Address the server listens on
+    pub local_addr: SocketAddr,
+
+    /// The server must stop
+    stop: Arc<AtomicBool>,
+
+    /// Incremented to disconnect the connected clients
+    generation: Arc<AtomicU64>,
+
+    /// Thread accepting the clients
+    thread: Option<JoinHandle<()>>,
+}
+
+impl MockServerHandle {
+    /// URL of the websocket
+    pub fn url(&self) -> String {
+        format!("ws://{}", self.local_addr)
+    }
+
+    /// Close the connection of every connected client, as if the server restarted
+    pub fn disconnect_all(&self) {
+        self.generation.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Wait until the server stops
+    pub fn wait(mut self) {
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+impl Drop for MockServerHandle {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+/// Timestamp of a log of the remote API (milliseconds)
+fn log_timestamp(one_log: &Value) -> Option<u64> {
+    one_log.get("timestamp").and_then(Value::as_u64)
+}
+
+/// Log of the remote API built from a trace of a log file
+fn log_of(trace: &Trace, idx: u64, config: &MockConfig) -> Value {
+    let AdditionalInfos::RRCInfos(infos) = &trace.additional_infos;
+    let mut data = if infos.canal.is_empty() {
+        vec![infos.canal_msg.clone()]
+    } else {
+        vec![format!("{}: {}", infos.canal, infos.canal_msg)]
+    };
+    data.extend(dump(&trace.hexa, DEFAULT_WIDTH).lines().map(str::to_owned));
+    if let Some(text) = &trace.text {
+        data.extend(text.iter().cloned());
+    }
+    json!({
+        "data": data,
+        "src": config.server_type,
+        "idx": idx,
+        "level": 3,
+        "timestamp": config.day_start_ms + trace.timestamp,
+        "layer": trace.layer,
+        "dir": match &infos.direction {
+            Direction::NONE => "-".to_owned(),
+            direction => format!("{direction:?}"),
+        },
+        "ue_id": infos.ue_id,
+    })
+}
@@ -0,0 +1,306 @@
+//! Search of the traces: text, regular expression or hexadecimal bytes
+//!
+//! A text or regular expression search matches the message name (`canal_msg`) and the text of the traces, a
+//! hexadecimal search matches the bytes of their payload. `??` matches any byte in a hexadecimal pattern.
+use std::ops::Range;
+
+use regex::{Regex, RegexBuilder};
+
+use crate::data::{AdditionalInfos, Data, Trace};
+use crate::errors::{ErrorCode, TramexError};
+use crate::tramex_error;
+
+#[derive(serde::Deserialize, serde::Serialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+/// Kind of pattern searched
+pub enum SearchMode {
+    /// Plain text
+    #[default]
+    Text,
+
+    /// Regular expression
+    Regex,
+
+    /// Hexadecimal bytes, such as `0a 1b ?? 3c`
+    Hex,
+}
+
+#[derive(serde::Deserialize, serde::Serialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+/// Search entered by the user
+pub struct SearchQuery {
+    /// Pattern searched
+    pub pattern: String,
+
+    /// Kind of pattern
+    pub mode: SearchMode,
+
+    /// Match the case of the text
+    pub case_sensitive: bool,
+
+    /// Match whole words only
+    pub whole_word: bool,
+
+    /// Search in the message names
+    pub in_message: bool,
+
+    /// Search in the text of the traces
+    pub in_text: bool,
+}
+
+impl Default for SearchQuery {
+    fn default() -> Self {
+        Self {
+            pattern: String::new(),
+            mode: SearchMode::Text,
+            case_sensitive: false,
+            whole_word: false,
+            in_message: true,
+            in_text: true,
+        }
+    }
+}
+
+impl SearchQuery {
+    /// Create a search of a pattern with the default options
+    pub fn new(pattern: &str, mode: SearchMode) -> Self {
+        Self {
+            pattern: pattern.to_owned(),
+            mode,
+            ..Default::default()
+        }
+    }
+
+    /// Match the case of the text
+    pub fn case_sensitive(mut self, case_sensitive: bool) -> Self {
+        self.case_sensitive = case_sensitive;
+        self
+    }
+
+    /// Match whole words only
+    pub fn whole_word(mut self, whole_word: bool) -> Self {
+        self.whole_word = whole_word;
+        self
+    }
+}
+
+#[derive(Debug, Clone)]
+/// Pattern of a search, ready to match
+enum Matcher {
+    /// Regular expression matching the text
+    Text(Regex),
+
+    /// Bytes, None matching any byte
+    Hex(Vec<Option<u8>>),
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+/// Matches of a search in a trace
+pub struct TraceMatches {
+    /// Ranges of the matches in the message name (bytes)
+    pub message: Vec<Range<usize>>,
+
+    /// Ranges of the matches in the lines of the text, with the index of the line
+    pub text: Vec<(usize, Range<usize>)>,
+
+    /// Ranges of the matches in the payload (bytes)
+    pub hexa: Vec<Range<usize>>,
+}
+
+impl TraceMatches {
+    /// Check if the search matches nothing
+    pub fn is_empty(&self) -> bool {
+        self.message.is_empty() && self.text.is_empty() && self.hexa.is_empty()
+    }
+}
+
+#[derive(Debug, Clone)]
+/// Search ready to match the traces
+pub struct Search {
+    /// Search entered by the user
+    pub query: SearchQuery,
+
+    /// Pattern compiled
+    matcher: Matcher,
+}
+
+impl Search {
+    /// Compile a search
+    /// # Errors
+    /// Return an error if the pattern is empty, is not a valid regular expression or is not made of hexadecimal bytes
+    pub fn new(query: &SearchQuery) -> Result<Self, TramexError> {
+        if query.pattern.trim().is_empty() {
+            return Err(tramex_error!("Empty search".to_owned(), ErrorCode::SearchInvalidPattern));
+        }
+        let matcher = match query.mode {
+            SearchMode::Hex => Matcher::Hex(parse_hex_pattern(&query.pattern)?),
+            SearchMode::Text | SearchMode::Regex => {
+                let mut pattern = match query.mode {
+                    SearchMode::Regex => query.pattern.clone(),
+                    _ => regex::escape(&query.pattern),
+                };
+                if query.whole_word {
+                    pattern = format!(r"\b(?:{pattern})\b");
+                }
+                match RegexBuilder::new(&pattern).case_insensitive(!query.case_sensitive).build() {
+                    Ok(regex) => Matcher::Text(regex),
+                    Err(err) => {
+                        return Err(tramex_error!(
+                            format!("Invalid regular expression: {err}"),
+                            ErrorCode::SearchInvalidPattern
+                        ));
+                    }
+                }
+            }
+        };
+        Ok(Self {
+            query: query.clone(),
+            matcher,
+        })
+    }
+
+    /// Ranges of the matches in a text (bytes), empty for a hexadecimal search
+    pub fn find_in_str(&self, text: &str) -> Vec<Range<usize>> {
+        match &self.matcher {
+            Matcher::Text(regex) => regex
+                .find_iter(text)
+                .filter(|found| !found.is_empty())
+                .map(|found| found.range())
+                .collect(),
+            Matcher::Hex(_) => vec![],
+        }
+    }
+
+    /// Ranges of the matches in bytes, empty for a text search
+    pub fn find_in_bytes(&self, bytes: &[u8]) -> Vec<Range<usize>> {
+        let Matcher::Hex(pattern) = &self.matcher else {
+            return vec![];
+        };
+        let mut ranges = vec![];
+        let mut start = 0;
+        while start + pattern.len() <= bytes.len() {
+            let window = &bytes[start..start + pattern.len()];
+            if pattern
+                .iter()
+                .zip(window)
+                .all(|(wanted, byte)| wanted.is_none_or(|wanted| wanted == *byte))
+            {
+                ranges.push(start..start + pattern.len());
+                start += pattern.len();
+            } else {
+                start += 1;
+            }
+        }
+        ranges
+    }
+
+    /// Matches of the search in a trace
+    pub fn trace_matches(&self, trace: &Trace) -> TraceMatches {
+        let AdditionalInfos::RRCInfos(infos) = &trace.additional_infos;
+        let mut matches = TraceMatches::default();
+        if self.query.in_message {
+            matches.message = self.find_in_str(&infos.canal_msg);
+        }
+        if self.query.in_text {
+            if let Some(lines) = &trace.text {
+                for (index, line) in lines.iter().enumerate() {
+                    matches
+                        .text
+                        .extend(self.find_in_str(line).into_iter().map(|range| (index, range)));
+                }
+            }
+        }
+        matches.hexa = self.find_in_bytes(&trace.hexa);
+        matches
+    }
+
+    /// Check if the search matches a trace
+    pub fn matches(&self, trace: &Trace) -> bool {
+        match &self.matcher {
+            Matcher::Hex(_) => !self.find_in_bytes(&trace.hexa).is_empty(),
+            Matcher::Text(regex) => {
+                let AdditionalInfos::RRCInfos(infos) = &trace.additional_infos;
+                (self.query.in_message && regex.is_match(&infos.canal_msg))
+                    || (self.query.in_text
+                        && trace
+                            .text
+                            .as_ref()
+                            .is_some_and(|lines| lines.iter().any(|line| regex.is_match(line))))
+            }
+        }
+    }
+
+    /// Indexes of the traces matching the search
+    pub fn matching_indexes(&self, data: &Data) -> Vec<usize> {
+        data.events
+            .iter()
+            .enumerate()
+            .filter(|(_, trace)| self.matches(trace))
+            .map(|(index, _)| index)
+            .collect()
+    }
+}
+
+/// Index of the next trace matching the search after the current one, from the first trace after the last one
+///
+/// `matching` are the indexes of the traces matching the search, in order.
+pub fn next_match(matching: &[usize], current_index: usize) -> Option<usize> {
+    let after = matching.partition_point(|index| *index <= current_index);
+    matching.get(after).or(matching.first()).copied()
+}
+
+/// Index of the previous trace matching the search before the current one, from the last trace before the first one
+///
+/// `matching` are the indexes of the traces matching the search, in order.
+pub fn previous_match(matching: &[usize], current_index: usize) -> Option<usize> {
+    let before = matching.partition_point(|index| *index < current_index);
+    match before {
+        0 => matching.last().copied(),
+        before => matching.get(before - 1).copied(),
+    }
+}
+
+/// Parse a pattern of hexadecimal bytes: pairs of digits, optionally prefixed by `0x` and separated by spaces,
+/// commas or colons, `??` matching any byte
+/// # Errors
+/// Return an error with the position of the first invalid character
+pub fn parse_hex_pattern(pattern: &str) -> Result<Vec<Option<u8>>, TramexError> {
+    let mut bytes = vec![];
+    for word in pattern.split([' ', ',', ':']).filter(|word| !word.is_empty()) {
+        let digits = word.strip_prefix("0x").or_else(|| word.strip_prefix("0X")).unwrap_or(word);
+        let column = word.as_ptr() as usize - pattern.as_ptr() as usize + word.len() - digits.len();
+        if digits.len() % 2 != 0 {
+            return Err(tramex_error!(
+                format!("Odd number of hexadecimal digits in {word:?} (column {})", column + 1),
+                ErrorCode::SearchInvalidPattern
+            ));
+        }
+        for (index, pair) in digits.as_bytes().chunks(2).enumerate() {
+            if pair == b"??" {
+                bytes.push(None);
+                continue;
+            }
+            let byte = match pair.iter().all(u8::is_ascii_hexdigit) {
+                true => std::str::from_utf8(pair)
+                    .ok()
+                    .and_then(|pair| u8::from_str_radix(pair, 16).ok()),
+                false => None,
+            };
+            match byte {
+                Some(byte) => bytes.push(Some(byte)),
+                None => {
+                    return Err(tramex_error!(
+                        format!("Invalid hexadecimal byte in {word:?} (column {})", column + index * 2 + 1),
+                        ErrorCode::SearchInvalidPattern
+                    ));
+                }
+            }
+        }
+    }
+    if bytes.is_empty() {
+        return Err(tramex_error!(
+            "No byte in the hexadecimal pattern".to_owned(),
+            ErrorCode::SearchInvalidPattern
+        ));
+    }
+    Ok(bytes)
+}
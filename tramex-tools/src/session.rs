@@ -0,0 +1,246 @@
+//! Session: the traces loaded and the state of an investigation, saved in a single file
+//!
+//! A session is a JSON document with the traces already parsed, so it is opened again without the original logs
+//! and without parsing them. The layout of the panels is defined by the application and kept as is.
+use crate::data::{Data, Trace, TraceFilter};
+use crate::errors::{ErrorCode, TramexError};
+use crate::interface::layer::Layers;
+use crate::tramex_error;
+
+/// Value of the `session` field of a session
+const SESSION_KIND: &str = "tramex-session";
+
+/// Version of the session format
+pub const SESSION_VERSION: u32 = 1;
+
+/// Extension of the session files
+pub const SESSION_EXTENSION: &str = "tramex";
+
+/// Prefix of the source of the traces restored from a session, to tell them apart from the traces of a live source
+pub const SESSION_SOURCE_PREFIX: &str = "session:";
+
+/// Source of a trace restored from a session: its source prefixed once with [`SESSION_SOURCE_PREFIX`]
+pub fn session_source(source: &str) -> String {
+    match source.starts_with(SESSION_SOURCE_PREFIX) {
+        true => source.to_owned(),
+        false => format!("{SESSION_SOURCE_PREFIX}{source}"),
+    }
+}
+
+#[derive(serde::Deserialize, serde::Serialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+/// Kind of interface a source was read from
+pub enum SourceKind {
+    /// Log file
+    #[default]
+    File,
+
+    /// Server of the remote API
+    WebSocket,
+
+    /// Recording of a websocket
+    Replay,
+}
+
+#[derive(serde::Deserialize, serde::Serialize, Debug, Clone, PartialEq)]
+/// Source of the traces of a session
+pub struct SourceInfo {
+    /// Name of the source, the `source` of its traces
+    pub name: String,
+
+    /// Kind of interface
+    pub kind: SourceKind,
+
+    /// Path of the file or URL of the server
+    #[serde(default)]
+    pub location: Option<String>,
+
+    /// Offset added to the timestamps of the file (milliseconds)
+    #[serde(default)]
+    pub clock_offset_ms: i64,
+}
+
+impl SourceInfo {
+    /// Create the information of a source
+    pub fn new(name: &str, kind: SourceKind) -> Self {
+        Self {
+            name: name.to_owned(),
+            kind,
+            location: None,
+            clock_offset_ms: 0,
+        }
+    }
+
+    /// Set the path of the file or the URL of the server
+    pub fn with_location(mut self, location: &str) -> Self {
+        self.location = Some(location.to_owned());
+        self
+    }
+
+    /// Set the clock offset of the file
+    pub fn with_clock_offset(mut self, clock_offset_ms: i64) -> Self {
+        self.clock_offset_ms = clock_offset_ms;
+        self
+    }
+}
+
+#[derive(serde::Deserialize, serde::Serialize, Debug, Clone)]
+/// Session saved in a file
+pub struct Session {
+    /// Kind of file, always "tramex-session"
+    pub session: String,
+
+    /// Version of the format
+    pub version: u32,
+
+    /// Time the session was saved (milliseconds)
+    pub saved_ms: i64,
+
+    /// Sources of the traces
+    #[serde(default)]
+    pub sources: Vec<SourceInfo>,
+
+    /// Traces shown, ordered by timestamp
+    pub events: Vec<Trace>,
+
+    /// Traces of the layers hidden, ordered by timestamp
+    #[serde(default)]
+    pub hidden_events: Vec<Trace>,
+
+    /// Index of the current trace
+    #[serde(default)]
+    pub current_index: usize,
+
+    /// Filter shared by the panels
+    #[serde(default)]
+    pub filter: TraceFilter,
+
+    /// Layout of the panels, defined by the application
+    #[serde(default)]
+    pub layout: serde_json::Value,
+
+    /// Log configuration of the layers, it tells which traces are hidden
+    #[serde(default)]
+    pub layers: Option<Layers>,
+}
+
+impl Session {
+    /// Create a session with the traces of the data
+    pub fn new(data: &Data, sources: Vec<SourceInfo>, layout: serde_json::Value, saved_ms: i64) -> Self {
+        Self {
+            session: SESSION_KIND.to_owned(),
+            version: SESSION_VERSION,
+            saved_ms,
+            sources,
+            events: data.events.clone(),
+            hidden_events: data.hidden_events.clone(),
+            current_index: data.current_index,
+            filter: data.filter.clone(),
+            layout,
+            layers: None,
+        }
+    }
+
+    /// Set the log configuration of the layers
+    pub fn with_layers(mut self, layers: Layers) -> Self {
+        self.layers = Some(layers);
+        self
+    }
+
+    /// Encode the session
+    /// # Errors
+    /// Return an error if the session can't be encoded
+    pub fn to_json(&self) -> Result<String, TramexError> {
+        match serde_json::to_string(self) {
+            Ok(json) => Ok(json),
+            Err(err) => Err(tramex_error!(err.to_string(), ErrorCode::SessionErrorWriting)),
+        }
+    }
+
+    /// Save the session in a file
+    /// # Errors
+    /// Return an error if the session can't be encoded or the file can't be written
+    pub fn save(&self, path: &std::path::Path) -> Result<(), TramexError> {
+        let json = self.to_json()?;
+        match std::fs::write(path, json) {
+            Ok(()) => Ok(()),
+            Err(err) => Err(tramex_error!(
+                format!("Can't write {}: {err}", path.display()),
+                ErrorCode::SessionErrorWriting
+            )),
+        }
+    }
+
+    /// Read a session
+    /// # Errors
+    /// Return an error with the line and column if the content is not a session
+    pub fn parse(content: &str) -> Result<Self, TramexError> {
+        let value: serde_json::Value = match serde_json::from_str(content) {
+            Ok(value) => value,
+            Err(err) => {
+                return Err(tramex_error!(
+                    format!("Invalid session (line {}, column {}): {err}", err.line(), err.column()),
+                    ErrorCode::SessionInvalid
+                ));
+            }
+        };
+        if value.get("session").and_then(serde_json::Value::as_str) != Some(SESSION_KIND) {
+            return Err(tramex_error!(
+                "Not a session: the kind of file is not a tramex session".to_owned(),
+                ErrorCode::SessionInvalid
+            ));
+        }
+        let version = value.get("version").and_then(serde_json::Value::as_u64).unwrap_or(0);
+        if version > SESSION_VERSION as u64 {
+            return Err(tramex_error!(
+                format!("Unsupported session version {version}"),
+                ErrorCode::SessionInvalid
+            ));
+        }
+        match serde_json::from_value(value) {
+            Ok(session) => Ok(session),
+            Err(err) => Err(tramex_error!(format!("Invalid session: {err}"), ErrorCode::SessionInvalid)),
+        }
+    }
+
+    /// Move the traces of the session to the data and return their sources
+    ///
+    /// The traces are merged with the traces already loaded. Their source is prefixed with
+    /// [`SESSION_SOURCE_PREFIX`], so they are removed without the traces of a live source with the same name.
+    /// The current trace and the filter of the session are restored when there was no trace loaded.
+    pub fn restore(&mut self, data: &mut Data) -> Vec<String> {
+        let was_empty = data.events.is_empty() && data.hidden_events.is_empty();
+        let mut sources: Vec<String> = vec![];
+        let mut tag = |trace: &mut Trace| {
+            let source = session_source(&trace.source.take().unwrap_or_default());
+            if !sources.contains(&source) {
+                sources.push(source.clone());
+            }
+            trace.source = Some(source);
+        };
+        for mut trace in std::mem::take(&mut self.events) {
+            tag(&mut trace);
+            data.push_ordered(trace);
+        }
+        for mut trace in std::mem::take(&mut self.hidden_events) {
+            tag(&mut trace);
+            data.push_hidden(trace);
+        }
+        if was_empty {
+            data.current_index = self.current_index.min(data.events.len().saturating_sub(1));
+            data.filter = self.filter.clone();
+        }
+        data.revision += 1;
+        sources
+    }
+
+    /// Information of the sources of the session, named like the sources of the restored traces
+    pub fn restored_source_infos(&self) -> Vec<SourceInfo> {
+        self.sources
+            .iter()
+            .map(|info| SourceInfo {
+                name: session_source(&info.name),
+                ..info.clone()
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,42 @@
+//! Helpers shared by the tests
+#![allow(dead_code)]
+
+use std::path::Path;
+
+use tramex_tools::{
+    data::{AdditionalInfos, Trace},
+    interface::{layer::Layer, parser::parser_rrc::RRCInfos, types::Direction},
+};
+
+/// Path of a file of the tests, run from the workspace or from the crate
+pub fn get_path(p: &str) -> String {
+    if std::env::current_dir().unwrap().ends_with("tramex-tools") {
+        return Path::new("tests").join(p).to_string_lossy().to_string();
+    }
+    let filename = file!();
+    Path::new(filename)
+        .parent()
+        .and_then(Path::parent)
+        .unwrap()
+        .join(p)
+        .to_string_lossy()
+        .to_string()
+}
+
+/// RRC trace without payload, text or source
+pub fn rrc_trace(timestamp: u64, direction: Direction, canal: &str, canal_msg: &str) -> Trace {
+    Trace {
+        timestamp,
+        layer: Layer::RRC,
+        additional_infos: AdditionalInfos::RRCInfos(RRCInfos {
+            direction,
+            canal: canal.to_owned(),
+            canal_msg: canal_msg.to_owned(),
+            ue_id: None,
+        }),
+        hexa: vec![],
+        text: None,
+        source: None,
+        bookmark: None,
+    }
+}
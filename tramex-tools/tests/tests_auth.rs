@@ -0,0 +1,43 @@
+// tests
+#[cfg(test)]
+mod tests {
+    use tramex_tools::interface::websocket::auth::{Authenticate, AuthenticateMessage, compute_response};
+
+    #[test]
+    fn test_compute_response() {
+        let res = compute_response("ENB", "ENB", "secret", "8a3f1c2e");
+        assert_eq!(res, "f2ae7ec97353daca8f9a8642a081d5b553a27f2e0a54f4b80de2212fd837a787");
+        assert_ne!(compute_response("ENB", "ENB", "wrong", "8a3f1c2e"), res);
+    }
+
+    #[test]
+    fn test_answer_challenge() {
+        let json = r#"{
+            "message": "authenticate",
+            "type": "ENB",
+            "name": "ENB",
+            "challenge": "8a3f1c2e"
+        }"#;
+        let challenge: AuthenticateMessage = serde_json::from_str(json).unwrap();
+        assert_eq!(challenge.server_type, "ENB");
+        assert!(challenge.ready.is_none());
+        let answer = serde_json::to_value(Authenticate::new(3, &challenge, "secret")).unwrap();
+        assert_eq!(answer["message"], "authenticate");
+        assert_eq!(answer["message_id"], 3);
+        assert_eq!(
+            answer["res"],
+            "f2ae7ec97353daca8f9a8642a081d5b553a27f2e0a54f4b80de2212fd837a787"
+        );
+    }
+
+    #[test]
+    fn test_authentication_result() {
+        let json = r#"{"message": "authenticate", "ready": true}"#;
+        let result: AuthenticateMessage = serde_json::from_str(json).unwrap();
+        assert_eq!(result.ready, Some(true));
+        assert!(result.challenge.is_none());
+        let json = r#"{"message": "authenticate", "error": "Authentication not valid"}"#;
+        let result: AuthenticateMessage = serde_json::from_str(json).unwrap();
+        assert_eq!(result.error.as_deref(), Some("Authentication not valid"));
+    }
+}
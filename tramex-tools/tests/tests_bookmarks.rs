@@ -0,0 +1,141 @@
+// tests
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::rrc_trace;
+    use tramex_tools::{
+        data::{Bookmark, Data, Trace},
+        interface::{
+            layer::{Layer, LayerLogLevel, Layers},
+            types::Direction,
+        },
+        session::Session,
+    };
+
+    fn trace_of(timestamp: u64, layer: Layer) -> Trace {
+        Trace {
+            layer,
+            source: Some("enb.log".to_owned()),
+            ..rrc_trace(timestamp, Direction::UL, "DCCH", &format!("message {timestamp}"))
+        }
+    }
+
+    fn data_of(timestamps: &[u64]) -> Data {
+        let mut data = Data::default();
+        for timestamp in timestamps {
+            data.push_ordered(trace_of(*timestamp, Layer::RRC));
+        }
+        data
+    }
+
+    fn bookmarked(data: &Data) -> Vec<u64> {
+        data.bookmarks().map(|(_, trace, _)| trace.timestamp).collect()
+    }
+
+    #[test]
+    fn test_toggle_bookmark() {
+        let mut data = data_of(&[10, 20, 30]);
+        data.toggle_bookmark(1);
+        assert_eq!(data.events[1].bookmark, Some(Bookmark::default()));
+        // the events are not recomputed for a bookmark
+        assert_eq!(data.revision, 0);
+        assert_eq!(data.bookmarks_revision, 1);
+        data.toggle_bookmark(1);
+        assert!(data.events[1].bookmark.is_none());
+        // out of range
+        data.toggle_bookmark(5);
+        assert!(bookmarked(&data).is_empty());
+    }
+
+    #[test]
+    fn test_bookmark_navigation() {
+        let mut data = data_of(&[10, 20, 30, 40, 50]);
+        assert_eq!(data.next_bookmark(), None);
+        assert_eq!(data.previous_bookmark(), None);
+        data.toggle_bookmark(0);
+        data.toggle_bookmark(2);
+        data.toggle_bookmark(4);
+
+        data.current_index = 0;
+        assert_eq!(data.next_bookmark(), Some(2));
+        assert_eq!(data.previous_bookmark(), None);
+        data.current_index = 2;
+        assert_eq!(data.next_bookmark(), Some(4));
+        assert_eq!(data.previous_bookmark(), Some(0));
+        data.current_index = 3;
+        assert_eq!(data.next_bookmark(), Some(4));
+        assert_eq!(data.previous_bookmark(), Some(2));
+        data.current_index = 4;
+        assert_eq!(data.next_bookmark(), None);
+    }
+
+    #[test]
+    fn test_bookmark_tags() {
+        let mut bookmark = Bookmark::default();
+        bookmark.set_tags(" attach, , failure,attach ");
+        assert_eq!(bookmark.tags, vec!["attach", "failure"]);
+        assert_eq!(bookmark.tags_text(), "attach, failure");
+        bookmark.set_tags("");
+        assert!(bookmark.tags.is_empty());
+    }
+
+    #[test]
+    fn test_bookmarks_follow_traces() {
+        let mut data = data_of(&[10, 30]);
+        data.toggle_bookmark(1);
+        // a trace inserted before the bookmarked trace
+        data.push_ordered(trace_of(20, Layer::RRC));
+        assert_eq!(bookmarked(&data), vec![30]);
+
+        data.push_ordered(trace_of(25, Layer::NAS));
+        data.toggle_bookmark(2);
+        assert_eq!(bookmarked(&data), vec![25, 30]);
+        let mut layers = Layers::all_debug();
+        for (name, config) in layers.iter_mut() {
+            if name == "NAS" {
+                config.level = LayerLogLevel::None;
+            }
+        }
+        data.filter_layers(&layers);
+        assert_eq!(bookmarked(&data), vec![30]);
+        data.filter_layers(&Layers::all_debug());
+        assert_eq!(bookmarked(&data), vec![25, 30]);
+    }
+
+    #[test]
+    fn test_export_bookmarks() {
+        let mut data = data_of(&[10, 20]);
+        data.toggle_bookmark(1);
+        if let Some(bookmark) = &mut data.events[1].bookmark {
+            bookmark.note = "wrong \"cause\", see\nspec".to_owned();
+            bookmark.set_tags("attach, failure");
+        }
+        let csv = data.export_bookmarks();
+        let mut lines = csv.lines();
+        assert_eq!(lines.next(), Some("index,timestamp,source,layer,message,tags,note"));
+        assert_eq!(
+            lines.next(),
+            Some("2,20,enb.log,RRC,message 20,attach;failure,\"wrong \"\"cause\"\", see")
+        );
+        assert_eq!(lines.next(), Some("spec\""));
+        assert_eq!(lines.next(), None);
+    }
+
+    #[test]
+    fn test_session_bookmarks() {
+        let mut data = data_of(&[10, 20]);
+        data.toggle_bookmark(0);
+        if let Some(bookmark) = &mut data.events[0].bookmark {
+            bookmark.note = "first message".to_owned();
+        }
+        let json = Session::new(&data, vec![], serde_json::Value::Null, 0).to_json().unwrap();
+        let mut restored = Data::default();
+        Session::parse(&json).unwrap().restore(&mut restored);
+        assert_eq!(
+            restored.events[0].bookmark.as_ref().map(|b| b.note.as_str()),
+            Some("first message")
+        );
+        assert!(restored.events[1].bookmark.is_none());
+    }
+}
@@ -0,0 +1,157 @@
+// tests
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::{get_path, rrc_trace};
+    use tramex_tools::{
+        analysis::compare::{DecodedField, DiffKind, TraceDiff, byte_diff, decoded_fields, diff_decoded},
+        data::{AdditionalInfos, Trace},
+        interface::{interface_file::file_handler::File, types::Direction},
+    };
+
+    fn trace_of(text: &str, hexa: &[u8]) -> Trace {
+        Trace {
+            hexa: hexa.to_vec(),
+            text: Some(text.lines().map(|line| line.to_owned()).collect()),
+            ..rrc_trace(1000, Direction::DL, "BCCH", "SIB")
+        }
+    }
+
+    const SIB2: &str = "        0000:  00 80 4c 61                                       ..La
+        {
+          message c1: systemInformation: {
+            sib2: {
+              preambleInfo {
+                numberOfRA-Preambles n52
+              },
+              powerRampingParameters {
+                powerRampingStep dB2,
+                preambleInitialReceivedTargetPower dBm-104
+              },
+              plmn-IdentityList {
+                {
+                  mcc {
+                    2,
+                    0,
+                    8
+                  }
+                }
+              },
+              maxHARQ-Msg3Tx 5
+            }
+          }
+        }";
+
+    fn field(path: &str, value: &str) -> DecodedField {
+        DecodedField {
+            path: path.to_owned(),
+            value: value.to_owned(),
+        }
+    }
+
+    #[test]
+    fn test_decoded_fields() {
+        let text: Vec<&str> = SIB2.lines().collect();
+        let fields = decoded_fields(&text);
+        let prefix = "message c1: systemInformation.sib2";
+        assert_eq!(
+            fields,
+            vec![
+                field(&format!("{prefix}.preambleInfo.numberOfRA-Preambles"), "n52"),
+                field(&format!("{prefix}.powerRampingParameters.powerRampingStep"), "dB2"),
+                field(
+                    &format!("{prefix}.powerRampingParameters.preambleInitialReceivedTargetPower"),
+                    "dBm-104"
+                ),
+                field(&format!("{prefix}.plmn-IdentityList.0.mcc.0"), "2"),
+                field(&format!("{prefix}.plmn-IdentityList.0.mcc.1"), "0"),
+                field(&format!("{prefix}.plmn-IdentityList.0.mcc.2"), "8"),
+                field(&format!("{prefix}.maxHARQ-Msg3Tx"), "5"),
+            ]
+        );
+        assert!(decoded_fields::<&str>(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_diff_decoded() {
+        let left = vec![field("a", "1"), field("b", "2"), field("c", "3"), field("e", "5")];
+        let right = vec![field("a", "1"), field("c", "4"), field("d", "4"), field("e", "5")];
+        let diffs = diff_decoded(&left, &right);
+        let kinds: Vec<(&str, DiffKind)> = diffs.iter().map(|diff| (diff.path.as_str(), diff.kind)).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                ("a", DiffKind::Same),
+                ("b", DiffKind::Removed),
+                ("c", DiffKind::Changed),
+                ("d", DiffKind::Added),
+                ("e", DiffKind::Same),
+            ]
+        );
+        assert_eq!(diffs[2].report_line(), "~ c 3 -> 4");
+        assert_eq!(diffs[1].report_line(), "- b 2");
+        assert_eq!(diffs[3].report_line(), "+ d 4");
+        // one side empty
+        assert!(diff_decoded(&[], &right).iter().all(|diff| diff.kind == DiffKind::Added));
+        assert!(diff_decoded(&left, &[]).iter().all(|diff| diff.kind == DiffKind::Removed));
+    }
+
+    #[test]
+    fn test_byte_diff() {
+        assert!(byte_diff(&[1, 2, 3], &[1, 2, 3]).is_empty());
+        assert_eq!(byte_diff(&[1, 2, 3, 4], &[1, 9, 9, 4]), vec![1..3]);
+        assert_eq!(byte_diff(&[1, 2, 3], &[0, 2, 3, 4, 5]), vec![0..1, 3..5]);
+        assert_eq!(byte_diff(&[], &[1]), vec![0..1]);
+    }
+
+    #[test]
+    fn test_trace_diff() {
+        let left = trace_of(SIB2, &[0x00, 0x80, 0x4c, 0x61]);
+        let changed = SIB2.replace("dB2", "dB4").replace(
+            "                    8\n",
+            "                    8\n                  },\n                  mnc {\n                    1\n",
+        );
+        let right = trace_of(&changed, &[0x00, 0x81, 0x4c, 0x61, 0x10]);
+        let diff = TraceDiff::new(&left, &right);
+        assert!(!diff.is_identical());
+        assert_eq!(diff.count(DiffKind::Changed), 1);
+        assert_eq!(diff.count(DiffKind::Added), 1);
+        assert_eq!(diff.count(DiffKind::Removed), 0);
+        assert_eq!(diff.bytes, vec![1..2, 4..5]);
+        assert_eq!(diff.different_bytes(), 2);
+        assert_eq!(diff.sizes, (4, 5));
+        let report = diff.report(&left, &right);
+        let lines: Vec<&str> = report.lines().collect();
+        assert_eq!(lines[0], "--- RRC BCCH SIB at 1000");
+        assert_eq!(lines[2], "1 changed, 1 added, 0 removed fields");
+        assert_eq!(
+            lines[3],
+            "~ message c1: systemInformation.sib2.powerRampingParameters.powerRampingStep dB2 -> dB4"
+        );
+        assert_eq!(lines[4], "+ message c1: systemInformation.sib2.plmn-IdentityList.0.mnc.0 1");
+        assert_eq!(lines[5], "2 different bytes (4 and 5 bytes): 0001..0002, 0004..0005");
+
+        assert!(TraceDiff::new(&left, &left.clone()).is_identical());
+    }
+
+    #[test]
+    fn test_compare_file_messages() {
+        let filename = &get_path("enb.log");
+        let content = std::fs::read_to_string(filename).unwrap();
+        let lines: Vec<String> = content.lines().map(|line| line.to_owned()).collect();
+        let mut ix = 0;
+        let (traces, _) = File::process_string(&lines, 50, &mut ix);
+        let sib1: Vec<&Trace> = traces
+            .iter()
+            .filter(|trace| {
+                let AdditionalInfos::RRCInfos(infos) = &trace.additional_infos;
+                infos.canal_msg == "SIB1"
+            })
+            .collect();
+        assert!(sib1.len() >= 2);
+        let diff = TraceDiff::new(sib1[0], sib1[1]);
+        assert!(diff.count(DiffKind::Same) > 10);
+        assert_eq!(diff.bytes.is_empty(), sib1[0].hexa == sib1[1].hexa);
+    }
+}
@@ -63,6 +63,7 @@ mod tests {
         assert!(infos.direction == Direction::DL);
         assert!(infos.canal == "BCCH");
         assert!(infos.canal_msg == "SIB");
+        assert!(infos.ue_id.is_none());
         assert!(one_trace.layer == Layer::RRC);
         eprintln!("{:?}", one_trace.timestamp);
         assert!(one_trace.timestamp == 39668348);
@@ -56,7 +56,7 @@ mod tests {
         let data = vec![
             "0000:  00 80 4c 61 bc 8c 8c c1  16 08 a8 02 40 04 08 01  ..La........@...",
             "0010:  73 39 4f 52 d5 42 48 00  18 01 2e 38 03 84 28 c5  s9OR.BH....8..(.",
-            "0020:  b0 9d 4b 48                                       ssdf............",
+            "0020:  b0 9d 4b 48                                       ..KH",
         ];
         let res_hexe = extract_hexe(&data);
         assert!(res_hexe.is_ok());
@@ -0,0 +1,60 @@
+// tests
+#[cfg(test)]
+mod tests {
+    use tramex_tools::hexdump::{self, CopyFormat};
+
+    #[test]
+    fn test_copy_formats() {
+        let bytes = [0x0a, 0x1b, 0xff];
+        assert_eq!(CopyFormat::Hex.format(&bytes), "0a 1b ff");
+        assert_eq!(
+            CopyFormat::CArray.format(&bytes),
+            "const uint8_t data[3] = { 0x0a, 0x1b, 0xff };"
+        );
+        assert_eq!(CopyFormat::Base64.format(&bytes), "Chv/");
+        assert_eq!(CopyFormat::Base64.format(b"tramex"), "dHJhbWV4");
+        assert_eq!(CopyFormat::Base64.format(b"rrc"), "cnJj");
+        assert_eq!(CopyFormat::Base64.format(b"ue"), "dWU=");
+        assert_eq!(CopyFormat::Hex.format(&[]), "");
+    }
+
+    #[test]
+    fn test_dump() {
+        let bytes: Vec<u8> = (0x41..0x41 + 20).collect();
+        let rows: Vec<(usize, usize)> = hexdump::rows(&bytes, 8).map(|(offset, row)| (offset, row.len())).collect();
+        assert_eq!(rows, vec![(0, 8), (8, 8), (16, 4)]);
+        let dump = hexdump::dump(&bytes, 16);
+        let mut lines = dump.lines();
+        assert_eq!(
+            lines.next(),
+            Some("0000:  41 42 43 44 45 46 47 48 49 4a 4b 4c 4d 4e 4f 50  ABCDEFGHIJKLMNOP")
+        );
+        assert_eq!(
+            lines.next(),
+            Some("0010:  51 52 53 54                                      QRST")
+        );
+        assert_eq!(lines.next(), None);
+        assert_eq!(hexdump::ascii_char(0x00), '.');
+        assert_eq!(hexdump::ascii_char(0x7f), '.');
+        assert_eq!(hexdump::ascii_char(b' '), ' ');
+    }
+
+    #[test]
+    fn test_bits() {
+        let bytes = [0b1010_0000, 0b0000_0011];
+        assert_eq!(hexdump::bit(&bytes, 0), Some(true));
+        assert_eq!(hexdump::bit(&bytes, 1), Some(false));
+        assert_eq!(hexdump::bit(&bytes, 15), Some(true));
+        assert_eq!(hexdump::bit(&bytes, 16), None);
+        assert_eq!(hexdump::bits_string(&bytes, 0..4).as_deref(), Some("1010"));
+        // a field across two bytes
+        assert_eq!(hexdump::bits_string(&bytes, 6..16).as_deref(), Some("0000000011"));
+        assert_eq!(hexdump::read_bits(&bytes, 6..16), Some(3));
+        assert_eq!(hexdump::read_bits(&bytes, 0..3), Some(0b101));
+        assert_eq!(hexdump::read_bits(&bytes, 0..0), Some(0));
+        assert_eq!(hexdump::read_bits(&bytes, 8..17), None);
+        assert_eq!(hexdump::bits_string(&bytes, 8..17), None);
+        assert_eq!(hexdump::read_bits(&[0xff; 9], 0..65), None);
+        assert_eq!(hexdump::read_bits(&[0xff; 9], 0..64), Some(u64::MAX));
+    }
+}
@@ -0,0 +1,129 @@
+// tests
+#[cfg(test)]
+mod tests {
+    use tramex_tools::errors::ErrorCode;
+    use tramex_tools::hexdump;
+    use tramex_tools::interface::functions::{extract_hexe, parse_hexe};
+    use tramex_tools::interface::interface_file::file_handler::File;
+
+    const SIB: [&str; 3] = [
+        "        0000:  00 80 4c 61 bc 8c 8c c1  16 08 a8 02 40 04 08 01  ..La........@...",
+        "        0010:  73 39 4f 52 d5 42 48 00  18 01 2e 38 03 84 28 c5  s9OR.BH....8..(.",
+        "        0020:  b0 9d 4b 48                                       ..KH",
+    ];
+
+    fn error_of(lines: &[&str]) -> (u64, Option<u64>, String) {
+        let err = parse_hexe(lines).unwrap_err();
+        (err.line_idx, err.column, err.message)
+    }
+
+    #[test]
+    fn test_widths() {
+        let bytes = parse_hexe(&SIB).unwrap();
+        assert_eq!(bytes.len(), 36);
+        // 8 bytes per row, uppercase, one space after the offset
+        let lines = [
+            "0000: 00 80 4C 61 BC 8C 8C C1  ..La....",
+            "0008: 16 08 A8 02 40 04 08 01  ....@...",
+            "0010: 73 39 4F 52 D5 42 48 00  s9OR.BH.",
+            "0018: 18 01 2E 38 03 84 28 C5  ...8..(.",
+            "0020: B0 9D 4B 48              ..KH",
+        ];
+        assert_eq!(parse_hexe(&lines).unwrap(), bytes);
+        // 32 bytes per row, longer offsets
+        let lines = [
+            "000000:  00 80 4c 61 bc 8c 8c c1 16 08 a8 02 40 04 08 01 73 39 4f 52 d5 42 48 00 18 01 2e 38 03 84 28 c5  ..La........@...s9OR.BH....8..(.",
+            "000020:  b0 9d 4b 48  ..KH",
+        ];
+        assert_eq!(parse_hexe(&lines).unwrap(), bytes);
+    }
+
+    #[test]
+    fn test_ascii_like_bytes() {
+        // the ASCII column looks like bytes
+        let lines = ["0000:  61 62        ab"];
+        assert_eq!(parse_hexe(&lines).unwrap(), vec![0x61, 0x62]);
+        let lines = ["0000:  61 20 62  a b"];
+        assert_eq!(parse_hexe(&lines).unwrap(), vec![0x61, 0x20, 0x62]);
+        let bytes: Vec<u8> = (0..=255).collect();
+        let dump = hexdump::dump(&bytes, hexdump::DEFAULT_WIDTH);
+        assert_eq!(parse_hexe(&dump.lines().collect::<Vec<&str>>()).unwrap(), bytes);
+    }
+
+    #[test]
+    fn test_skipped_lines() {
+        let mut lines = vec!["11:01:08.348 [RRC] DL    - 01 BCCH: SIB"];
+        lines.extend(SIB);
+        lines.push("          9,");
+        assert_eq!(parse_hexe(&lines).unwrap().len(), 36);
+        assert!(parse_hexe(&Vec::<String>::new()).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_offset_continuity() {
+        // missing row
+        let (line, column, message) = error_of(&[SIB[0], SIB[2]]);
+        assert_eq!((line, column), (1, Some(9)));
+        assert!(message.contains("expected 0010"), "{message}");
+        // dump not starting at 0
+        let (line, column, _) = error_of(&[SIB[1], SIB[2]]);
+        assert_eq!((line, column), (0, Some(9)));
+        // missing byte: the ASCII column doesn't match the remaining bytes
+        let lines = [
+            "0000:  00 80 4c 61 bc 8c 8c c1  16 08 a8 02 40 04 08     ..La........@...",
+            "0010:  73 39 4f 52 d5 42 48 00  18 01 2e 38 03 84 28 c5  s9OR.BH....8..(.",
+        ];
+        let (line, column, message) = error_of(&lines);
+        assert_eq!((line, column), (0, Some(58)));
+        assert!(message.contains("16 characters for 15 bytes"), "{message}");
+    }
+
+    #[test]
+    fn test_ascii_mismatch() {
+        let lines = [
+            SIB[0],
+            "        0010:  73 39 4f 52 d5 42 48 00  18 01 2e 38 03 84 28 c5  s9OX.BH....8..(.",
+        ];
+        let (line, column, message) = error_of(&lines);
+        assert_eq!((line, column), (1, Some(69)));
+        assert!(message.contains("'X' doesn't match the byte 52"), "{message}");
+        let (line, column, message) = error_of(&["0000:  b0 9d 4b 48                                       ssdf"]);
+        assert_eq!((line, column), (0, Some(58)));
+        assert!(message.contains("'s' doesn't match the byte b0"), "{message}");
+    }
+
+    #[test]
+    fn test_invalid_byte() {
+        let lines = ["0000:  00 80 4c 6z bc 8c 8c c1  ..La...."];
+        let (line, column, message) = error_of(&lines);
+        assert_eq!((line, column), (0, Some(17)));
+        assert!(message.contains("\"6z\""), "{message}");
+        let (_, _, message) = error_of(&["0000:  00 80 4c 61"]);
+        assert!(message.contains("Missing ASCII column"), "{message}");
+
+        let err = extract_hexe(&[SIB[0], "        0010:  73 39 4f 52 d5 42 48 00  18 01 2e 38 03 84 28 c5"]).unwrap_err();
+        assert!(matches!(err.get_code(), ErrorCode::HexeDecodingError));
+        assert!(err.message.contains("line 2, column 64"), "{}", err.message);
+    }
+
+    #[test]
+    fn test_file_error_position() {
+        let content = [
+            "11:01:08.348 [RRC] DL    - 01 BCCH: SIB",
+            SIB[0],
+            "        0010:  73 39 4f 52 d5 42 48 00  18 01 2e 38 03 84 28 c5  s9OX.BH....8..(.",
+            SIB[2],
+            "        {",
+            "          message c1: systemInformation: {",
+            "          }",
+            "        }",
+            "",
+        ]
+        .map(|line| line.to_owned());
+        let mut ix = 0;
+        let (traces, errors) = File::process_string(&content, 1, &mut ix);
+        assert!(traces.is_empty());
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].message.contains("line 3, column 69"), "{}", errors[0].message);
+    }
+}
@@ -0,0 +1,160 @@
+// tests
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::{get_path, rrc_trace};
+
+    use tramex_tools::{
+        data::{Data, Trace},
+        interface::{
+            interface_file::file_handler::File,
+            interface_types::InterfaceTrait,
+            layer::{Layer, LayerLogLevel, Layers},
+            types::Direction,
+        },
+    };
+
+    /// enb.log with NAS logs between its RRC logs
+    fn file_with_nas() -> File {
+        let filename = &get_path("enb.log");
+        let content = std::fs::read_to_string(filename).unwrap();
+        let mut with_nas = String::new();
+        for line in content.lines() {
+            if line.contains("[RRC]") {
+                with_nas.push_str("11:01:08.000 [NAS] UL 0001 EMM: Attach request\n");
+            }
+            with_nas.push_str(line);
+            with_nas.push('\n');
+        }
+        File::new_file_content(filename.into(), with_nas)
+    }
+
+    fn layers_with(layer: &str, level: LayerLogLevel) -> Layers {
+        let mut layers = Layers::all_debug();
+        for (name, config) in layers.iter_mut() {
+            if name == layer {
+                config.level = level;
+            }
+        }
+        layers
+    }
+
+    fn trace_of(timestamp: u64, layer: Layer) -> Trace {
+        Trace {
+            layer,
+            ..rrc_trace(timestamp, Direction::UL, "DCCH", &format!("{timestamp}"))
+        }
+    }
+
+    fn timestamps(traces: &[Trace]) -> Vec<u64> {
+        traces.iter().map(|trace| trace.timestamp).collect()
+    }
+
+    #[test]
+    fn test_layers_shows() {
+        let layers = layers_with("RRC", LayerLogLevel::None);
+        assert!(!layers.shows(&Layer::RRC));
+        assert!(layers.shows(&Layer::NAS));
+        assert!(layers.shows(&Layer::PROD));
+        assert_eq!(layers.get(&Layer::RRC).map(|config| config.level), Some(LayerLogLevel::None));
+        assert!(layers.get(&Layer::PROD).is_none());
+    }
+
+    #[test]
+    fn test_file_skips_hidden_layers() {
+        let mut file = file_with_nas();
+        file.change_nb_read(5);
+        let mut data = Data::default();
+        let layers = layers_with("NAS", LayerLogLevel::None);
+        // the NAS logs are not counted in the batch and their errors are ignored
+        assert!(file.get_more_data(layers.clone(), &mut data).is_ok());
+        assert_eq!(data.events.len(), 5);
+        assert!(data.events.iter().all(|trace| trace.layer == Layer::RRC));
+        while !file.full_read {
+            if let Err(errors) = file.get_more_data(layers.clone(), &mut data) {
+                assert!(errors.is_empty());
+            }
+        }
+        assert_eq!(data.events.len(), 15);
+        assert!(data.hidden_events.is_empty());
+
+        // the NAS logs are counted when their layer is shown
+        let mut file = file_with_nas();
+        file.change_nb_read(5);
+        let mut data = Data::default();
+        let errors = file.get_more_data(Layers::all_debug(), &mut data).unwrap_err();
+        assert_eq!(errors.len(), 3);
+        assert_eq!(data.events.len(), 2);
+    }
+
+    #[test]
+    fn test_file_keeps_hidden_traces() {
+        let mut file = file_with_nas();
+        file.change_nb_read(2);
+        let mut data = Data::default();
+        let mut layers = layers_with("RRC", LayerLogLevel::None);
+        for (name, config) in layers.iter_mut() {
+            if name == "NAS" {
+                config.level = LayerLogLevel::None;
+            }
+        }
+        // every log is hidden, a read skips at most 4 batches of them
+        assert!(file.get_more_data(layers.clone(), &mut data).is_ok());
+        assert!(!file.full_read);
+        assert!(!data.hidden_events.is_empty() && data.hidden_events.len() < 15);
+        while !file.full_read {
+            let _ = file.get_more_data(layers.clone(), &mut data);
+        }
+        assert!(data.events.is_empty());
+        assert_eq!(data.hidden_events.len(), 15);
+        assert!(
+            data.hidden_events
+                .iter()
+                .all(|trace| trace.source.as_deref() == Some("enb.log"))
+        );
+
+        data.filter_layers(&Layers::all_debug());
+        assert_eq!(data.events.len(), 15);
+        assert!(data.hidden_events.is_empty());
+    }
+
+    #[test]
+    fn test_filter_layers() {
+        let mut data = Data::default();
+        for (timestamp, layer) in [(10, Layer::RRC), (20, Layer::NAS), (30, Layer::RRC), (40, Layer::NAS)] {
+            data.push_ordered(trace_of(timestamp, layer));
+        }
+        data.current_index = 2;
+        let revision = data.revision;
+
+        // the current trace is kept
+        data.filter_layers(&layers_with("NAS", LayerLogLevel::None));
+        assert_eq!(timestamps(&data.events), vec![10, 30]);
+        assert_eq!(timestamps(&data.hidden_events), vec![20, 40]);
+        assert_eq!(data.get_current_trace().map(|trace| trace.timestamp), Some(30));
+        assert!(data.revision > revision);
+
+        // nothing changes, the revision is kept
+        let revision = data.revision;
+        data.filter_layers(&layers_with("NAS", LayerLogLevel::None));
+        assert_eq!(data.revision, revision);
+
+        // the hidden traces are merged back in order
+        data.filter_layers(&Layers::all_debug());
+        assert_eq!(timestamps(&data.events), vec![10, 20, 30, 40]);
+        assert!(data.hidden_events.is_empty());
+        assert_eq!(data.get_current_trace().map(|trace| trace.timestamp), Some(30));
+
+        // the current trace is hidden, the previous trace shown becomes the current one
+        data.filter_layers(&layers_with("RRC", LayerLogLevel::None));
+        assert_eq!(timestamps(&data.events), vec![20, 40]);
+        assert_eq!(data.get_current_trace().map(|trace| trace.timestamp), Some(20));
+
+        data.push_hidden(trace_of(35, Layer::RRC));
+        assert_eq!(timestamps(&data.hidden_events), vec![10, 30, 35]);
+        data.filter_layers(&Layers::all_debug());
+        assert_eq!(timestamps(&data.events), vec![10, 20, 30, 35, 40]);
+        assert_eq!(data.get_current_trace().map(|trace| trace.timestamp), Some(20));
+    }
+}
@@ -0,0 +1,144 @@
+// tests
+#[cfg(test)]
+mod tests {
+    use serde_json::{Value, json};
+    use tramex_tools::interface::{
+        layer::{LayerConfig, LayerLogLevel, Layers},
+        log_get::{LogGet, LogGetOptions, parse_range_bound},
+    };
+
+    fn to_json(log_get: &LogGet) -> Value {
+        serde_json::to_value(log_get).unwrap()
+    }
+
+    #[test]
+    fn test_log_get_default() {
+        let log_get = to_json(&LogGet::new(7, Layers::new(), 1024));
+        assert_eq!(log_get["message"], "log_get");
+        assert_eq!(log_get["message_id"], 7);
+        assert_eq!(log_get["timeout"], 1);
+        assert_eq!(log_get["min"], 64);
+        assert_eq!(log_get["max"], 1024);
+        assert_eq!(log_get["headers"], false);
+        assert_eq!(log_get["layers"]["RRC"], "warn");
+        // the options not set are not sent
+        for key in [
+            "short",
+            "allow_empty",
+            "start_timestamp",
+            "end_timestamp",
+            "ue_id",
+            "rnti",
+            "cell_id",
+        ] {
+            assert!(log_get.get(key).is_none(), "{key} sent");
+        }
+    }
+
+    #[test]
+    fn test_log_get_builder() {
+        let log_get = LogGet::new(1, Layers::new(), 8192)
+            .timeout(5)
+            .min(1)
+            .headers(true)
+            .short(true)
+            .allow_empty(true)
+            .with_start_timestamp(Some(1000))
+            .with_end_timestamp(Some(2000))
+            .ue_id(Some(3))
+            .rnti(Some(0x4601))
+            .cell_id(Some(2));
+        let log_get = to_json(&log_get);
+        let expected = json!({
+            "timeout": 5, "min": 1, "max": 8192, "headers": true, "short": true, "allow_empty": true,
+            "start_timestamp": 1000, "end_timestamp": 2000, "ue_id": 3, "rnti": 0x4601, "cell_id": 2
+        });
+        for (key, value) in expected.as_object().unwrap() {
+            assert_eq!(&log_get[key], value, "{key}");
+        }
+        // min and max stay consistent
+        assert_eq!(to_json(&LogGet::new(1, Layers::new(), 100).min(500))["max"], 500);
+        assert_eq!(to_json(&LogGet::new(1, Layers::new(), 100).max(10))["min"], 10);
+    }
+
+    #[test]
+    fn test_log_get_options() {
+        let options = LogGetOptions {
+            timeout: 3,
+            short: true,
+            start_timestamp: Some(1000),
+            end_timestamp: Some(2000),
+            ue_id: Some(1),
+            ..Default::default()
+        };
+        let log_get = to_json(&LogGet::new(1, Layers::new(), 1024).with_options(&options));
+        assert_eq!(log_get["timeout"], 3);
+        assert_eq!(log_get["short"], true);
+        assert_eq!(log_get["end_timestamp"], 2000);
+        assert_eq!(log_get["ue_id"], 1);
+        // the start is only sent with the first request of the range
+        assert!(log_get.get("start_timestamp").is_none());
+        let stored: LogGetOptions = serde_json::from_str(r#"{"timeout": 2}"#).unwrap();
+        assert_eq!(stored.min, LogGetOptions::default().min);
+    }
+
+    #[test]
+    fn test_parse_range_bound() {
+        // 2024-03-01 12:00:00 UTC
+        let now_ms = 1_709_294_400_000;
+        assert_eq!(
+            parse_range_bound("10:38:10.017", now_ms),
+            Some(1_709_251_200_000 + 38_290_017)
+        );
+        assert_eq!(parse_range_bound(" 10:38:10 ", now_ms), Some(1_709_251_200_000 + 38_290_000));
+        assert_eq!(
+            parse_range_bound("2024-02-28 10:38:10.5", now_ms),
+            Some(1_709_078_400_000 + 38_290_500)
+        );
+        assert_eq!(parse_range_bound("2024-02-28T00:00:00", now_ms), Some(1_709_078_400_000));
+        assert_eq!(parse_range_bound("25:00:00", now_ms), None);
+        assert_eq!(parse_range_bound("2024-02-30 10:00:00", now_ms), None);
+        assert_eq!(parse_range_bound("", now_ms), None);
+    }
+
+    #[test]
+    fn test_layers_serialization() {
+        let mut layers = Layers::new_optiniated();
+        layers.phy = LayerConfig {
+            level: LayerLogLevel::Info,
+            max_size: 32,
+            payload: true,
+        };
+        layers.mac.level = LayerLogLevel::None;
+        let value = serde_json::to_value(&layers).unwrap();
+        assert_eq!(value["PHY"], json!({"level": "info", "max_size": 32, "payload": true}));
+        assert_eq!(value["RRC"]["level"], "debug");
+        assert_eq!(value["MAC"]["level"], "none");
+        let read: Layers = serde_json::from_value(value).unwrap();
+        assert_eq!(read, layers);
+        // the log_get only sends the levels
+        let log_get = to_json(&LogGet::new(1, layers, 1024));
+        assert_eq!(log_get["layers"]["PHY"], "info");
+        assert_eq!(log_get["layers"]["TRX"], "warn");
+    }
+
+    #[test]
+    fn test_layers_deserialization() {
+        // a level alone, a partial configuration, the missing layers get the default
+        let layers: Layers =
+            serde_json::from_str(r#"{"RRC": "debug", "PHY": {"level": "error"}, "NAS": {"max_size": 64}}"#).unwrap();
+        assert_eq!(layers.rrc, LayerConfig::with_level(LayerLogLevel::Debug));
+        assert_eq!(layers.phy.level, LayerLogLevel::Error);
+        assert_eq!(layers.nas.level, LayerLogLevel::Warn);
+        assert_eq!(layers.nas.max_size, 64);
+        assert_eq!(layers.mac, LayerConfig::default());
+        assert!(LayerLogLevel::Debug > LayerLogLevel::Info);
+        assert!(LayerLogLevel::None < LayerLogLevel::Error);
+        assert!(
+            Layers::all_debug()
+                .iter()
+                .iter()
+                .all(|(_, config)| config.level == LayerLogLevel::Debug)
+        );
+    }
+}
@@ -0,0 +1,219 @@
+// tests
+mod common;
+
+#[cfg(all(test, feature = "mock-server"))]
+mod tests {
+    use crate::common::get_path;
+
+    use serde_json::{Value, json};
+    use tramex_tools::{
+        data::{AdditionalInfos, Data},
+        interface::{
+            interface_file::file_handler::File,
+            interface_types::InterfaceTrait,
+            layer::Layers,
+            log_get::LogGet,
+            remote_api::Request,
+            types::{BaseMessage, WebSocketLog},
+        },
+        mock_server::{MockConfig, MockServer, Session},
+    };
+
+    fn mock(batch_size: usize) -> MockServer {
+        mock_file("enb.log", batch_size)
+    }
+
+    fn mock_file(file: &str, batch_size: usize) -> MockServer {
+        let content = std::fs::read_to_string(get_path(file)).unwrap();
+        let config = MockConfig {
+            batch_size,
+            day_start_ms: 0,
+            ..Default::default()
+        };
+        MockServer::from_log_file(config, content)
+    }
+
+    fn log_get(server: &MockServer, session: &mut Session, request: Value) -> WebSocketLog {
+        let response = server.handle_message(session, &request.to_string()).unwrap();
+        serde_json::from_str(&response).unwrap()
+    }
+
+    #[test]
+    fn test_mock_protocol() {
+        let server = mock(4);
+        assert_eq!(server.nb_logs(), 15);
+        let ready: BaseMessage = serde_json::from_str(&server.ready_message()).unwrap();
+        assert_eq!(ready.message, "ready");
+        assert_eq!(ready.name, "ENB");
+
+        let mut session = Session::default();
+        let mut received = vec![];
+        loop {
+            let response = log_get(
+                &server,
+                &mut session,
+                json!({"message": "log_get", "message_id": 1, "max": 10}),
+            );
+            assert_eq!(response.message_id, Some(1));
+            if response.logs.is_empty() {
+                break;
+            }
+            assert!(response.logs.len() <= 4);
+            received.extend(response.logs);
+        }
+        assert_eq!(received.len(), 15);
+
+        // the logs decode to the traces of the file
+        let content = std::fs::read_to_string(get_path("enb.log")).unwrap();
+        let mut file = File::new_file_content("enb.log".into(), content);
+        file.change_nb_read(50);
+        let mut data = Data::default();
+        let _ = file.get_more_data(Layers::all_debug(), &mut data);
+        for (one_log, expected) in received.iter().zip(&data.events) {
+            let trace = one_log.extract_data().unwrap();
+            assert_eq!(trace.timestamp, expected.timestamp);
+            assert_eq!(trace.hexa, expected.hexa);
+            let (AdditionalInfos::RRCInfos(infos), AdditionalInfos::RRCInfos(expected_infos)) =
+                (&trace.additional_infos, &expected.additional_infos);
+            assert_eq!(infos.canal_msg, expected_infos.canal_msg);
+        }
+
+        // start_timestamp rewinds to the first log at this time
+        let timestamps = server.timestamps();
+        let response = log_get(
+            &server,
+            &mut session,
+            json!({"message": "log_get", "message_id": 2, "start_timestamp": timestamps[5]}),
+        );
+        let first = timestamps.iter().position(|ts| *ts == timestamps[5]).unwrap();
+        assert_eq!(response.logs[0].idx, first as u64);
+
+        let config = server
+            .handle_message(&mut session, r#"{"message": "config_get", "message_id": 3}"#)
+            .unwrap();
+        let config: Value = serde_json::from_str(&config).unwrap();
+        assert_eq!(config["message_id"], 3);
+        assert!(config["logs"]["layers"].is_object());
+        let log_set = Request::log_set(&Layers::all_debug()).to_json(5).to_string();
+        let log_set = server.handle_message(&mut session, &log_set).unwrap();
+        assert!(!log_set.contains("error"));
+        let unknown = server
+            .handle_message(&mut session, r#"{"message": "stats", "message_id": 4}"#)
+            .unwrap();
+        assert!(unknown.contains("error"));
+        assert!(server.handle_message(&mut session, "not json").is_none());
+    }
+
+    #[test]
+    fn test_mock_range_and_filter() {
+        let server = mock(50);
+        let timestamps = server.timestamps();
+        let (start, end) = (timestamps[2], timestamps[9]);
+        let request = LogGet::new(1, Layers::all_debug(), 1024)
+            .with_start_timestamp(Some(start))
+            .with_end_timestamp(Some(end));
+        let mut session = Session::default();
+        let response = log_get(&server, &mut session, serde_json::to_value(&request).unwrap());
+        assert!(!response.logs.is_empty());
+        assert!(
+            response
+                .logs
+                .iter()
+                .all(|one_log| one_log.timestamp >= start && one_log.timestamp <= end)
+        );
+        let expected = timestamps.iter().filter(|ts| **ts >= start && **ts <= end).count();
+        assert_eq!(response.logs.len(), expected);
+
+        // the UE filter, on a file with several UEs
+        let server = mock_file("enb0_only_rrc.log", 1000);
+        let all = log_get(
+            &server,
+            &mut Session::default(),
+            json!({"message": "log_get", "message_id": 2}),
+        );
+        let ue_id = all.logs.iter().find_map(|one_log| one_log.ue_id);
+        assert!(ue_id.is_some());
+        let request = LogGet::new(2, Layers::all_debug(), 1024)
+            .with_start_timestamp(Some(0))
+            .ue_id(ue_id);
+        let response = log_get(&server, &mut Session::default(), serde_json::to_value(&request).unwrap());
+        assert!(!response.logs.is_empty());
+        assert!(response.logs.iter().all(|one_log| one_log.ue_id == ue_id));
+    }
+
+    #[test]
+    fn test_mock_websocket() {
+        let handle = mock(50).start("127.0.0.1:0").unwrap();
+        let (mut socket, _) = tungstenite::connect(handle.url()).unwrap();
+        let ready = socket.read().unwrap().into_text().unwrap();
+        assert!(ready.contains("\"ready\""));
+        let request = json!({"message": "log_get", "message_id": 1, "max": 100}).to_string();
+        socket.send(tungstenite::Message::Text(request)).unwrap();
+        let response: WebSocketLog = serde_json::from_str(&socket.read().unwrap().into_text().unwrap()).unwrap();
+        assert_eq!(response.logs.len(), 15);
+        handle.disconnect_all();
+        loop {
+            match socket.read() {
+                Ok(tungstenite::Message::Close(_)) | Err(_) => break,
+                Ok(_) => {}
+            }
+        }
+    }
+
+    /// Receive until the condition is met, fail after 5 seconds
+    #[cfg(feature = "websocket")]
+    fn receive_until(
+        connection: &mut tramex_tools::interface::websocket::ws_connection::WsConnection,
+        data: &mut Data,
+        mut condition: impl FnMut(&tramex_tools::interface::websocket::ws_connection::WsConnection, &Data, bool) -> bool,
+    ) {
+        use std::time::{Duration, Instant};
+
+        let start = Instant::now();
+        loop {
+            let closed = connection.try_recv(data).is_err();
+            if condition(connection, data, closed) {
+                return;
+            }
+            assert!(start.elapsed() < Duration::from_secs(5), "timeout");
+            std::thread::sleep(Duration::from_millis(5));
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "websocket")]
+    fn test_mock_ws_connection() {
+        use std::time::{Duration, Instant};
+        use tramex_tools::interface::websocket::ws_connection::WsConnection;
+
+        let server = mock(4);
+        let nb_logs = server.nb_logs();
+        let handle = server.start("127.0.0.1:0").unwrap();
+        let (sender, receiver) = WsConnection::connect(&handle.url(), || {}).unwrap();
+        let mut connection = WsConnection::new(sender, receiver);
+        let mut data = Data::default();
+        receive_until(&mut connection, &mut data, |connection, _, _| connection.name == "ENB");
+
+        connection.get_more_data(Layers::all_debug(), &mut data).unwrap();
+        receive_until(&mut connection, &mut data, |_, data, _| data.events.len() == 4);
+        assert_eq!(data.events[0].source.as_deref(), Some("ENB"));
+
+        // the server restarts: the logs resume after the last one received, without duplicates
+        handle.disconnect_all();
+        receive_until(&mut connection, &mut data, |_, _, closed| closed);
+        let (sender, receiver) = WsConnection::connect(&handle.url(), || {}).unwrap();
+        connection.reconnect(sender, receiver);
+        connection.streaming = true;
+        connection.stream_interval_ms = 0;
+        let start = Instant::now();
+        while data.events.len() < nb_logs {
+            let _ = connection.try_recv(&mut data);
+            if connection.should_stream() {
+                connection.get_more_data(Layers::all_debug(), &mut data).unwrap();
+            }
+            assert!(start.elapsed() < Duration::from_secs(5), "timeout");
+            std::thread::sleep(Duration::from_millis(5));
+        }
+        assert_eq!(data.events.len(), nb_logs);
+    }
+}
@@ -0,0 +1,143 @@
+// tests
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::{get_path, rrc_trace};
+
+    use tramex_tools::{
+        analysis::procedures::{ProcedureAnalyzer, ProcedureKind, ProcedureOutcome},
+        data::{AdditionalInfos, Data, Trace},
+        interface::{interface_file::file_handler::File, interface_types::InterfaceTrait, layer::Layers, types::Direction},
+    };
+
+    fn load_events(p: &str) -> Vec<Trace> {
+        let filename = &get_path(p);
+        let content = std::fs::read_to_string(filename).unwrap();
+        let mut file = File::new_file_content(filename.into(), content);
+        let mut data = Data::default();
+        while !file.full_read {
+            let _ = file.get_more_data(Layers::all_debug(), &mut data);
+        }
+        data.events
+    }
+
+    #[test]
+    fn test_procedures() {
+        let events = load_events("enb0_only_rrc.log");
+        let mut analyzer = ProcedureAnalyzer::new();
+        analyzer.analyze(&events);
+        let first = &analyzer.procedures[0];
+        assert!(first.kind == ProcedureKind::RrcConnectionEstablishment);
+        assert!(first.ue_id == 1);
+        assert!(first.start_index == 0);
+        assert!(first.end_index == Some(2));
+        assert!(first.outcome == ProcedureOutcome::Success);
+        assert!(first.duration() == Some(41));
+        let kpis = analyzer.kpis();
+        let reestablishment = kpis.iter().find(|kpi| kpi.kind == ProcedureKind::Reestablishment).unwrap();
+        assert!(reestablishment.total == 5);
+        assert!(reestablishment.success == 5);
+        assert!(reestablishment.success_rate == Some(1.0));
+        assert!(reestablishment.latency_p50 == Some(24));
+        assert!(reestablishment.latency_p99 == Some(43));
+        let reconfiguration = kpis.iter().find(|kpi| kpi.kind == ProcedureKind::Reconfiguration).unwrap();
+        assert!(reconfiguration.total == 6);
+        assert!(kpis.iter().all(|kpi| kpi.kind != ProcedureKind::Handover));
+    }
+
+    #[test]
+    fn test_incremental_analysis() {
+        let events = load_events("enb0_only_rrc.log");
+        let mut full = ProcedureAnalyzer::new();
+        full.analyze(&events);
+        let mut incremental = ProcedureAnalyzer::new();
+        for end in 1..=events.len() {
+            incremental.analyze(&events[..end]);
+        }
+        assert!(full.kpis() == incremental.kpis());
+    }
+
+    #[test]
+    fn test_interrupted_by_release() {
+        let events = load_events("enb0_only_rrc.log");
+        let security_command = events
+            .iter()
+            .position(|trace| {
+                let tramex_tools::data::AdditionalInfos::RRCInfos(infos) = &trace.additional_infos;
+                infos.canal_msg == "Security mode command"
+            })
+            .unwrap();
+        let mut truncated: Vec<Trace> = events[..=security_command].to_vec();
+        truncated.push(events.last().unwrap().clone()); // RRC connection release
+        let mut analyzer = ProcedureAnalyzer::new();
+        analyzer.analyze(&truncated);
+        let security = analyzer
+            .procedures
+            .iter()
+            .find(|p| p.kind == ProcedureKind::SecurityMode)
+            .unwrap();
+        assert!(security.outcome == ProcedureOutcome::Failure);
+        assert!(security.end_index == Some(truncated.len() - 1));
+    }
+
+    #[test]
+    fn test_attach() {
+        let events = load_events("enb0_only_rrc.log");
+        let mut analyzer = ProcedureAnalyzer::new();
+        analyzer.analyze(&events);
+        // attach request in the RRC connection setup complete, attach complete in an UL information transfer
+        let attach = analyzer.procedures.iter().find(|p| p.kind == ProcedureKind::Attach).unwrap();
+        assert!(attach.ue_id == 1);
+        assert!(attach.start_index == 2);
+        assert!(attach.end_index == Some(19));
+        assert!(attach.outcome == ProcedureOutcome::Success);
+        assert!(attach.duration() == Some(632));
+        let kpis = analyzer.kpis();
+        let attach = kpis.iter().find(|kpi| kpi.kind == ProcedureKind::Attach).unwrap();
+        assert!(attach.total == 1);
+        assert!(attach.success_rate == Some(1.0));
+    }
+
+    #[test]
+    fn test_incomplete_attach() {
+        let events = load_events("enb0_only_rrc.log");
+        let mut analyzer = ProcedureAnalyzer::new();
+        analyzer.analyze(&events[..19]);
+        let attach = analyzer.procedures.iter().find(|p| p.kind == ProcedureKind::Attach).unwrap();
+        assert!(attach.outcome == ProcedureOutcome::Incomplete);
+        assert!(attach.end_index.is_none());
+        assert!(attach.duration().is_none());
+        let kpis = analyzer.kpis();
+        let attach = kpis.iter().find(|kpi| kpi.kind == ProcedureKind::Attach).unwrap();
+        assert!(attach.incomplete == 1);
+        assert!(attach.success_rate.is_none());
+    }
+
+    /// NR message of the UE 1 carrying a NAS message
+    fn nr_trace(timestamp: u64, canal_msg: &str, nas: &str) -> Trace {
+        let mut trace = Trace {
+            text: Some(vec![format!("dedicatedNAS-Message '{nas}'H")]),
+            ..rrc_trace(timestamp, Direction::UL, "DCCH", canal_msg)
+        };
+        let AdditionalInfos::RRCInfos(infos) = &mut trace.additional_infos;
+        infos.ue_id = Some(1);
+        trace
+    }
+
+    #[test]
+    fn test_registration() {
+        let events = vec![
+            // plain registration request
+            nr_trace(1000, "RRC setup complete", "7E004179000D0109F1070000000000000010"),
+            // registration complete after a security header
+            nr_trace(1250, "UL information transfer", "7E02A1B2C3D4027E0043"),
+        ];
+        let mut analyzer = ProcedureAnalyzer::new();
+        analyzer.analyze(&events);
+        let registration = &analyzer.procedures[0];
+        assert!(registration.kind == ProcedureKind::Attach);
+        assert!(registration.outcome == ProcedureOutcome::Success);
+        assert!(registration.duration() == Some(250));
+    }
+}
@@ -0,0 +1,56 @@
+// tests
+#[cfg(test)]
+mod tests {
+    use tramex_tools::interface::websocket::reconnect::{Backoff, ResumePoint, accept_log};
+
+    #[test]
+    fn test_backoff_exponential() {
+        let mut backoff = Backoff::new(500, 4000);
+        assert!(backoff.time_left_ms(0).is_none());
+        let delays: Vec<u64> = (0..6)
+            .map(|_| {
+                let delay = backoff.schedule(0);
+                assert!(backoff.take_due(delay as i64));
+                delay
+            })
+            .collect();
+        assert_eq!(delays, vec![500, 1000, 2000, 4000, 4000, 4000]);
+        backoff.reset();
+        assert_eq!(backoff.attempt(), 0);
+        assert_eq!(backoff.delay_ms(), 500);
+    }
+
+    #[test]
+    fn test_backoff_schedule_once() {
+        let mut backoff = Backoff::new(1000, 30_000);
+        assert_eq!(backoff.schedule(100), 1000);
+        // already scheduled: the same attempt is kept
+        assert_eq!(backoff.schedule(600), 500);
+        assert_eq!(backoff.attempt(), 1);
+        assert!(!backoff.take_due(1099));
+        assert_eq!(backoff.time_left_ms(1099), Some(1));
+        assert!(backoff.take_due(1100));
+        assert!(backoff.time_left_ms(1100).is_none());
+    }
+
+    #[test]
+    fn test_resume_without_duplicates() {
+        let mut resume_point = None;
+        let received = [(10, 1), (10, 2), (12, 3)];
+        for (timestamp, idx) in received {
+            assert!(accept_log(&mut resume_point, timestamp, idx));
+        }
+        assert_eq!(resume_point, Some(ResumePoint { timestamp: 12, idx: 3 }));
+        // after a reconnection the server sends again the logs from the last timestamp
+        let resent = [(12, 3), (12, 4), (13, 5)];
+        let accepted: Vec<u64> = resent
+            .into_iter()
+            .filter(|(timestamp, idx)| accept_log(&mut resume_point, *timestamp, *idx))
+            .map(|(_, idx)| idx)
+            .collect();
+        assert_eq!(accepted, vec![4, 5]);
+        // a restarted server starts its indexes again
+        assert!(accept_log(&mut resume_point, 20, 0));
+        assert!(!accept_log(&mut resume_point, 19, 6));
+    }
+}
@@ -0,0 +1,123 @@
+// tests
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use tramex_tools::data::Data;
+    use tramex_tools::errors::ErrorCode;
+    use tramex_tools::interface::websocket::{
+        recording::{Recorder, Recording, day_timestamp, push_logs},
+        replay::{Replay, ReplaySpeed},
+    };
+
+    fn frame_of(idx: u64, timestamp: u64) -> String {
+        json!({
+            "message": "log_get",
+            "message_id": idx,
+            "logs": [{
+                "data": ["CCCH: RRC connection request"],
+                "src": "ENB",
+                "idx": idx,
+                "level": 3,
+                "timestamp": timestamp,
+                "layer": "RRC",
+                "dir": "UL",
+                "ue_id": 1
+            }],
+            "time": 1.0,
+            "utc": 1.0
+        })
+        .to_string()
+    }
+
+    fn record(frames: &[(i64, String)]) -> String {
+        let mut recorder = Recorder::new(Vec::new(), "ENB", 1000).unwrap();
+        for (time_ms, frame) in frames {
+            recorder.record(*time_ms, frame).unwrap();
+        }
+        assert_eq!(recorder.nb_frames(), frames.len());
+        String::from_utf8(recorder.finish().unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_record_and_read() {
+        let content = record(&[(1000, frame_of(1, 50)), (1500, frame_of(2, 60))]);
+        assert_eq!(content.lines().count(), 3);
+        let recording = Recording::parse(&content).unwrap();
+        assert_eq!(recording.header.name, "ENB");
+        assert_eq!(recording.frames.len(), 2);
+        assert_eq!(recording.frames[1].time_ms, 1500);
+        assert_eq!(recording.frames[1].frame, frame_of(2, 60));
+        let not_recording = Recording::parse("11:01:08.348 [RRC] DL    - 01 BCCH: SIB").unwrap_err();
+        assert!(matches!(not_recording.get_code(), ErrorCode::RecordingInvalid));
+        let truncated = format!("{content}{{\"time_ms\":");
+        let err = Recording::parse(&truncated).unwrap_err();
+        assert!(err.message.contains("line 4"));
+    }
+
+    #[test]
+    fn test_day_timestamp() {
+        // 2024-03-01 23:59:59.500 UTC
+        let before_midnight = 1_709_337_599_500;
+        let mut day_start = None;
+        assert_eq!(day_timestamp(&mut day_start, before_midnight), 86_399_500);
+        // the next day continues after the first one, like the logs of a file
+        assert_eq!(day_timestamp(&mut day_start, before_midnight + 1000), 86_400_500);
+        assert_eq!(day_start, Some(1_709_251_200_000));
+    }
+
+    #[test]
+    fn test_push_logs_again() {
+        let mut data = Data::default();
+        let mut day_start = None;
+        for frames in [vec![frame_of(1, 50), frame_of(2, 60)], vec![frame_of(2, 60), frame_of(3, 70)]] {
+            // a range fetched again starts without resume point
+            let mut resume_point = None;
+            for frame in frames {
+                let decoded = serde_json::from_str(&frame).unwrap();
+                push_logs(decoded, Some("ENB".to_owned()), &mut resume_point, &mut day_start, &mut data).unwrap();
+            }
+        }
+        let timestamps: Vec<u64> = data.events.iter().map(|trace| trace.timestamp).collect();
+        assert_eq!(timestamps, vec![50, 60, 70]);
+        assert!(data.contains(&data.events[1].clone()));
+    }
+
+    #[test]
+    fn test_replay_instant() {
+        // the same log twice: received again after a reconnection
+        let content = record(&[(1000, frame_of(1, 50)), (1500, frame_of(1, 50)), (9000, frame_of(2, 60))]);
+        let mut replay = Replay::new(Recording::parse(&content).unwrap(), ReplaySpeed::Instant);
+        let mut data = Data::default();
+        replay.poll(0, &mut data).unwrap();
+        assert!(replay.is_finished());
+        assert_eq!(data.events.len(), 2);
+        assert_eq!(data.events[0].source.as_deref(), Some("ENB"));
+        assert_eq!(data.events[1].timestamp, 60);
+    }
+
+    #[test]
+    fn test_replay_speed() {
+        let content = record(&[(1000, frame_of(1, 50)), (1400, frame_of(2, 60)), (2000, frame_of(3, 70))]);
+        let mut replay = Replay::new(Recording::parse(&content).unwrap(), ReplaySpeed::Speed(2.0));
+        let mut data = Data::default();
+        replay.poll(10_000, &mut data).unwrap();
+        assert_eq!(replay.position(), 1);
+        assert_eq!(replay.next_frame_in_ms(10_000), Some(200));
+        replay.poll(10_199, &mut data).unwrap();
+        assert_eq!(replay.position(), 1);
+        replay.poll(10_200, &mut data).unwrap();
+        assert_eq!(replay.position(), 2);
+        // paused: nothing is played
+        replay.pause(10_200);
+        assert_eq!(replay.next_frame_in_ms(20_000), None);
+        replay.poll(20_000, &mut data).unwrap();
+        assert_eq!(replay.position(), 2);
+        // the remaining 600 ms at the original speed
+        replay.play(20_000);
+        replay.set_speed(ReplaySpeed::Speed(1.0), 20_000);
+        assert_eq!(replay.next_frame_in_ms(20_000), Some(600));
+        replay.poll(20_600, &mut data).unwrap();
+        assert!(replay.is_finished());
+        assert_eq!(data.events.len(), 3);
+    }
+}
@@ -0,0 +1,122 @@
+// tests
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use tramex_tools::interface::{
+        layer::{LayerLogLevel, LayersPreset},
+        remote_api::{MAX_EXCHANGES, RemoteApi, Request},
+    };
+
+    #[test]
+    fn test_request_json() {
+        let request = Request::new("ue_get").param("stats", true);
+        assert_eq!(
+            request.to_json(7),
+            json!({"message": "ue_get", "message_id": 7, "stats": true})
+        );
+    }
+
+    #[test]
+    fn test_match_by_message_id() {
+        let mut remote = RemoteApi::default();
+        assert!(remote.request(Request::new("stats")).is_none());
+        remote.set_connected("ENB");
+        let stats_id = remote.request(Request::new("stats")).unwrap();
+        let config_id = remote.request(Request::new("config_get")).unwrap();
+        assert!(config_id > stats_id);
+        assert!(remote.is_pending("stats", 0));
+        let outgoing = remote.take_outgoing("ENB");
+        assert_eq!(outgoing.len(), 2);
+        // the message ID given when queued is the one sent
+        assert_eq!(outgoing[0].message_id, Some(stats_id));
+        for (message_id, request) in outgoing.into_iter().enumerate() {
+            remote.sent(message_id as u64 + 10, request, "ENB", 0);
+        }
+        assert!(remote.is_pending("stats", 100));
+        assert!(!remote.received(12, json!({"message": "stats"}), 100));
+        assert!(remote.received(11, json!({"message": "config_get", "message_id": 11}), 100));
+        assert!(remote.latest_response("stats").is_none());
+        assert!(remote.received(10, json!({"message": "stats", "error": "busy"}), 150));
+        let stats = remote.latest_response("stats").unwrap();
+        assert_eq!(stats.error(), Some("busy"));
+        assert_eq!(stats.latency_ms(), Some(150));
+        assert!(!remote.is_pending("stats", 200));
+    }
+
+    #[test]
+    fn test_pending_timeout_and_history() {
+        let mut remote = RemoteApi::default();
+        remote.sent(0, Request::new("ue_get"), "ENB", 0);
+        assert!(remote.is_pending("ue_get", 1000));
+        assert!(!remote.is_pending("ue_get", 60_000));
+        for message_id in 1..=MAX_EXCHANGES as u64 {
+            remote.sent(message_id, Request::new("stats"), "ENB", 0);
+        }
+        assert_eq!(remote.exchanges.len(), MAX_EXCHANGES);
+        assert_eq!(remote.exchanges.front().unwrap().message_id, 1);
+    }
+
+    #[test]
+    fn test_route_to_source() {
+        let mut remote = RemoteApi::default();
+        remote.set_connected("ENB");
+        remote.set_connected("MME");
+        assert!(remote.request(Request::new("stats").to("IMS")).is_none());
+        assert!(remote.request(Request::new("stats").to("MME")).is_some());
+        assert!(remote.request(Request::new("ue_get")).is_some());
+        let enb = remote.take_outgoing("ENB");
+        assert_eq!(enb.len(), 1);
+        assert_eq!(enb[0].message, "ue_get");
+        let mme = remote.take_outgoing("MME");
+        assert_eq!(mme.len(), 1);
+        assert_eq!(mme[0].target.as_deref(), Some("MME"));
+        remote.reset_connections();
+        assert!(!remote.is_connected());
+        assert!(remote.request(Request::new("stats")).is_none());
+    }
+
+    #[test]
+    fn test_responses_per_source() {
+        let mut remote = RemoteApi::default();
+        remote.set_connected("ENB");
+        remote.set_connected("MME");
+        let enb_id = remote.request(Request::new("stats").to("ENB")).unwrap();
+        assert!(remote.is_pending_to("stats", "ENB", 0));
+        assert!(!remote.is_pending_to("stats", "MME", 0));
+        let mme_id = remote.request(Request::new("stats").to("MME")).unwrap();
+        for source in ["ENB", "MME"] {
+            for request in remote.take_outgoing(source) {
+                remote.sent(request.message_id.unwrap(), request, source, 0);
+            }
+        }
+        assert!(remote.is_pending_to("stats", "MME", 100));
+        assert!(remote.received(enb_id, json!({"message": "stats"}), 100));
+        assert!(!remote.is_pending_to("stats", "ENB", 100));
+        assert!(remote.is_pending_to("stats", "MME", 100));
+        assert_eq!(remote.latest_response_from("stats", "ENB").unwrap().message_id, enb_id);
+        assert!(remote.latest_response_from("stats", "MME").is_none());
+        assert!(remote.received(mme_id, json!({"message": "stats"}), 120));
+        assert_eq!(remote.latest_response("stats").unwrap().message_id, mme_id);
+        assert_eq!(remote.latest_response_from("stats", "ENB").unwrap().message_id, enb_id);
+    }
+
+    #[test]
+    fn test_log_set_presets() {
+        let presets = LayersPreset::builtin();
+        let names: Vec<&str> = presets.iter().map(|preset| preset.name.as_str()).collect();
+        assert_eq!(names, ["RRC only", "Attach debugging", "PHY scheduling", "Everything"]);
+        let rrc_only = &presets[0].layers;
+        assert!(rrc_only.iter().iter().all(|(name, config)| match *name {
+            "RRC" => config.level == LayerLogLevel::Debug,
+            _ => config.level == LayerLogLevel::None,
+        }));
+        let request = Request::log_set(rrc_only).to_json(3);
+        assert_eq!(request["message"], "log_set");
+        assert_eq!(request["logs"]["layers"]["RRC"]["level"], "debug");
+        assert_eq!(request["logs"]["layers"]["PHY"]["level"], "none");
+        assert!(request["logs"]["layers"]["PHY"]["max_size"].is_number());
+        // saved with the app state
+        let stored = serde_json::to_string(&presets[1]).unwrap();
+        assert_eq!(serde_json::from_str::<LayersPreset>(&stored).unwrap(), presets[1]);
+    }
+}
@@ -0,0 +1,99 @@
+// tests
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::{get_path, rrc_trace};
+
+    use tramex_tools::{
+        analysis::rrc_state::{RrcState, rrc_states_at},
+        data::{AdditionalInfos, Data, Trace},
+        interface::{interface_file::file_handler::File, interface_types::InterfaceTrait, layer::Layers, types::Direction},
+    };
+
+    fn load_events(p: &str) -> Vec<Trace> {
+        let filename = &get_path(p);
+        let content = std::fs::read_to_string(filename).unwrap();
+        let mut file = File::new_file_content(filename.into(), content);
+        let mut data = Data::default();
+        while !file.full_read {
+            let _ = file.get_more_data(Layers::all_debug(), &mut data);
+        }
+        data.events
+    }
+
+    fn index_of(events: &[Trace], ue: u64, msg: &str) -> usize {
+        events
+            .iter()
+            .position(|trace| {
+                let AdditionalInfos::RRCInfos(infos) = &trace.additional_infos;
+                infos.ue_id == Some(ue) && infos.canal_msg == msg
+            })
+            .unwrap()
+    }
+
+    #[test]
+    fn test_connection_setup_and_release() {
+        let events = load_events("enb0_only_rrc.log");
+        assert!(rrc_states_at(&events, 0).get(&1) == Some(&RrcState::Connecting));
+        let setup_complete = index_of(&events, 1, "RRC connection setup complete");
+        assert!(rrc_states_at(&events, setup_complete - 1).get(&1) == Some(&RrcState::Connecting));
+        assert!(rrc_states_at(&events, setup_complete).get(&1) == Some(&RrcState::Connected));
+        let release = index_of(&events, 1, "RRC connection release");
+        assert!(rrc_states_at(&events, release - 1).get(&1) == Some(&RrcState::Connected));
+        assert!(rrc_states_at(&events, release).get(&1) == Some(&RrcState::Idle));
+    }
+
+    #[test]
+    fn test_reestablishment_per_ue() {
+        let events = load_events("enb0_only_rrc.log");
+        let request = index_of(&events, 2, "RRC connection reestablishment request");
+        assert!(!rrc_states_at(&events, request - 1).contains_key(&2));
+        let states = rrc_states_at(&events, request);
+        assert!(states.get(&2) == Some(&RrcState::Reestablishing));
+        assert!(states.get(&1) == Some(&RrcState::Connected));
+        let complete = index_of(&events, 2, "RRC connection reestablishment complete");
+        assert!(rrc_states_at(&events, complete).get(&2) == Some(&RrcState::Connected));
+    }
+
+    #[test]
+    fn test_deterministic_backward() {
+        let events = load_events("enb0_only_rrc.log");
+        let forward: Vec<_> = (0..events.len()).map(|idx| rrc_states_at(&events, idx)).collect();
+        for idx in (0..events.len()).rev() {
+            assert!(rrc_states_at(&events, idx) == forward[idx]);
+        }
+    }
+
+    fn ue_trace(ue_id: u64, direction: Direction, canal: &str, canal_msg: &str) -> Trace {
+        let mut trace = rrc_trace(0, direction, canal, canal_msg);
+        let AdditionalInfos::RRCInfos(infos) = &mut trace.additional_infos;
+        infos.ue_id = Some(ue_id);
+        trace
+    }
+
+    #[test]
+    fn test_ue_id_change() {
+        let events = load_events("enb0_only_rrc.log");
+        let change = index_of(&events, 2, "Changing UE_ID to 0x1");
+        let AdditionalInfos::RRCInfos(infos) = &events[change].additional_infos;
+        assert!(infos.direction == Direction::NONE);
+        assert!(events[change].text.is_none());
+        assert!(rrc_states_at(&events, change - 1).get(&2) == Some(&RrcState::Connected));
+        let states = rrc_states_at(&events, change);
+        assert!(!states.contains_key(&2));
+        assert!(states.get(&1) == Some(&RrcState::Connected));
+
+        // the UE released before is connected again under its former ID
+        let events = vec![
+            ue_trace(1, Direction::DL, "DCCH", "RRC connection release"),
+            ue_trace(2, Direction::UL, "CCCH", "RRC connection reestablishment request"),
+            ue_trace(2, Direction::UL, "DCCH", "RRC connection reestablishment complete"),
+            ue_trace(2, Direction::NONE, "", "Changing UE_ID to 0x1"),
+        ];
+        assert!(rrc_states_at(&events, 2).get(&1) == Some(&RrcState::Idle));
+        let states = rrc_states_at(&events, 3);
+        assert!(states.len() == 1);
+        assert!(states.get(&1) == Some(&RrcState::Connected));
+    }
+}
@@ -0,0 +1,158 @@
+// tests
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::rrc_trace;
+    use tramex_tools::{
+        data::{Data, Trace},
+        errors::ErrorCode,
+        interface::types::Direction,
+        search::{Search, SearchMode, SearchQuery, next_match, parse_hex_pattern, previous_match},
+    };
+
+    fn trace_of(timestamp: u64, canal_msg: &str, text: &[&str], hexa: &[u8]) -> Trace {
+        Trace {
+            hexa: hexa.to_vec(),
+            text: Some(text.iter().map(|line| line.to_string()).collect()),
+            ..rrc_trace(timestamp, Direction::UL, "DCCH", canal_msg)
+        }
+    }
+
+    fn data() -> Data {
+        let mut data = Data::default();
+        data.push_ordered(trace_of(
+            10,
+            "RRC connection request",
+            &["ue-Identity s-TMSI"],
+            &[0x40, 0x12, 0x98],
+        ));
+        data.push_ordered(trace_of(
+            20,
+            "RRC connection setup",
+            &["srb-ToAddModList"],
+            &[0x60, 0x12, 0x9b],
+        ));
+        data.push_ordered(trace_of(
+            30,
+            "SIB1",
+            &["cellAccessRelatedInfo", "plmn-IdentityList"],
+            &[0x40, 0x12],
+        ));
+        data.push_ordered(trace_of(40, "RRC connection release", &["releaseCause other"], &[0x28, 0x02]));
+        data
+    }
+
+    fn search(pattern: &str, mode: SearchMode) -> Search {
+        Search::new(&SearchQuery::new(pattern, mode)).unwrap()
+    }
+
+    #[test]
+    fn test_search_text() {
+        let data = data();
+        assert_eq!(search("connection", SearchMode::Text).matching_indexes(&data), vec![0, 1, 3]);
+        // case insensitive by default
+        assert_eq!(search("SIB1", SearchMode::Text).matching_indexes(&data), vec![2]);
+        assert_eq!(search("sib1", SearchMode::Text).matching_indexes(&data), vec![2]);
+        let query = SearchQuery::new("sib1", SearchMode::Text).case_sensitive(true);
+        assert!(Search::new(&query).unwrap().matching_indexes(&data).is_empty());
+        // in the text
+        assert_eq!(search("identity", SearchMode::Text).matching_indexes(&data), vec![0, 2]);
+        // special characters are not a regular expression
+        assert!(search("RRC.*setup", SearchMode::Text).matching_indexes(&data).is_empty());
+    }
+
+    #[test]
+    fn test_search_whole_word() {
+        let data = data();
+        let query = SearchQuery::new("Identity", SearchMode::Text).whole_word(true);
+        // "plmn-IdentityList" doesn't contain the whole word
+        assert_eq!(Search::new(&query).unwrap().matching_indexes(&data), vec![0]);
+        let query = SearchQuery::new("Ident", SearchMode::Text).whole_word(true);
+        assert!(Search::new(&query).unwrap().matching_indexes(&data).is_empty());
+        let query = SearchQuery::new("conn", SearchMode::Regex).whole_word(true);
+        assert!(Search::new(&query).unwrap().matching_indexes(&data).is_empty());
+    }
+
+    #[test]
+    fn test_search_regex() {
+        let data = data();
+        assert_eq!(
+            search("RRC.*(setup|release)", SearchMode::Regex).matching_indexes(&data),
+            vec![1, 3]
+        );
+        assert_eq!(search("^sib\\d$", SearchMode::Regex).matching_indexes(&data), vec![2]);
+        let err = Search::new(&SearchQuery::new("(unclosed", SearchMode::Regex)).unwrap_err();
+        assert!(matches!(err.get_code(), ErrorCode::SearchInvalidPattern));
+        let err = Search::new(&SearchQuery::new("  ", SearchMode::Text)).unwrap_err();
+        assert!(matches!(err.get_code(), ErrorCode::SearchInvalidPattern));
+    }
+
+    #[test]
+    fn test_search_scope() {
+        let data = data();
+        let mut query = SearchQuery::new("identity", SearchMode::Text);
+        query.in_text = false;
+        assert!(Search::new(&query).unwrap().matching_indexes(&data).is_empty());
+        let mut query = SearchQuery::new("connection", SearchMode::Text);
+        query.in_message = false;
+        assert!(Search::new(&query).unwrap().matching_indexes(&data).is_empty());
+    }
+
+    #[test]
+    fn test_search_hex() {
+        let data = data();
+        assert_eq!(search("40 12", SearchMode::Hex).matching_indexes(&data), vec![0, 2]);
+        assert_eq!(search("0x12,0x9b", SearchMode::Hex).matching_indexes(&data), vec![1]);
+        assert_eq!(search("?? 12 98", SearchMode::Hex).matching_indexes(&data), vec![0]);
+        assert_eq!(search("?? 12 ??", SearchMode::Hex).matching_indexes(&data), vec![0, 1]);
+        assert_eq!(
+            search("4012", SearchMode::Hex).find_in_bytes(&[0x40, 0x12, 0x40, 0x12]),
+            vec![0..2, 2..4]
+        );
+        // a hexadecimal search doesn't match the text
+        assert!(search("12", SearchMode::Hex).find_in_str("12").is_empty());
+
+        assert_eq!(parse_hex_pattern("0a:1B ??").unwrap(), vec![Some(0x0a), Some(0x1b), None]);
+        let err = parse_hex_pattern("0a 1").unwrap_err();
+        assert!(err.message.contains("column 4"), "{}", err.message);
+        let err = parse_hex_pattern("0a zz").unwrap_err();
+        assert!(err.message.contains("column 4"), "{}", err.message);
+        assert!(parse_hex_pattern("+f").is_err());
+        assert!(parse_hex_pattern(" , ").is_err());
+    }
+
+    #[test]
+    fn test_trace_matches() {
+        let data = data();
+        let matches = search("rrc", SearchMode::Text).trace_matches(&data.events[0]);
+        assert_eq!(matches.message, vec![0..3]);
+        assert!(matches.text.is_empty());
+        let matches = search("list", SearchMode::Text).trace_matches(&data.events[2]);
+        assert!(matches.message.is_empty());
+        assert_eq!(matches.text, vec![(1, 13..17)]);
+        let matches = search("12", SearchMode::Hex).trace_matches(&data.events[1]);
+        assert_eq!(matches.hexa, vec![1..2]);
+        assert!(!matches.is_empty());
+    }
+
+    #[test]
+    fn test_find_next_previous() {
+        let data = data();
+        let connection = search("connection", SearchMode::Text).matching_indexes(&data);
+        assert_eq!(next_match(&connection, 1), Some(3));
+        assert_eq!(previous_match(&connection, 1), Some(0));
+        // the current trace does not match
+        assert_eq!(next_match(&connection, 2), Some(3));
+        assert_eq!(previous_match(&connection, 2), Some(1));
+        // wrap around
+        assert_eq!(next_match(&connection, 3), Some(0));
+        assert_eq!(previous_match(&connection, 0), Some(3));
+        // the only match is the current trace
+        let sib = search("sib1", SearchMode::Text).matching_indexes(&data);
+        assert_eq!(next_match(&sib, 2), Some(2));
+        assert_eq!(previous_match(&sib, 2), Some(2));
+        assert_eq!(next_match(&[], 0), None);
+        assert_eq!(previous_match(&[], 0), None);
+    }
+}
@@ -0,0 +1,155 @@
+// tests
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::get_path;
+
+    use tramex_tools::{
+        data::{Data, TraceFilter},
+        errors::ErrorCode,
+        interface::{
+            interface_file::file_handler::File,
+            interface_types::InterfaceTrait,
+            layer::{LayerLogLevel, Layers},
+        },
+        session::{SESSION_SOURCE_PREFIX, SESSION_VERSION, Session, SourceInfo, SourceKind},
+    };
+
+    fn read_all(p: &str, layers: Layers, data: &mut Data) -> File {
+        let filename = &get_path(p);
+        let content = std::fs::read_to_string(filename).unwrap();
+        let mut file = File::new_file_content(filename.into(), content);
+        while !file.full_read {
+            let _ = file.get_more_data(layers.clone(), data);
+        }
+        file
+    }
+
+    fn session_of(data: &Data) -> Session {
+        let sources = vec![
+            SourceInfo::new("enb.log", SourceKind::File)
+                .with_location("tests/enb.log")
+                .with_clock_offset(-500),
+        ];
+        Session::new(
+            data,
+            sources,
+            serde_json::json!({"open_windows": ["Timeline"]}),
+            1_700_000_000_000,
+        )
+        .with_layers(Layers::all_debug())
+    }
+
+    #[test]
+    fn test_session_round_trip() {
+        let mut data = Data::default();
+        read_all("enb.log", Layers::all_debug(), &mut data);
+        data.current_index = 7;
+        data.filter = TraceFilter {
+            time_range: Some((1, 2)),
+            ue_id: Some(3),
+        };
+        let json = session_of(&data).to_json().unwrap();
+
+        let mut session = Session::parse(&json).unwrap();
+        assert_eq!(session.version, SESSION_VERSION);
+        assert_eq!(session.saved_ms, 1_700_000_000_000);
+        assert_eq!(session.sources[0].kind, SourceKind::File);
+        assert_eq!(session.sources[0].location.as_deref(), Some("tests/enb.log"));
+        assert_eq!(session.sources[0].clock_offset_ms, -500);
+        assert_eq!(session.layout["open_windows"][0], "Timeline");
+        assert_eq!(session.layers, Some(Layers::all_debug()));
+
+        let mut restored = Data::default();
+        let sources = session.restore(&mut restored);
+        assert_eq!(sources, vec![format!("{SESSION_SOURCE_PREFIX}enb.log")]);
+        assert!(session.events.is_empty());
+        assert_eq!(restored.events.len(), data.events.len());
+        assert_eq!(restored.current_index, 7);
+        assert_eq!(restored.filter, data.filter);
+        for (trace, expected) in restored.events.iter().zip(data.events.iter()) {
+            assert_eq!(trace.timestamp, expected.timestamp);
+            assert_eq!(trace.layer, expected.layer);
+            assert_eq!(trace.hexa, expected.hexa);
+            assert_eq!(trace.text, expected.text);
+            assert_eq!(
+                trace.source,
+                expected
+                    .source
+                    .as_ref()
+                    .map(|source| format!("{SESSION_SOURCE_PREFIX}{source}"))
+            );
+        }
+        let infos = session.restored_source_infos();
+        assert_eq!(infos[0].name, sources[0]);
+        assert_eq!(infos[0].clock_offset_ms, -500);
+        // a session saved again keeps a single prefix, for its traces and its sources
+        let mut again = Session::parse(&session_of(&restored).to_json().unwrap()).unwrap();
+        assert_eq!(again.restore(&mut Data::default()), sources);
+        let again = Session::new(&restored, infos, serde_json::Value::Null, 0);
+        assert_eq!(again.restored_source_infos()[0].name, sources[0]);
+    }
+
+    #[test]
+    fn test_session_hidden_events() {
+        let mut layers = Layers::all_debug();
+        for (name, config) in layers.iter_mut() {
+            if name == "RRC" {
+                config.level = LayerLogLevel::None;
+            }
+        }
+        let mut data = Data::default();
+        read_all("enb.log", layers, &mut data);
+        assert!(data.events.is_empty());
+        let mut session = Session::parse(&session_of(&data).to_json().unwrap()).unwrap();
+        let mut restored = Data::default();
+        session.restore(&mut restored);
+        assert_eq!(restored.hidden_events.len(), data.hidden_events.len());
+        restored.filter_layers(&Layers::all_debug());
+        assert_eq!(restored.events.len(), data.hidden_events.len());
+    }
+
+    #[test]
+    fn test_session_merge() {
+        let mut data = Data::default();
+        read_all("enb.log", Layers::all_debug(), &mut data);
+        let nb_events = data.events.len();
+        data.current_index = 3;
+        let mut session = Session::parse(&session_of(&data).to_json().unwrap()).unwrap();
+
+        // the traces already loaded and the current trace are kept
+        let mut other = Data::default();
+        read_all("enb0_only_rrc.log", Layers::all_debug(), &mut other);
+        let nb_other = other.events.len();
+        other.current_index = 1;
+        let current = other.get_current_trace().map(|trace| trace.timestamp);
+        let sources = session.restore(&mut other);
+        assert_eq!(other.events.len(), nb_events + nb_other);
+        // closing the session removes only its traces
+        other.remove_source(&sources[0]);
+        assert_eq!(other.events.len(), nb_other);
+        assert_eq!(other.get_current_trace().map(|trace| trace.timestamp), current);
+        assert!(other.events.windows(2).all(|pair| pair[0].timestamp <= pair[1].timestamp));
+    }
+
+    #[test]
+    fn test_session_invalid() {
+        let err = Session::parse("{\"session\": \"tramex-session\",\n \"version\": }").unwrap_err();
+        assert!(matches!(err.get_code(), ErrorCode::SessionInvalid));
+        assert!(err.message.contains("line 2"), "{}", err.message);
+
+        let err = Session::parse("{\"recording\": \"tramex\", \"version\": 1}").unwrap_err();
+        assert!(matches!(err.get_code(), ErrorCode::SessionInvalid));
+        assert!(err.message.starts_with("Not a session"));
+
+        let json = format!("{{\"session\": \"tramex-session\", \"version\": {}}}", SESSION_VERSION + 1);
+        let err = Session::parse(&json).unwrap_err();
+        assert!(err.message.starts_with("Unsupported session version"));
+
+        let json =
+            format!("{{\"session\": \"tramex-session\", \"version\": {SESSION_VERSION}, \"saved_ms\": 0, \"events\": [1]}}");
+        let err = Session::parse(&json).unwrap_err();
+        assert!(matches!(err.get_code(), ErrorCode::SessionInvalid));
+    }
+}
@@ -0,0 +1,129 @@
+// tests
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::{get_path, rrc_trace};
+
+    use tramex_tools::{
+        data::{Data, Trace},
+        interface::{interface_file::file_handler::File, interface_types::InterfaceTrait, layer::Layers, types::Direction},
+    };
+
+    fn open_file(p: &str) -> File {
+        let filename = &get_path(p);
+        let content = std::fs::read_to_string(filename).unwrap();
+        File::new_file_content(filename.into(), content)
+    }
+
+    fn read_all(file: &mut File, data: &mut Data) {
+        while !file.full_read {
+            let _ = file.get_more_data(Layers::all_debug(), data);
+        }
+    }
+
+    fn trace_of(timestamp: u64, source: &str) -> Trace {
+        Trace {
+            source: Some(source.to_owned()),
+            ..rrc_trace(timestamp, Direction::UL, "DCCH", &format!("{source} {timestamp}"))
+        }
+    }
+
+    fn timestamps(data: &Data) -> Vec<u64> {
+        data.events.iter().map(|trace| trace.timestamp).collect()
+    }
+
+    #[test]
+    fn test_push_ordered() {
+        let mut data = Data::default();
+        data.push_ordered(trace_of(10, "ENB"));
+        data.push_ordered(trace_of(30, "ENB"));
+        assert_eq!(data.revision, 0);
+        data.current_index = 1;
+        data.push_ordered(trace_of(20, "MME"));
+        data.push_ordered(trace_of(10, "MME"));
+        assert_eq!(timestamps(&data), vec![10, 10, 20, 30]);
+        assert_eq!(data.events[1].source.as_deref(), Some("MME"));
+        assert_eq!(data.revision, 2);
+        // the current trace did not change
+        assert_eq!(data.current_index, 3);
+        assert_eq!(data.get_current_trace().unwrap().timestamp, 30);
+        // a trace is found again after an insertion
+        assert_eq!(data.position(&trace_of(30, "ENB")), Some(3));
+        assert_eq!(data.position(&trace_of(10, "MME")), Some(1));
+        assert_eq!(data.position(&trace_of(30, "MME")), None);
+    }
+
+    #[test]
+    fn test_remove_source() {
+        let mut data = Data::default();
+        for (timestamp, source) in [(10, "ENB"), (15, "MME"), (20, "ENB"), (25, "MME")] {
+            data.push_ordered(trace_of(timestamp, source));
+        }
+        data.current_index = 2;
+        data.remove_source("MME");
+        assert_eq!(timestamps(&data), vec![10, 20]);
+        assert_eq!(data.current_index, 1);
+        assert_eq!(data.revision, 1);
+        data.current_index = 1;
+        data.remove_source("ENB");
+        assert!(data.events.is_empty());
+        assert_eq!(data.current_index, 0);
+    }
+
+    #[test]
+    fn test_absolute_timestamp() {
+        let mut file = File::default();
+        assert_eq!(file.absolute_timestamp(86_399_000), 86_399_000);
+        // after midnight
+        assert_eq!(file.absolute_timestamp(500), 86_400_500);
+        // a log slightly out of order is not a new day
+        assert_eq!(file.absolute_timestamp(400), 86_400_400);
+        file.clock_offset_ms = -1000;
+        assert_eq!(file.absolute_timestamp(1000), 86_400_000);
+    }
+
+    #[test]
+    fn test_merge_files() {
+        let mut data = Data::default();
+        let mut enb = open_file("enb.log");
+        let mut other = open_file("enb0_only_rrc.log");
+        read_all(&mut enb, &mut data);
+        let nb_enb = data.events.len();
+        read_all(&mut other, &mut data);
+        assert!(data.events.len() > nb_enb);
+        assert!(data.events.windows(2).all(|pair| pair[0].timestamp <= pair[1].timestamp));
+        let enb_source = enb.source();
+        assert_eq!(enb_source.as_deref(), Some("enb.log"));
+        let enb_timestamps: Vec<u64> = data
+            .events
+            .iter()
+            .filter(|trace| trace.source == enb_source)
+            .map(|trace| trace.timestamp)
+            .collect();
+        assert_eq!(enb_timestamps.len(), nb_enb);
+        enb.set_clock_offset(2500, &mut data);
+        let shifted: Vec<u64> = data
+            .events
+            .iter()
+            .filter(|trace| trace.source == enb_source)
+            .map(|trace| trace.timestamp)
+            .collect();
+        assert_eq!(
+            shifted,
+            enb_timestamps.iter().map(|timestamp| timestamp + 2500).collect::<Vec<u64>>()
+        );
+        assert!(data.events.windows(2).all(|pair| pair[0].timestamp <= pair[1].timestamp));
+    }
+
+    #[test]
+    fn test_unique_source() {
+        let mut file = File::new("logs/build1/enb.log".into(), String::new());
+        file.make_source_unique(&["ue.log".to_owned()]);
+        assert_eq!(file.source().as_deref(), Some("enb.log"));
+        let sources = vec!["enb.log".to_owned(), "enb.log #2".to_owned()];
+        let mut other = File::new("logs/build2/enb.log".into(), String::new());
+        other.make_source_unique(&sources);
+        assert_eq!(other.source().as_deref(), Some("enb.log #3"));
+    }
+}
@@ -0,0 +1,48 @@
+// tests
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use tramex_tools::interface::stats::{CellMetric, StatsHistory, StatsResponse};
+
+    fn stats_with(dl_bitrate: f64, cpu: f64) -> StatsResponse {
+        let json = json!({
+            "message": "stats",
+            "message_id": 2,
+            "cpu": {"global": cpu},
+            "cells": {
+                "1": {"dl_bitrate": dl_bitrate, "ul_bitrate": 1000.0, "dl_use_avg": 0.25, "ue_count_avg": 2.0}
+            }
+        });
+        StatsResponse::from_json(&json).unwrap()
+    }
+
+    #[test]
+    fn test_stats_response() {
+        let stats = stats_with(5e6, 40.0);
+        assert_eq!(stats.cpu.unwrap().global, Some(40.0));
+        let cell = &stats.cells["1"];
+        assert_eq!(CellMetric::DlBitrate.value(cell), Some(5e6));
+        assert_eq!(CellMetric::DlPrbUsage.value(cell), Some(25.0));
+        assert_eq!(CellMetric::UlPrbUsage.value(cell), None);
+        assert_eq!(CellMetric::UeCount.value(cell), Some(2.0));
+    }
+
+    #[test]
+    fn test_stats_history() {
+        let mut history = StatsHistory {
+            max_samples: 3,
+            ..Default::default()
+        };
+        for (idx, dl_bitrate) in [1.0, 2.0, 3.0, 4.0].into_iter().enumerate() {
+            history.push(10_000 + idx as i64 * 500, stats_with(dl_bitrate, 10.0 * idx as f64));
+        }
+        assert_eq!(history.samples.len(), 3);
+        assert_eq!(history.cells(), vec!["1"]);
+        assert_eq!(
+            history.cell_series("1", CellMetric::DlBitrate),
+            vec![[0.0, 2.0], [0.5, 3.0], [1.0, 4.0]]
+        );
+        assert_eq!(history.cpu_series().last(), Some(&[1.0, 30.0]));
+        assert!(history.cell_series("2", CellMetric::DlBitrate).is_empty());
+    }
+}
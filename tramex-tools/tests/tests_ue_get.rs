@@ -0,0 +1,80 @@
+// tests
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::rrc_trace;
+    use serde_json::json;
+    use tramex_tools::{
+        data::{AdditionalInfos, Trace, TraceFilter},
+        interface::{types::Direction, ue_get::UeGetResponse},
+    };
+
+    fn trace_of(ue_id: Option<u64>) -> Trace {
+        let mut trace = rrc_trace(0, Direction::UL, "DCCH", "RRC connection setup complete");
+        let AdditionalInfos::RRCInfos(infos) = &mut trace.additional_infos;
+        infos.ue_id = ue_id;
+        trace
+    }
+
+    #[test]
+    fn test_ue_get_response() {
+        let json = json!({
+            "message": "ue_get",
+            "message_id": 4,
+            "ue_list": [
+                {
+                    "enb_ue_id": 2,
+                    "mme_ue_id": 1,
+                    "cells": [{"cell_id": 1, "rnti": 70, "dl_bitrate": 1500000.0, "ul_bitrate": 2000.0}],
+                    "erab_list": [{"erab_id": 5, "qci": 9}]
+                },
+                {
+                    "ran_ue_id": 3,
+                    "5g_guti": "0x1234",
+                    "cells": [{"cell_id": 2, "rnti": 17}],
+                    "qos_flow_list": [{"pdu_session_id": 1, "qfi": 9}]
+                }
+            ]
+        });
+        let response = UeGetResponse::from_json(&json).unwrap();
+        assert_eq!(response.ue_list.len(), 2);
+        let lte = &response.ue_list[0];
+        assert_eq!(lte.ue_id(), Some(2));
+        assert_eq!(lte.rnti(), Some(70));
+        assert_eq!(lte.identity(), None);
+        assert_eq!(lte.dl_bitrate(), 1500000.0);
+        assert_eq!(lte.bearers(), vec!["E-RAB 5"]);
+        let nr = &response.ue_list[1];
+        assert_eq!(nr.ue_id(), Some(3));
+        assert_eq!(nr.identity(), Some("0x1234"));
+        assert_eq!(nr.ul_bitrate(), 0.0);
+        assert_eq!(nr.bearers(), vec!["PDU 1 QFI 9"]);
+    }
+
+    #[test]
+    fn test_filter_on_ue() {
+        let filter = TraceFilter {
+            ue_id: Some(2),
+            ..Default::default()
+        };
+        assert!(!filter.is_empty());
+        assert!(filter.matches(&trace_of(Some(2))));
+        assert!(!filter.matches(&trace_of(Some(1))));
+        assert!(!filter.matches(&trace_of(None)));
+        assert!(TraceFilter::default().matches(&trace_of(None)));
+        // the trace of the fixture is at 0
+        let filter = TraceFilter {
+            time_range: Some((0, 10)),
+            ue_id: Some(1),
+        };
+        assert!(filter.matches_time(&trace_of(Some(2))));
+        assert!(!filter.matches(&trace_of(Some(2))));
+        let filter = TraceFilter {
+            time_range: Some((1, 10)),
+            ..Default::default()
+        };
+        assert!(!filter.matches_time(&trace_of(None)));
+        assert!(filter.matches_ue(&trace_of(None)));
+    }
+}
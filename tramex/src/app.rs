@@ -163,7 +163,9 @@ impl eframe::App for TramexApp {
         egui::TopBottomPanel::top("top_panel").show(ctx, |ui| {
             ui.horizontal_wrapped(|ui| {
                 self.menu_bar(ctx, ui);
-                self.frontend.menu_bar(ui);
+                if let Err(err) = self.frontend.menu_bar(ui) {
+                    self.error_panel.push(err);
+                }
             });
         });
 
@@ -1,18 +1,24 @@
 //! Frontend module
 use crate::handlers::Handler;
 use crate::handlers::handler_file::FileHandler;
+use crate::handlers::handler_replay::ReplayHandler;
+use crate::handlers::handler_session::SessionHandler;
 #[cfg(feature = "websocket")]
 use crate::handlers::handler_ws::WsHandler;
 
 use crate::panels::{
-    PanelController, logical_channels::LogicalChannels, panel_message::MessageBox, rrc_status::LinkPanel,
-    trame_manager::TrameManager,
+    PanelController, cell_stats::CellStats, compare::ComparePanel, console::Console, hex_viewer::HexViewer,
+    ladder_diagram::LadderDiagram, logical_channels::LogicalChannels, message_list::MessageList, panel_message::MessageBox,
+    procedures::ProceduresPanel, rrc_status::LinkPanel, search::SearchPanel, timeline::Timeline,
+    trame_manager::TrameManager, ue_list::UeList,
 };
 use crate::set_open;
 use egui::Ui;
 use std::collections::BTreeSet;
 use tramex_tools::data::Data;
 use tramex_tools::errors::{ErrorCode, TramexError};
+#[cfg(not(target_arch = "wasm32"))]
+use tramex_tools::session::{SESSION_EXTENSION, Session};
 
 #[derive(serde::Deserialize, serde::Serialize, Debug, Clone, PartialEq, Default)]
 /// Choice enum
@@ -24,6 +30,23 @@ pub enum Choice {
     /// WebSocket choice
     #[cfg(feature = "websocket")]
     WebSocket,
+
+    /// Replay of a websocket recording
+    Replay,
+
+    /// Session saved by Tramex
+    Session,
+}
+
+#[derive(serde::Deserialize, serde::Serialize, Default)]
+#[serde(default)]
+/// Layout of the panels, saved with a session
+struct SessionLayout {
+    /// Open windows
+    open_windows: BTreeSet<String>,
+
+    /// Position of the windows (areas of egui)
+    areas: serde_json::Value,
 }
 
 #[derive(serde::Deserialize, serde::Serialize)]
@@ -44,8 +67,8 @@ pub struct FrontEnd {
     pub open_menu_connector: bool,
 
     #[serde(skip)]
-    /// File upload
-    handler: Option<Box<dyn Handler>>,
+    /// Interfaces, the last one may not be connected yet
+    handlers: Vec<Box<dyn Handler>>,
 
     /// Trame manager
     trame_manager: TrameManager,
@@ -62,7 +85,7 @@ impl Default for FrontEnd {
             windows: Vec::new(),
             open_menu_connector: true,
             radio_choice: Choice::default(),
-            handler: None,
+            handlers: Vec::new(),
             trame_manager: TrameManager::new(),
         }
     }
@@ -74,10 +97,30 @@ impl FrontEnd {
         let mb = MessageBox::new();
         let lc = LogicalChannels::new();
         let status = LinkPanel::new();
+        let list = MessageList::new();
+        let ladder = LadderDiagram::new();
+        let timeline = Timeline::new();
+        let procedures = ProceduresPanel::new();
+        let console = Console::new();
+        let ue_list = UeList::new();
+        let cell_stats = CellStats::new();
+        let search = SearchPanel::new();
+        let hex_viewer = HexViewer::new();
+        let compare = ComparePanel::new();
         let wins: Vec<Box<dyn PanelController>> = vec![
             Box::<MessageBox>::new(mb),
             Box::<LogicalChannels>::new(lc),
             Box::<LinkPanel>::new(status),
+            Box::<MessageList>::new(list),
+            Box::<LadderDiagram>::new(ladder),
+            Box::<Timeline>::new(timeline),
+            Box::<ProceduresPanel>::new(procedures),
+            Box::<Console>::new(console),
+            Box::<UeList>::new(ue_list),
+            Box::<CellStats>::new(cell_stats),
+            Box::<SearchPanel>::new(search),
+            Box::<HexViewer>::new(hex_viewer),
+            Box::<ComparePanel>::new(compare),
         ];
         let mut open_windows = BTreeSet::new();
         for one_box in wins.iter() {
@@ -91,12 +134,16 @@ impl FrontEnd {
     }
 
     /// Menu bar
-    pub fn menu_bar(&mut self, ui: &mut Ui) {
+    /// # Errors
+    /// Return an error if the session can't be saved
+    pub fn menu_bar(&mut self, ui: &mut Ui) -> Result<(), TramexError> {
+        let mut result = Ok(());
         if self.interface_available() {
             ui.with_layout(egui::Layout::top_down(egui::Align::RIGHT), |ui| {
                 ui.horizontal(|ui| {
-                    if let Some(handle) = &self.handler {
-                        self.trame_manager.show_controls(ui, &mut self.data, handle.is_full_read());
+                    if !self.handlers.is_empty() {
+                        let full_read = self.is_full_read();
+                        self.trame_manager.show_controls(ui, &mut self.data, full_read);
                     }
                     ui.menu_button("Windows", |ui| {
                         for one_window in self.windows.iter_mut() {
@@ -105,9 +152,73 @@ impl FrontEnd {
                             set_open(&mut self.open_windows, one_window.name(), is_open);
                         }
                     });
+                    result = self.ui_save_session(ui);
                 });
             });
         }
+        result
+    }
+
+    /// Show the button to save the session, sessions are saved by the native application only
+    /// # Errors
+    /// Return an error if the session can't be saved
+    fn ui_save_session(&self, _ui: &mut Ui) -> Result<(), TramexError> {
+        #[cfg(not(target_arch = "wasm32"))]
+        if _ui
+            .button("Save session")
+            .on_hover_text("Save the traces, the current message and the layout to open them again later")
+            .clicked()
+        {
+            return self.save_session(_ui.ctx());
+        }
+        Ok(())
+    }
+
+    /// Save the session with the file dialog
+    /// # Errors
+    /// Return an error if the session can't be saved
+    #[cfg(not(target_arch = "wasm32"))]
+    fn save_session(&self, ctx: &egui::Context) -> Result<(), TramexError> {
+        let Some(path) = rfd::FileDialog::new()
+            .add_filter("Tramex session", &[SESSION_EXTENSION])
+            .set_file_name(format!("session.{SESSION_EXTENSION}"))
+            .save_file()
+        else {
+            return Ok(());
+        };
+        let sources = self.handlers.iter().flat_map(|handle| handle.source_infos()).collect();
+        let session = Session::new(
+            &self.data,
+            sources,
+            self.layout(ctx),
+            tramex_tools::interface::websocket::reconnect::now_ms(),
+        )
+        .with_layers(self.trame_manager.layers_list.clone());
+        session.save(&path)
+    }
+
+    /// Layout of the panels: open windows and their positions
+    #[cfg(not(target_arch = "wasm32"))]
+    fn layout(&self, ctx: &egui::Context) -> serde_json::Value {
+        let layout = SessionLayout {
+            open_windows: self.open_windows.clone(),
+            areas: ctx.memory(|mem| serde_json::to_value(mem.areas())).unwrap_or_default(),
+        };
+        serde_json::to_value(layout).unwrap_or_default()
+    }
+
+    /// Restore the layout of the panels of a session
+    fn restore_layout(&mut self, ctx: &egui::Context, layout: serde_json::Value) {
+        let Ok(layout) = serde_json::from_value::<SessionLayout>(layout) else {
+            log::warn!("Invalid layout in the session");
+            return;
+        };
+        if !layout.open_windows.is_empty() {
+            self.open_windows = layout.open_windows;
+        }
+        if let Ok(areas) = serde_json::from_value(layout.areas) {
+            ctx.memory_mut(|mem| *mem.areas_mut() = areas);
+        }
     }
 
     /// Show the UI connector
@@ -122,58 +233,79 @@ impl FrontEnd {
                     ui.vertical_centered(|ui| {
                         ui.heading("Connector");
                         let save = self.radio_choice.clone();
+                        let pending = self.handlers.last().is_none_or(|handle| !handle.is_interface());
                         ui.horizontal(|ui| {
-                            let enabled = if let Some(handle) = &self.handler {
-                                !handle.is_interface()
-                            } else {
-                                true
-                            };
-                            ui.add_enabled_ui(enabled, |ui| {
+                            ui.add_enabled_ui(pending, |ui| {
                                 ui.label("Choose ws or file");
                                 ui.radio_value(&mut self.radio_choice, Choice::File, "File");
                                 #[cfg(feature = "websocket")]
                                 ui.radio_value(&mut self.radio_choice, Choice::WebSocket, "WebSocket");
+                                ui.radio_value(&mut self.radio_choice, Choice::Replay, "Replay");
+                                ui.radio_value(&mut self.radio_choice, Choice::Session, "Session");
                             });
                         });
-                        match &self.radio_choice {
-                            #[cfg(feature = "websocket")]
-                            Choice::WebSocket => {
-                                if save != self.radio_choice || self.handler.is_none() {
-                                    self.handler = Some(Box::new(WsHandler::new()));
-                                }
-                            }
-                            Choice::File => {
-                                if save != self.radio_choice || self.handler.is_none() {
-                                    self.handler = Some(Box::new(FileHandler::new()));
-                                }
-                            }
-                        };
-                        ui.vertical(|ui| {
-                            if let Some(handle) = &mut self.handler {
-                                match handle.ui(ui, &mut self.data, ctx.clone()) {
-                                    Ok(true) => {
-                                        self.handler = None;
-                                        for one_panel in self.windows.iter_mut() {
-                                            one_panel.clear();
+                        if pending && (save != self.radio_choice || self.handlers.is_empty()) {
+                            self.handlers.pop();
+                            self.handlers.push(self.new_handler());
+                        }
+                        let mut closed = vec![];
+                        let mut layout = None;
+                        let mut layers = None;
+                        for (index, handle) in self.handlers.iter_mut().enumerate() {
+                            ui.push_id(index, |ui| {
+                                ui.group(|ui| {
+                                    ui.vertical(|ui| {
+                                        // the sources are lost once the interface is closed
+                                        let sources = handle.sources();
+                                        match sources.is_empty() {
+                                            true => ui.strong(format!("Interface {}", index + 1)),
+                                            false => ui.strong(format!("Interface {}: {}", index + 1, sources.join(", "))),
+                                        };
+                                        match handle.ui(ui, &mut self.data, ctx.clone()) {
+                                            Ok(true) => closed.push((index, sources)),
+                                            Ok(false) => {}
+                                            Err(err) => {
+                                                errors.push(err);
+                                            }
                                         }
-                                    }
-                                    Ok(false) => {}
-                                    Err(err) => {
-                                        errors.push(err);
-                                    }
-                                }
-                            }
-                        });
+                                        layout = layout.take().or_else(|| handle.take_layout());
+                                        layers = layers.take().or_else(|| handle.take_layers());
+                                    });
+                                });
+                            });
+                        }
+                        if let Some(layout) = layout {
+                            self.restore_layout(ctx, layout);
+                        }
+                        if let Some(layers) = layers {
+                            self.data.filter_layers(&layers);
+                            self.trame_manager.layers_list = layers;
+                        }
+                        for (index, sources) in closed.into_iter().rev() {
+                            self.handlers.remove(index);
+                            self.remove_sources(&sources);
+                        }
+                        if !pending
+                            && ui
+                                .button("Add interface")
+                                .on_hover_text("Connect another interface")
+                                .clicked()
+                        {
+                            self.handlers.push(self.new_handler());
+                        }
                     });
                     ui.separator();
-                    if let Some(handle) = &mut self.handler {
-                        handle.ui_options(ui);
+                    for (index, handle) in self.handlers.iter_mut().enumerate() {
+                        ui.push_id(index, |ui| handle.ui_options(ui));
                     }
                     if self.interface_available() {
-                        if let Some(handle) = &mut self.handler {
-                            self.trame_manager.show_options(ui);
-                            if self.trame_manager.should_get_more_log {
-                                self.trame_manager.should_get_more_log = false;
+                        self.trame_manager.show_options(ui, &mut self.data);
+                        if self.trame_manager.should_get_more_log {
+                            self.trame_manager.should_get_more_log = false;
+                            for handle in self.handlers.iter_mut() {
+                                if !handle.is_interface_available() {
+                                    continue;
+                                }
                                 if let Err(err) =
                                     handle.get_more_data(self.trame_manager.layers_list.clone(), &mut self.data)
                                 {
@@ -194,21 +326,52 @@ impl FrontEnd {
         Ok(())
     }
 
-    /// Check if the interface is available
-    pub fn interface_available(&self) -> bool {
-        if let Some(handle) = &self.handler {
-            handle.is_interface_available()
-        } else {
-            false
+    /// Create a new handler of the chosen type
+    fn new_handler(&self) -> Box<dyn Handler> {
+        match &self.radio_choice {
+            #[cfg(feature = "websocket")]
+            Choice::WebSocket => Box::new(WsHandler::new()),
+            Choice::File => Box::new(FileHandler::new()),
+            Choice::Replay => Box::new(ReplayHandler::new()),
+            Choice::Session => Box::new(SessionHandler::new()),
+        }
+    }
+
+    /// Remove the traces of a closed interface, everything is cleared when the last one is closed
+    fn remove_sources(&mut self, sources: &[String]) {
+        if self.handlers.iter().any(|handle| handle.is_interface()) {
+            for source in sources {
+                self.data.remove_source(source);
+            }
+            return;
+        }
+        self.data.clear();
+        for one_panel in self.windows.iter_mut() {
+            one_panel.clear();
         }
     }
 
+    /// Check if at least one interface is available
+    pub fn interface_available(&self) -> bool {
+        self.handlers.iter().any(|handle| handle.is_interface_available())
+    }
+
+    /// Check if every interface is fully read
+    fn is_full_read(&self) -> bool {
+        self.handlers
+            .iter()
+            .filter(|handle| handle.is_interface())
+            .all(|handle| handle.is_full_read())
+    }
+
     /// Show the UI
     /// # Errors
     /// Return a vector of TramexError
     pub fn ui(&mut self, ctx: &egui::Context) -> Result<(), Vec<TramexError>> {
         let mut error_to_return = vec![];
-        if let Some(handle) = &mut self.handler {
+        // each interface declares again if it can send requests
+        self.data.remote.reset_connections();
+        for handle in self.handlers.iter_mut() {
             if let Err(errors_vect) = handle.try_recv(&mut self.data) {
                 for one_error in errors_vect {
                     if !matches!(one_error.get_code(), ErrorCode::ParsingLayerNotImplemented) {
@@ -216,6 +379,15 @@ impl FrontEnd {
                     }
                 }
             }
+            if handle.should_get_more_data(ctx) {
+                if let Err(errors_vect) = handle.get_more_data(self.trame_manager.layers_list.clone(), &mut self.data) {
+                    for one_error in errors_vect {
+                        if !matches!(one_error.get_code(), ErrorCode::ParsingLayerNotImplemented) {
+                            error_to_return.push(one_error);
+                        }
+                    }
+                }
+            }
         }
         egui::CentralPanel::default().show(ctx, |ui| {
             if self.interface_available() {
@@ -229,7 +401,7 @@ impl FrontEnd {
                 }
                 // show nothing
             } else {
-                match &self.handler {
+                match self.handlers.last() {
                     Some(handle) => handle.show_available(ui),
                     None => {
                         ui.label("Not connected");
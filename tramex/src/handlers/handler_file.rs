@@ -7,6 +7,7 @@ use tramex_tools::{
     data::Data,
     errors::TramexError,
     interface::{interface_file::file_handler::File, interface_types::InterfaceTrait},
+    session::{SourceInfo, SourceKind},
     tramex_error,
 };
 
@@ -26,11 +27,8 @@ struct Item {
 /// File handler
 pub struct FileHandler {
     #[serde(skip)]
-    /// Picked path
-    pub picked_path: Option<String>,
-    #[serde(skip)]
-    /// File upload
-    pub file_upload: Option<Promise<Result<File, TramexError>>>,
+    /// Files being uploaded
+    pub file_upload: Option<Promise<Result<Vec<File>, TramexError>>>,
     #[serde(skip)]
     /// File list
     file_list: Option<Promise<Result<Vec<Item>, TramexError>>>,
@@ -39,8 +37,12 @@ pub struct FileHandler {
     url_files: String,
 
     #[serde(skip)]
-    /// File
-    file: Option<File>,
+    /// Files opened, their traces are merged by timestamp
+    files: Vec<File>,
+
+    #[serde(skip)]
+    /// Clock offset being dragged: index of the file and offset, the traces are shifted when the drag ends
+    dragged_offset: Option<(usize, i64)>,
 }
 
 impl FileHandler {
@@ -53,11 +55,11 @@ impl FileHandler {
         };
 
         let mut s = Self {
-            picked_path: None,
             file_upload: None,
             file_list: None,
             url_files: url_list,
-            file: None,
+            files: vec![],
+            dragged_offset: None,
         };
         s.get_file_list();
         s
@@ -130,8 +132,8 @@ impl FileHandler {
 
     /// Reset the file handler
     pub fn reset(&mut self) {
-        self.picked_path = None;
         self.file_upload = None;
+        self.files.clear();
     }
 
     /// Clear the file handler
@@ -140,38 +142,22 @@ impl FileHandler {
         self.file_list = None;
     }
 
-    /// Get the result
+    /// Get the files uploaded, None while the upload is not finished
     /// # Errors
-    /// Return an error if the file contains errors
-    pub fn get_result(&mut self) -> Result<Option<File>, TramexError> {
-        let mut should_clean = false;
-        let res = match &self.file_upload {
-            Some(result) => match &result.ready() {
-                Some(ready) => match ready {
-                    Ok(curr_file) => Ok(Some(curr_file.clone())),
-                    Err(e) => {
-                        should_clean = true;
-                        Err(e.to_owned())
-                    }
-                },
-                None => Err(tramex_error!(
-                    "File not ready".to_string(),
-                    tramex_tools::errors::ErrorCode::FileNotReady
-                )),
-            },
-            None => Ok(None),
+    /// Return an error if a file can't be read
+    pub fn get_result(&mut self) -> Result<Option<Vec<File>>, TramexError> {
+        let Some(ready) = self.file_upload.as_ref().and_then(|result| result.ready()) else {
+            return Ok(None);
         };
+        let res = ready.clone();
         log::debug!("Result: {res:?}");
-        if should_clean {
-            log::debug!("Cleaning file upload");
-            self.clear();
-        }
-        res
+        self.file_upload = None;
+        res.map(Some)
     }
 
     /// Load file from URL
     pub fn load_from_url(&mut self, url: String) {
-        self.reset();
+        self.file_upload = None;
         let copied_url = url.clone();
         let call = move |res: Result<ehttp::Response, String>| match res {
             Ok(res) => {
@@ -182,7 +168,7 @@ impl FileHandler {
                             Some(f) => f.to_str().unwrap_or(&url),
                             None => &url,
                         };
-                        Ok(File::new(path.into(), v.to_string()))
+                        Ok(vec![File::new(path.into(), v.to_string())])
                     }
                     Err(e) => Err(tramex_error!(
                         e.to_string(),
@@ -216,66 +202,68 @@ impl FileHandler {
         }
     }
 
-    /// Get the picked path
-    pub fn get_picket_path(&self) -> Option<String> {
-        self.picked_path.clone()
-    }
-
-    /// Load file upload
+    /// Load file upload, several files can be selected
     fn load_file_upload(&mut self) {
-        self.reset();
+        self.file_upload = None;
         #[cfg(target_arch = "wasm32")]
         {
             self.file_upload = Some(Promise::spawn_local(async {
-                let file_selected = rfd::AsyncFileDialog::new().pick_file().await;
-                if let Some(curr_file) = file_selected {
+                let Some(files_selected) = rfd::AsyncFileDialog::new().pick_files().await else {
+                    return Err(tramex_error!(
+                        "Upload: no file Selected".to_string(),
+                        tramex_tools::errors::ErrorCode::FileNotSelected
+                    ));
+                };
+                let mut files = vec![];
+                for curr_file in files_selected {
                     let buf = curr_file.read().await;
                     log::info!("File reading from wasm");
-                    return match std::str::from_utf8(&buf) {
-                        Ok(v) => Ok(File::new(curr_file.file_name().into(), v.to_string())),
-                        Err(e) => Err(tramex_error!(
-                            e.to_string(),
-                            tramex_tools::errors::ErrorCode::FileInvalidEncoding
-                        )),
+                    match std::str::from_utf8(&buf) {
+                        Ok(v) => files.push(File::new(curr_file.file_name().into(), v.to_string())),
+                        Err(e) => {
+                            return Err(tramex_error!(
+                                e.to_string(),
+                                tramex_tools::errors::ErrorCode::FileInvalidEncoding
+                            ));
+                        }
                     };
                 }
-                Err(tramex_error!(
-                    "Upload: no file Selected".to_string(),
-                    tramex_tools::errors::ErrorCode::FileNotSelected
-                ))
+                Ok(files)
             }));
         }
         #[cfg(not(target_arch = "wasm32"))]
         {
             self.file_upload = Some(Promise::spawn_thread("slow", move || {
-                if let Some(path_buf) = rfd::FileDialog::new().pick_file() {
+                let Some(paths) = rfd::FileDialog::new().pick_files() else {
+                    return Err(tramex_error!(
+                        "Upload: no file Selected".to_string(),
+                        tramex_tools::errors::ErrorCode::FileNotSelected
+                    ));
+                };
+                let mut files = vec![];
+                for path_buf in paths {
                     // read file as string
-                    if let Some(path) = path_buf.to_str() {
-                        let path = path.to_string();
-                        let buf = std::fs::read(path.clone());
-                        let buf = match buf {
-                            Ok(v) => v,
-                            Err(e) => {
-                                log::warn!("{e:?}");
-                                return Err(tramex_error!(
-                                    e.to_string(),
-                                    tramex_tools::errors::ErrorCode::FileErrorReadingFile
-                                ));
-                            }
-                        };
-                        return match std::str::from_utf8(&buf) {
-                            Ok(v) => Ok(File::new(path_buf, v.to_string())),
-                            Err(e) => Err(tramex_error!(
+                    let buf = match std::fs::read(&path_buf) {
+                        Ok(v) => v,
+                        Err(e) => {
+                            log::warn!("{e:?}");
+                            return Err(tramex_error!(
+                                e.to_string(),
+                                tramex_tools::errors::ErrorCode::FileErrorReadingFile
+                            ));
+                        }
+                    };
+                    match std::str::from_utf8(&buf) {
+                        Ok(v) => files.push(File::new(path_buf, v.to_string())),
+                        Err(e) => {
+                            return Err(tramex_error!(
                                 e.to_string(),
                                 tramex_tools::errors::ErrorCode::FileInvalidEncoding
-                            )),
-                        };
-                    }
+                            ));
+                        }
+                    };
                 }
-                Err(tramex_error!(
-                    "Upload: no file Selected".to_string(),
-                    tramex_tools::errors::ErrorCode::FileNotSelected
-                ))
+                Ok(files)
             }))
         }
     }
@@ -285,17 +273,15 @@ impl FileHandler {
     /// Return an error if the file contains errors
     pub fn internal_ui(&mut self, ui: &mut egui::Ui) -> Result<(), TramexError> {
         let mut error_to_return = None;
-        ui.add_enabled_ui(self.get_picket_path().is_none(), |ui| {
-            if ui.button("Open file…").clicked() {
+        ui.add_enabled_ui(self.file_upload.is_none(), |ui| {
+            let label = if self.files.is_empty() {
+                "Open files…"
+            } else {
+                "Add files…"
+            };
+            if ui.button(label).on_hover_text("Several files can be selected").clicked() {
                 self.load_file_upload();
             }
-
-            match self.check_file_load() {
-                Ok(_) => {}
-                Err(e) => {
-                    error_to_return = Some(e);
-                }
-            }
             let mut file_path = None;
             if let Some(result) = &self.file_list {
                 if let Some(ready) = result.ready() {
@@ -336,53 +322,78 @@ impl FileHandler {
             if let Some(filepath) = &file_path {
                 self.load_from_url(filepath.to_string());
             }
-
-            if let Some(picked_path) = &self.picked_path {
-                ui.horizontal(|ui| {
-                    ui.label("Picked file:");
-                    ui.monospace(picked_path);
-                });
-            } else if self.file_upload.is_some() {
-                ui.add(egui::Spinner::new());
-            }
-            ui.add_space(12.0);
         });
+        if self.file_upload.is_some() {
+            ui.add(egui::Spinner::new());
+        }
+        if let Err(err) = self.check_file_load() {
+            error_to_return = Some(err);
+        }
+        ui.add_space(12.0);
         if let Some(err) = error_to_return {
             return Err(err);
         };
         Ok(())
     }
 
-    /// Check file load
+    /// Check file load, the files uploaded are added to the files opened
+    ///
+    /// A file with the same name as a file opened gets a numbered source, such as `enb.log #2`.
     /// # Errors
-    /// Return an error if the file contains errors
+    /// Return an error if a file can't be read
     pub fn check_file_load(&mut self) -> Result<(), TramexError> {
-        if self.picked_path.is_none() {
-            let mut error = None;
-            if let Some(result) = &self.file_upload {
-                if let Some(ready) = result.ready() {
-                    match &ready {
-                        Ok(file) => {
-                            let path_filename = file
-                                .file_path
-                                .file_name()
-                                .and_then(|f| f.to_str())
-                                .map(|f| f.to_string())
-                                .unwrap_or_default();
-                            self.picked_path = Some(path_filename);
-                        }
-                        Err(e) => {
-                            error = Some(e.to_owned());
-                        }
-                    }
+        let Some(files) = self.get_result()? else {
+            return Ok(());
+        };
+        for mut file in files {
+            file.make_source_unique(&self.sources());
+            self.files.push(file);
+        }
+        Ok(())
+    }
+
+    /// Display the files opened with their clock offset
+    /// Return true if the last file was closed
+    fn ui_files(&mut self, ui: &mut egui::Ui, data: &mut Data) -> bool {
+        let mut closed = None;
+        let mut dragged_offset = self.dragged_offset;
+        for (index, file) in self.files.iter_mut().enumerate() {
+            ui.horizontal(|ui| {
+                ui.monospace(file.source().unwrap_or_default());
+                let mut clock_offset_ms = match dragged_offset {
+                    Some((dragged, offset)) if dragged == index => offset,
+                    _ => file.clock_offset_ms,
+                };
+                let response = ui
+                    .add(
+                        egui::DragValue::new(&mut clock_offset_ms)
+                            .speed(1.0)
+                            .suffix(" ms")
+                            .update_while_editing(false),
+                    )
+                    .on_hover_text("Clock offset added to the timestamps of the file");
+                // the traces are ordered again only once the drag ends, not on each step of the drag
+                if response.dragged() {
+                    dragged_offset = Some((index, clock_offset_ms));
+                } else if response.changed() || response.drag_stopped() {
+                    dragged_offset = None;
+                    file.set_clock_offset(clock_offset_ms, data);
                 }
+                if ui.button("✖").on_hover_text("Close this file").clicked() {
+                    closed = Some(index);
+                }
+            });
+        }
+        self.dragged_offset = dragged_offset;
+        if let Some(index) = closed {
+            self.dragged_offset = None;
+            let file = self.files.remove(index);
+            if let Some(source) = file.source() {
+                data.remove_source(&source);
             }
-            if let Some(e) = error {
-                self.clear();
-                return Err(e);
-            }
+            return self.files.is_empty();
         }
-        Ok(())
+        false
     }
 }
 
@@ -399,30 +410,23 @@ impl Handler for FileHandler {
 
     fn ui(&mut self, ui: &mut egui::Ui, data: &mut Data, _new_ctx: egui::Context) -> Result<bool, TramexError> {
         self.internal_ui(ui)?; // may return error
-        if self.picked_path.is_some() && self.file.is_none() {
-            match self.get_result() {
-                Ok(Some(curr_file)) => {
-                    self.file = curr_file.into();
-                    self.clear();
-                }
-                Ok(None) => {}
-                Err(err) => {
-                    log::error!("Error in get_result() {err:?}");
-                    self.clear();
-                    return Err(err);
-                }
-            };
-        };
-        if self.get_picket_path().is_some() && ui.button("Close").on_hover_text("Close file").clicked() {
+        if self.files.is_empty() {
+            return Ok(false);
+        }
+        ui.label("Files and clock offsets:");
+        if self.ui_files(ui, data) {
+            self.reset();
+            return Ok(true);
+        }
+        if ui.button("Close").on_hover_text("Close the files").clicked() {
             self.reset();
-            data.clear();
             return Ok(true);
         }
         Ok(false)
     }
 
     fn close(&mut self) -> Result<(), TramexError> {
-        if let Some(file) = &mut self.file {
+        for file in self.files.iter_mut() {
             file.close()?;
         }
         Ok(())
@@ -433,8 +437,14 @@ impl Handler for FileHandler {
         layer_list: tramex_tools::interface::layer::Layers,
         data: &mut tramex_tools::data::Data,
     ) -> Result<(), Vec<TramexError>> {
-        if let Some(file) = &mut self.file {
-            return file.get_more_data(layer_list, data);
+        let mut errors = vec![];
+        for file in self.files.iter_mut() {
+            if let Err(mut file_errors) = file.get_more_data(layer_list.clone(), data) {
+                errors.append(&mut file_errors);
+            }
+        }
+        if !errors.is_empty() {
+            return Err(errors);
         }
         Ok(())
     }
@@ -444,28 +454,42 @@ impl Handler for FileHandler {
     }
 
     fn show_available(&self, ui: &mut egui::Ui) {
-        if self.file.is_some() {
-            ui.label("File available");
-        } else {
+        if self.files.is_empty() {
             ui.label("File not available");
+        } else {
+            ui.label("File available");
         }
     }
 
     fn is_full_read(&self) -> bool {
-        if let Some(file) = &self.file {
-            return file.full_read;
-        }
-        false
+        !self.files.is_empty() && self.files.iter().all(|file| file.full_read)
     }
 
     fn is_interface(&self) -> bool {
-        self.file.is_some()
+        !self.files.is_empty()
     }
 
-    fn is_interface_available(&self) -> bool {
-        if let Some(file) = &self.file {
-            return file.available;
-        }
+    fn should_get_more_data(&mut self, _ctx: &egui::Context) -> bool {
         false
     }
+
+    fn is_interface_available(&self) -> bool {
+        self.files.iter().any(|file| file.available)
+    }
+
+    fn sources(&self) -> Vec<String> {
+        self.files.iter().filter_map(File::source).collect()
+    }
+
+    fn source_infos(&self) -> Vec<SourceInfo> {
+        self.files
+            .iter()
+            .filter_map(|file| {
+                let info = SourceInfo::new(&file.source()?, SourceKind::File)
+                    .with_location(&file.file_path.to_string_lossy())
+                    .with_clock_offset(file.clock_offset_ms);
+                Some(info)
+            })
+            .collect()
+    }
 }
@@ -0,0 +1,216 @@
+//! Replay handler panel
+use eframe::egui;
+use poll_promise::Promise;
+use tramex_tools::{
+    data::Data,
+    errors::TramexError,
+    interface::{
+        interface_types::InterfaceTrait,
+        layer::Layers,
+        websocket::{
+            reconnect::now_ms,
+            recording::Recording,
+            replay::{Replay, ReplaySpeed},
+        },
+    },
+    session::{SourceInfo, SourceKind},
+    tramex_error,
+};
+
+use super::Handler;
+
+/// Speeds offered in the speed menu
+const SPEEDS: [f64; 5] = [0.5, 1.0, 2.0, 5.0, 10.0];
+
+/// Replay handler
+#[derive(Default)]
+pub struct ReplayHandler {
+    /// Recording being loaded
+    recording_upload: Option<Promise<Result<Recording, TramexError>>>,
+
+    /// Replay of the recording loaded
+    replay: Option<Replay>,
+}
+
+impl ReplayHandler {
+    /// Create a new replay handler
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Load a recording with the file dialog
+    fn load_recording(&mut self) {
+        #[cfg(target_arch = "wasm32")]
+        {
+            self.recording_upload = Some(Promise::spawn_local(async {
+                let Some(curr_file) = rfd::AsyncFileDialog::new().pick_file().await else {
+                    return Err(tramex_error!(
+                        "Upload: no file Selected".to_string(),
+                        tramex_tools::errors::ErrorCode::FileNotSelected
+                    ));
+                };
+                let buf = curr_file.read().await;
+                match std::str::from_utf8(&buf) {
+                    Ok(v) => Recording::parse(v),
+                    Err(e) => Err(tramex_error!(
+                        e.to_string(),
+                        tramex_tools::errors::ErrorCode::FileInvalidEncoding
+                    )),
+                }
+            }));
+        }
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            self.recording_upload = Some(Promise::spawn_thread("slow", move || {
+                let Some(path_buf) = rfd::FileDialog::new().pick_file() else {
+                    return Err(tramex_error!(
+                        "Upload: no file Selected".to_string(),
+                        tramex_tools::errors::ErrorCode::FileNotSelected
+                    ));
+                };
+                match std::fs::read_to_string(&path_buf) {
+                    Ok(v) => Recording::parse(&v),
+                    Err(e) => Err(tramex_error!(
+                        e.to_string(),
+                        tramex_tools::errors::ErrorCode::FileErrorReadingFile
+                    )),
+                }
+            }));
+        }
+    }
+
+    /// Start the replay once the recording is loaded
+    /// # Errors
+    /// Return an error if the recording can't be read
+    fn check_recording_load(&mut self) -> Result<(), TramexError> {
+        let Some(ready) = self.recording_upload.as_ref().and_then(|result| result.ready()) else {
+            return Ok(());
+        };
+        let res = ready.clone();
+        self.recording_upload = None;
+        self.replay = Some(Replay::new(res?, ReplaySpeed::Speed(1.0)));
+        Ok(())
+    }
+
+    /// Display the controls of the replay
+    fn ui_controls(replay: &mut Replay, ui: &mut egui::Ui) {
+        ui.label(format!("Frames played: {} / {}", replay.position(), replay.len()));
+        ui.add(egui::ProgressBar::new(match replay.is_empty() {
+            true => 1.0,
+            false => replay.position() as f32 / replay.len() as f32,
+        }));
+        ui.horizontal(|ui| {
+            let now = now_ms();
+            if replay.is_paused() {
+                if ui.button("Play").clicked() {
+                    replay.play(now);
+                }
+            } else if ui.button("Pause").clicked() {
+                replay.pause(now);
+            }
+            let speed = replay.speed();
+            let selected_text = match speed {
+                ReplaySpeed::Instant => "Instant".to_owned(),
+                ReplaySpeed::Speed(factor) => format!("x{factor}"),
+            };
+            egui::ComboBox::from_label("Speed")
+                .selected_text(selected_text)
+                .show_ui(ui, |ui| {
+                    if ui.selectable_label(speed == ReplaySpeed::Instant, "Instant").clicked() {
+                        replay.set_speed(ReplaySpeed::Instant, now);
+                    }
+                    for factor in SPEEDS {
+                        let one_speed = ReplaySpeed::Speed(factor);
+                        if ui.selectable_label(speed == one_speed, format!("x{factor}")).clicked() {
+                            replay.set_speed(one_speed, now);
+                        }
+                    }
+                });
+        });
+    }
+}
+
+impl Handler for ReplayHandler {
+    fn ui_options(&mut self, _ui: &mut egui::Ui) {}
+
+    fn ui(&mut self, ui: &mut egui::Ui, _data: &mut Data, _new_ctx: egui::Context) -> Result<bool, TramexError> {
+        let Some(replay) = &mut self.replay else {
+            ui.add_enabled_ui(self.recording_upload.is_none(), |ui| {
+                if ui.button("Open recording…").clicked() {
+                    self.load_recording();
+                }
+            });
+            if self.recording_upload.is_some() {
+                ui.spinner();
+            }
+            self.check_recording_load()?;
+            return Ok(false);
+        };
+        ui.label(format!("Recording of {}", replay.recording.header.name));
+        Self::ui_controls(replay, ui);
+        if ui.button("Close").on_hover_text("Close the recording").clicked() {
+            self.replay = None;
+            return Ok(true);
+        }
+        Ok(false)
+    }
+
+    fn close(&mut self) -> Result<(), TramexError> {
+        if let Some(replay) = &mut self.replay {
+            replay.close()?;
+        }
+        Ok(())
+    }
+
+    fn get_more_data(&mut self, layer_list: Layers, data: &mut Data) -> Result<(), Vec<TramexError>> {
+        if let Some(replay) = &mut self.replay {
+            return replay.get_more_data(layer_list, data);
+        }
+        Ok(())
+    }
+
+    fn should_get_more_data(&mut self, ctx: &egui::Context) -> bool {
+        if let Some(delay) = self.replay.as_ref().and_then(|replay| replay.next_frame_in_ms(now_ms())) {
+            ctx.request_repaint_after(std::time::Duration::from_millis(delay.max(10)));
+        }
+        false
+    }
+
+    fn try_recv(&mut self, data: &mut Data) -> Result<(), Vec<TramexError>> {
+        if let Some(replay) = &mut self.replay {
+            return replay.poll(now_ms(), data);
+        }
+        Ok(())
+    }
+
+    fn show_available(&self, ui: &mut egui::Ui) {
+        if self.replay.is_some() {
+            ui.label("Recording available");
+        } else {
+            ui.label("Recording not available");
+        }
+    }
+
+    fn is_full_read(&self) -> bool {
+        self.replay.as_ref().is_some_and(Replay::is_finished)
+    }
+
+    fn is_interface(&self) -> bool {
+        self.replay.is_some()
+    }
+
+    fn is_interface_available(&self) -> bool {
+        self.replay.is_some()
+    }
+
+    fn sources(&self) -> Vec<String> {
+        self.replay.iter().filter_map(Replay::source).collect()
+    }
+
+    fn source_infos(&self) -> Vec<SourceInfo> {
+        self.sources()
+            .iter()
+            .map(|name| SourceInfo::new(name, SourceKind::Replay))
+            .collect()
+    }
+}
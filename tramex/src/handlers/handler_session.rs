@@ -0,0 +1,200 @@
+//! Session handler panel
+use eframe::egui;
+use poll_promise::Promise;
+use tramex_tools::{
+    data::Data,
+    errors::TramexError,
+    interface::layer::Layers,
+    session::{Session, SourceInfo, SourceKind},
+    tramex_error,
+};
+
+use super::Handler;
+
+/// Session handler
+#[derive(Default)]
+pub struct SessionHandler {
+    /// Session being loaded
+    session_upload: Option<Promise<Result<Session, TramexError>>>,
+
+    /// Session loaded, its traces are moved to the data
+    session: Option<Session>,
+
+    /// Layout of the session, not restored yet
+    layout: Option<serde_json::Value>,
+
+    /// Log configuration of the layers of the session, not restored yet
+    layers: Option<Layers>,
+
+    /// Sources of the traces restored, prefixed to be removed without the traces of the live sources
+    restored_sources: Vec<String>,
+
+    /// Number of traces of the session
+    nb_traces: usize,
+}
+
+impl SessionHandler {
+    /// Create a new session handler
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Load a session with the file dialog
+    fn load_session(&mut self) {
+        #[cfg(target_arch = "wasm32")]
+        {
+            self.session_upload = Some(Promise::spawn_local(async {
+                let Some(curr_file) = rfd::AsyncFileDialog::new().pick_file().await else {
+                    return Err(tramex_error!(
+                        "Upload: no file Selected".to_string(),
+                        tramex_tools::errors::ErrorCode::FileNotSelected
+                    ));
+                };
+                let buf = curr_file.read().await;
+                match std::str::from_utf8(&buf) {
+                    Ok(v) => Session::parse(v),
+                    Err(e) => Err(tramex_error!(
+                        e.to_string(),
+                        tramex_tools::errors::ErrorCode::FileInvalidEncoding
+                    )),
+                }
+            }));
+        }
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            self.session_upload = Some(Promise::spawn_thread("slow", move || {
+                let Some(path_buf) = rfd::FileDialog::new()
+                    .add_filter("Tramex session", &[tramex_tools::session::SESSION_EXTENSION])
+                    .pick_file()
+                else {
+                    return Err(tramex_error!(
+                        "Upload: no file Selected".to_string(),
+                        tramex_tools::errors::ErrorCode::FileNotSelected
+                    ));
+                };
+                match std::fs::read_to_string(&path_buf) {
+                    Ok(v) => Session::parse(&v),
+                    Err(e) => Err(tramex_error!(
+                        e.to_string(),
+                        tramex_tools::errors::ErrorCode::FileErrorReadingFile
+                    )),
+                }
+            }));
+        }
+    }
+
+    /// Move the traces of the session to the data once it is loaded
+    /// # Errors
+    /// Return an error if the session can't be read
+    fn check_session_load(&mut self, data: &mut Data) -> Result<(), TramexError> {
+        let Some(ready) = self.session_upload.as_ref().and_then(|result| result.ready()) else {
+            return Ok(());
+        };
+        let res = ready.clone();
+        self.session_upload = None;
+        let mut session = res?;
+        self.nb_traces = session.events.len() + session.hidden_events.len();
+        // the layers are restored with the current trace, when the session is the first interface
+        let was_empty = data.events.is_empty() && data.hidden_events.is_empty();
+        self.restored_sources = session.restore(data);
+        self.layout = Some(std::mem::take(&mut session.layout));
+        self.layers = session.layers.take().filter(|_| was_empty);
+        self.session = Some(session);
+        Ok(())
+    }
+}
+
+/// Display the name of a kind of source
+fn kind_name(kind: SourceKind) -> &'static str {
+    match kind {
+        SourceKind::File => "file",
+        SourceKind::WebSocket => "server",
+        SourceKind::Replay => "recording",
+    }
+}
+
+impl Handler for SessionHandler {
+    fn ui_options(&mut self, _ui: &mut egui::Ui) {}
+
+    fn ui(&mut self, ui: &mut egui::Ui, data: &mut Data, _new_ctx: egui::Context) -> Result<bool, TramexError> {
+        let Some(session) = &self.session else {
+            ui.add_enabled_ui(self.session_upload.is_none(), |ui| {
+                if ui.button("Open session…").clicked() {
+                    self.load_session();
+                }
+            });
+            if self.session_upload.is_some() {
+                ui.spinner();
+            }
+            self.check_session_load(data)?;
+            return Ok(false);
+        };
+        ui.label(format!("Session of {} traces", self.nb_traces));
+        for source in &session.sources {
+            let label = ui.label(format!("{} ({})", source.name, kind_name(source.kind)));
+            if let Some(location) = &source.location {
+                label.on_hover_text(location);
+            }
+        }
+        if ui.button("Close").on_hover_text("Close the session").clicked() {
+            self.session = None;
+            return Ok(true);
+        }
+        Ok(false)
+    }
+
+    fn close(&mut self) -> Result<(), TramexError> {
+        Ok(())
+    }
+
+    fn get_more_data(&mut self, _layer_list: Layers, _data: &mut Data) -> Result<(), Vec<TramexError>> {
+        Ok(())
+    }
+
+    fn should_get_more_data(&mut self, _ctx: &egui::Context) -> bool {
+        false
+    }
+
+    fn try_recv(&mut self, _data: &mut Data) -> Result<(), Vec<TramexError>> {
+        Ok(())
+    }
+
+    fn show_available(&self, ui: &mut egui::Ui) {
+        if self.session.is_some() {
+            ui.label("Session available");
+        } else {
+            ui.label("Session not available");
+        }
+    }
+
+    fn is_full_read(&self) -> bool {
+        true
+    }
+
+    fn is_interface(&self) -> bool {
+        self.session.is_some()
+    }
+
+    fn is_interface_available(&self) -> bool {
+        self.session.is_some()
+    }
+
+    fn sources(&self) -> Vec<String> {
+        match self.session {
+            Some(_) => self.restored_sources.clone(),
+            None => vec![],
+        }
+    }
+
+    fn source_infos(&self) -> Vec<SourceInfo> {
+        self.session.iter().flat_map(Session::restored_source_infos).collect()
+    }
+
+    fn take_layout(&mut self) -> Option<serde_json::Value> {
+        self.layout.take()
+    }
+
+    fn take_layers(&mut self) -> Option<Layers> {
+        self.layers.take()
+    }
+}
@@ -3,11 +3,23 @@ use eframe::egui;
 use tramex_tools::{
     data::Data,
     errors::{ErrorCode, TramexError},
-    interface::{interface_types::InterfaceTrait, layer::Layers, websocket::ws_connection::WsConnection},
+    interface::{
+        interface_types::InterfaceTrait,
+        layer::Layers,
+        log_get::{LogGetOptions, parse_range_bound},
+        websocket::{
+            reconnect::{Backoff, now_ms},
+            ws_connection::WsConnection,
+        },
+    },
+    session::{SourceInfo, SourceKind},
     tramex_error,
 };
 
 use super::Handler;
+use crate::utils::format_timestamp;
+#[cfg(not(target_arch = "wasm32"))]
+use tramex_tools::interface::websocket::recording::Recorder;
 
 /// Ws handler
 pub struct WsHandler {
@@ -16,6 +28,27 @@ pub struct WsHandler {
 
     /// WsConnection
     inner: Option<WsConnection>,
+
+    /// Password of the remote API (empty if not protected)
+    pub password: String,
+
+    /// Reconnect automatically when the connection is lost
+    pub auto_reconnect: bool,
+
+    /// Delay between the reconnection attempts
+    backoff: Backoff,
+
+    /// Context to wake up on new message
+    ctx: Option<egui::Context>,
+
+    /// Options of the log_get requests being edited, applied with the Apply button
+    log_options: LogGetOptions,
+
+    /// Start of the range of logs asked (date and time, or time of today), empty for the live logs
+    range_start: String,
+
+    /// End of the range of logs asked (date and time, or time of today), empty for no end
+    range_end: String,
 }
 
 impl Default for WsHandler {
@@ -30,6 +63,13 @@ impl WsHandler {
         Self {
             url: "ws://127.0.0.1:9001".to_owned(),
             inner: None,
+            password: String::new(),
+            auto_reconnect: true,
+            backoff: Backoff::default(),
+            ctx: None,
+            log_options: LogGetOptions::default(),
+            range_start: String::new(),
+            range_end: String::new(),
         }
     }
 
@@ -39,7 +79,12 @@ impl WsHandler {
     pub fn connect(&mut self, wakeup: impl Fn() + Send + Sync + 'static) -> Result<(), TramexError> {
         match WsConnection::connect(&self.url, wakeup) {
             Ok((ws_sender, ws_receiver)) => {
-                self.inner = Some(WsConnection::new(ws_sender, ws_receiver));
+                let mut interface_ws = WsConnection::new(ws_sender, ws_receiver);
+                if !self.password.is_empty() {
+                    interface_ws.password = Some(self.password.clone());
+                }
+                interface_ws.url = self.url.clone();
+                self.inner = Some(interface_ws);
                 Ok(())
             }
             Err(error) => {
@@ -49,6 +94,52 @@ impl WsHandler {
         }
     }
 
+    /// Connect again to the websocket, keeping the logs already received
+    fn reconnect(&mut self) {
+        let Some(interface_ws) = &mut self.inner else {
+            return;
+        };
+        log::info!("Reconnecting to {:?} (attempt {})", &self.url, self.backoff.attempt());
+        let ctx = self.ctx.clone();
+        let wakeup_fn = move || {
+            if let Some(ctx) = &ctx {
+                ctx.request_repaint();
+            }
+        };
+        match WsConnection::connect(&self.url, wakeup_fn) {
+            Ok((ws_sender, ws_receiver)) => interface_ws.reconnect(ws_sender, ws_receiver),
+            Err(error) => log::warn!("Failed to reconnect to {:?}: {}", &self.url, error),
+        }
+    }
+
+    /// Schedule or start a reconnection if the connection is lost
+    /// Return true if a reconnection was already in progress
+    fn handle_reconnection(&mut self) -> bool {
+        let Some(interface_ws) = &self.inner else {
+            return false;
+        };
+        if interface_ws.auth_failed {
+            // reconnecting with the same password would fail again
+            return false;
+        }
+        if interface_ws.available {
+            self.backoff.reset();
+            return false;
+        }
+        let reconnecting = self.backoff.attempt() > 0;
+        if interface_ws.connecting {
+            return reconnecting;
+        }
+        let now = now_ms();
+        let delay = self.backoff.schedule(now);
+        if self.backoff.take_due(now) {
+            self.reconnect();
+        } else if let Some(ctx) = &self.ctx {
+            ctx.request_repaint_after(std::time::Duration::from_millis(delay));
+        }
+        reconnecting
+    }
+
     /// Display the url
     pub fn display_url(&mut self, ui: &mut egui::Ui, enabled: bool) -> bool {
         let mut lost_focus = false;
@@ -59,6 +150,110 @@ impl WsHandler {
         lost_focus
     }
 
+    /// Display the password of the remote API
+    pub fn display_password(&mut self, ui: &mut egui::Ui) -> bool {
+        ui.label("Password (optional):");
+        ui.add(egui::TextEdit::singleline(&mut self.password).password(true))
+            .lost_focus()
+    }
+
+    /// Display the recording controls
+    /// # Errors
+    /// Return an error if the recording can't be started or stopped
+    #[cfg(not(target_arch = "wasm32"))]
+    fn ui_recording(interface_ws: &mut WsConnection, ui: &mut egui::Ui) -> Result<(), TramexError> {
+        if let Some(nb_frames) = interface_ws.nb_recorded_frames() {
+            ui.label(format!("Recording: {nb_frames} frames"));
+            if ui.button("Stop recording").clicked() {
+                interface_ws.stop_recording()?;
+            }
+            return Ok(());
+        }
+        if ui
+            .button("Record…")
+            .on_hover_text("Record the logs received to replay them later")
+            .clicked()
+        {
+            let file_name = format!("{}.tramex-rec", interface_ws.name.to_lowercase());
+            if let Some(path) = rfd::FileDialog::new().set_file_name(file_name).save_file() {
+                let recorder = Recorder::create(&path, &interface_ws.name, now_ms())?;
+                interface_ws.start_recording(recorder);
+            }
+        }
+        Ok(())
+    }
+
+    /// Display an optional filter: a checkbox to enable it and its value
+    fn optional_value(ui: &mut egui::Ui, label: &str, value: &mut Option<u64>) {
+        ui.horizontal(|ui| {
+            let mut enabled = value.is_some();
+            if ui.checkbox(&mut enabled, label).changed() {
+                *value = enabled.then_some(0);
+            }
+            if let Some(value) = value {
+                ui.add(egui::DragValue::new(value).speed(1.0));
+            }
+        });
+    }
+
+    /// Display a bound of the range, red if it can't be read
+    fn time_field(ui: &mut egui::Ui, label: &str, text: &mut String) {
+        ui.horizontal(|ui| {
+            ui.label(label);
+            let valid = text.trim().is_empty() || Self::range_timestamp(text).is_some();
+            let mut edit = egui::TextEdit::singleline(text)
+                .hint_text("[YYYY-MM-DD] HH:MM:SS.mmm")
+                .desired_width(180.0);
+            if !valid {
+                edit = edit.text_color(egui::Color32::RED);
+            }
+            ui.add(edit);
+        });
+    }
+
+    /// Timestamp (milliseconds) of a bound of the range, a time without date is today, None if empty or invalid
+    fn range_timestamp(text: &str) -> Option<u64> {
+        parse_range_bound(text, now_ms())
+    }
+
+    /// Display the advanced options of the log_get requests
+    fn ui_log_options(&mut self, ui: &mut egui::Ui) {
+        ui.collapsing("Advanced log_get", |ui| {
+            let options = &mut self.log_options;
+            ui.horizontal(|ui| {
+                ui.label("Timeout: ");
+                ui.add(egui::DragValue::new(&mut options.timeout).range(0..=60).suffix(" s"));
+                ui.label("Min logs: ");
+                ui.add(egui::DragValue::new(&mut options.min).range(1..=4096));
+            });
+            ui.checkbox(&mut options.headers, "Headers");
+            ui.checkbox(&mut options.short, "Short (first line only)");
+            ui.checkbox(&mut options.allow_empty, "Allow empty responses");
+            ui.label("Range (UTC, today if no date):");
+            Self::time_field(ui, "From", &mut self.range_start);
+            Self::time_field(ui, "To", &mut self.range_end);
+            ui.label("Filters:");
+            Self::optional_value(ui, "UE ID", &mut options.ue_id);
+            Self::optional_value(ui, "RNTI", &mut options.rnti);
+            Self::optional_value(ui, "Cell ID", &mut options.cell_id);
+            let Some(interface_ws) = &mut self.inner else {
+                return;
+            };
+            if let Some(end) = interface_ws.log_options().end_timestamp {
+                ui.label(format!("Logs until {}", format_timestamp(end)));
+            }
+            if ui
+                .button("Apply")
+                .on_hover_text("Use these options for the next requests, from the start of the range if given")
+                .clicked()
+            {
+                options.start_timestamp = Self::range_timestamp(&self.range_start);
+                options.end_timestamp = Self::range_timestamp(&self.range_end);
+                interface_ws.set_log_options(options.clone());
+            }
+        });
+    }
+
     /// Close the websocket
     /// # Errors
     /// Return an error if the closing failed
@@ -82,6 +277,29 @@ impl Handler for WsHandler {
                         .range(64.0..=4096.0),
                 );
             });
+            ui.checkbox(&mut self.auto_reconnect, "Reconnect automatically");
+            ui.horizontal(|ui| {
+                ui.checkbox(&mut interface_ws.streaming, "Live streaming");
+                ui.add_enabled_ui(interface_ws.streaming, |ui| {
+                    let text = if interface_ws.stream_paused { "Resume" } else { "Pause" };
+                    if ui.button(text).clicked() {
+                        interface_ws.stream_paused = !interface_ws.stream_paused;
+                    }
+                });
+            });
+            ui.add_enabled_ui(interface_ws.streaming, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label("Min delay between requests: ");
+                    ui.add(
+                        egui::DragValue::new(&mut interface_ws.stream_interval_ms)
+                            .speed(10.0)
+                            .range(0.0..=60000.0)
+                            .suffix(" ms"),
+                    );
+                });
+                ui.label(format!("Pending requests: {}", interface_ws.nb_outstanding()));
+            });
+            self.ui_log_options(ui);
         }
     }
 
@@ -89,11 +307,29 @@ impl Handler for WsHandler {
         if self.inner.is_some() {
             self.display_url(ui, false);
             if let Some(interface_ws) = &mut self.inner {
-                if interface_ws.connecting {
+                if interface_ws.auth_failed {
+                    ui.label("Authentication failed, close and check the password");
+                    if ui.button("Close").clicked() {
+                        self.close_ws()?;
+                        return Ok(true);
+                    }
+                } else if let Some(time_left) = self.backoff.time_left_ms(now_ms()) {
+                    ui.label(format!(
+                        "Connection lost, reconnecting in {:.1} s (attempt {})",
+                        time_left as f64 / 1000.0,
+                        self.backoff.attempt()
+                    ));
+                    if ui.button("Close").clicked() {
+                        self.close_ws()?;
+                        return Ok(true);
+                    }
+                } else if interface_ws.connecting {
                     ui.label("Connecting...");
                     ui.spinner();
                 } else {
                     ui.label(format!("Name: {}", &interface_ws.name));
+                    #[cfg(not(target_arch = "wasm32"))]
+                    Self::ui_recording(interface_ws, ui)?;
                     if ui.button("Close").clicked() {
                         match self.close_ws() {
                             Ok(_) => {}
@@ -105,9 +341,13 @@ impl Handler for WsHandler {
             }
             Ok(false)
         } else {
-            if (self.display_url(ui, true) && ui.input(|i| i.key_pressed(egui::Key::Enter)))
+            let url_lost_focus = self.display_url(ui, true);
+            let password_lost_focus = self.display_password(ui);
+            if ((url_lost_focus || password_lost_focus) && ui.input(|i| i.key_pressed(egui::Key::Enter)))
                 || ui.button("Connect").clicked()
             {
+                self.ctx = Some(new_ctx.clone());
+                self.backoff.reset();
                 let wakeup_fn = move || new_ctx.request_repaint(); // wake up UI thread on new message
                 match self.connect(wakeup_fn) {
                     Ok(_) => {}
@@ -124,10 +364,41 @@ impl Handler for WsHandler {
     }
 
     fn try_recv(&mut self, data: &mut Data) -> Result<(), Vec<TramexError>> {
+        let Some(interface_ws) = &mut self.inner else {
+            return Ok(());
+        };
+        let result = interface_ws.try_recv(data);
+        if !self.auto_reconnect {
+            return result;
+        }
+        if self.handle_reconnection() {
+            // the loss of connection was already reported, the failed attempts are only logged
+            if let Err(errors) = result {
+                let (lost, others): (Vec<TramexError>, Vec<TramexError>) = errors
+                    .into_iter()
+                    .partition(|err| matches!(err.get_code(), ErrorCode::WebSocketClosed | ErrorCode::WebSocketError));
+                for err in lost {
+                    log::warn!("Reconnection failed: {}", err.get_msg());
+                }
+                if !others.is_empty() {
+                    return Err(others);
+                }
+            }
+            return Ok(());
+        }
+        result
+    }
+
+    fn should_get_more_data(&mut self, ctx: &egui::Context) -> bool {
         if let Some(interface_ws) = &mut self.inner {
-            return interface_ws.try_recv(data);
+            if interface_ws.should_stream() {
+                return true;
+            }
+            if let Some(delay) = interface_ws.next_stream_request_in_ms() {
+                ctx.request_repaint_after(std::time::Duration::from_millis(delay.max(10)));
+            }
         }
-        Ok(())
+        false
     }
 
     fn get_more_data(&mut self, layer_list: Layers, data: &mut Data) -> Result<(), Vec<TramexError>> {
@@ -166,4 +437,15 @@ impl Handler for WsHandler {
         }
         false
     }
+
+    fn sources(&self) -> Vec<String> {
+        self.inner.iter().filter_map(WsConnection::source).collect()
+    }
+
+    fn source_infos(&self) -> Vec<SourceInfo> {
+        self.sources()
+            .iter()
+            .map(|name| SourceInfo::new(name, SourceKind::WebSocket).with_location(&self.url))
+            .collect()
+    }
 }
@@ -1,8 +1,10 @@
 //! Module: handlers
 
-use tramex_tools::{data::Data, errors::TramexError, interface::layer::Layers};
+use tramex_tools::{data::Data, errors::TramexError, interface::layer::Layers, session::SourceInfo};
 
 pub mod handler_file;
+pub mod handler_replay;
+pub mod handler_session;
 #[cfg(feature = "websocket")]
 pub mod handler_ws;
 
@@ -22,6 +24,10 @@ pub trait Handler {
     /// Return an error if the interface is not set
     fn get_more_data(&mut self, layer_list: Layers, data: &mut Data) -> Result<(), Vec<TramexError>>;
 
+    /// Check if more data should be asked without user action (streaming mode)
+    /// The handler can schedule a repaint on `ctx` to be polled again later
+    fn should_get_more_data(&mut self, ctx: &egui::Context) -> bool;
+
     /// Try to receive data
     /// # Errors
     /// Return an error if the interface is not set
@@ -43,4 +49,20 @@ pub trait Handler {
 
     /// Check if the interface is available
     fn is_interface_available(&self) -> bool;
+
+    /// Sources of the traces of the interface (server or file names), empty until they are known
+    fn sources(&self) -> Vec<String>;
+
+    /// Information of the sources, saved with a session
+    fn source_infos(&self) -> Vec<SourceInfo>;
+
+    /// Layout of the panels to restore, returned once
+    fn take_layout(&mut self) -> Option<serde_json::Value> {
+        None
+    }
+
+    /// Log configuration of the layers to restore, returned once
+    fn take_layers(&mut self) -> Option<Layers> {
+        None
+    }
 }
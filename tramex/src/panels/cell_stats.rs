@@ -0,0 +1,259 @@
+//! Cell statistics dashboard panel
+use std::collections::BTreeMap;
+
+use crate::format_bitrate;
+use eframe::egui;
+use egui_plot::{Legend, Line, Plot};
+use tramex_tools::{
+    data::Data,
+    errors::TramexError,
+    interface::{
+        remote_api::Request,
+        stats::{CellMetric, StatsHistory, StatsResponse},
+        websocket::reconnect::now_ms,
+    },
+};
+
+/// Default delay between two stats (milliseconds)
+const DEFAULT_REFRESH_MS: u64 = 1000;
+
+/// Height of one plot
+const PLOT_HEIGHT: f32 = 120.0;
+
+/// Dashboard of the cell statistics
+pub struct CellStats {
+    /// History of the statistics of each source
+    histories: BTreeMap<String, StatsHistory>,
+
+    /// Message ID of the latest stats response of each source added to `histories`
+    cached_message_ids: BTreeMap<String, u64>,
+
+    /// Source of the statistics displayed, None for the first one
+    selected_source: Option<String>,
+
+    /// Time of the latest stats (milliseconds)
+    last_request_ms: i64,
+
+    /// Delay between two stats (milliseconds)
+    refresh_ms: u64,
+
+    /// Refresh the statistics periodically
+    auto_refresh: bool,
+}
+
+impl Default for CellStats {
+    fn default() -> Self {
+        Self {
+            histories: BTreeMap::new(),
+            cached_message_ids: BTreeMap::new(),
+            selected_source: None,
+            last_request_ms: 0,
+            refresh_ms: DEFAULT_REFRESH_MS,
+            auto_refresh: true,
+        }
+    }
+}
+
+impl CellStats {
+    /// Create a new CellStats
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Send a stats to each source if the refresh delay is elapsed
+    fn poll(&mut self, ctx: &egui::Context, data: &mut Data) {
+        if !self.auto_refresh || !data.remote.is_connected() {
+            return;
+        }
+        let now = now_ms();
+        let elapsed = (now - self.last_request_ms).max(0) as u64;
+        if elapsed >= self.refresh_ms {
+            let sources: Vec<String> = data.remote.sources().cloned().collect();
+            for source in sources {
+                if !data.remote.is_pending_to("stats", &source, now)
+                    && data.remote.request(Request::new("stats").to(&source)).is_some()
+                {
+                    self.last_request_ms = now;
+                }
+            }
+        }
+        ctx.request_repaint_after(std::time::Duration::from_millis(
+            self.refresh_ms.saturating_sub(elapsed).max(10),
+        ));
+    }
+
+    /// Add the latest stats response of each source to its history
+    fn update_histories(&mut self, data: &Data) {
+        for source in data.remote.sources() {
+            let Some(exchange) = data.remote.latest_response_from("stats", source) else {
+                continue;
+            };
+            if self.cached_message_ids.get(source) == Some(&exchange.message_id) {
+                continue;
+            }
+            self.cached_message_ids.insert(source.clone(), exchange.message_id);
+            if let Some(stats) = exchange.response.as_ref().and_then(StatsResponse::from_json) {
+                self.histories
+                    .entry(source.clone())
+                    .or_default()
+                    .push(exchange.received_ms.unwrap_or(exchange.sent_ms), stats);
+            }
+        }
+    }
+
+    /// History of the source displayed
+    fn history(&self) -> Option<&StatsHistory> {
+        match &self.selected_source {
+            Some(source) => self.histories.get(source),
+            None => self.histories.values().next(),
+        }
+    }
+
+    /// Display the controls
+    fn ui_controls(&mut self, ui: &mut egui::Ui, data: &Data) {
+        ui.horizontal(|ui| {
+            ui.checkbox(&mut self.auto_refresh, "Refresh every");
+            ui.add(
+                egui::DragValue::new(&mut self.refresh_ms)
+                    .speed(10.0)
+                    .range(200.0..=60000.0)
+                    .suffix(" ms"),
+            );
+            if ui.button("Clear").clicked() {
+                self.histories.clear();
+                self.selected_source = None;
+            }
+        });
+        if self.histories.len() > 1 {
+            ui.horizontal(|ui| {
+                ui.label("Source:");
+                egui::ComboBox::from_id_salt("cell_stats_source")
+                    .selected_text(
+                        self.selected_source
+                            .as_deref()
+                            .or(self.histories.keys().next().map(String::as_str))
+                            .unwrap_or_default(),
+                    )
+                    .show_ui(ui, |ui| {
+                        for source in self.histories.keys() {
+                            ui.selectable_value(&mut self.selected_source, Some(source.clone()), source);
+                        }
+                    });
+            });
+        }
+        if !data.remote.is_connected() {
+            ui.label("The statistics are only available with a websocket connection");
+        }
+    }
+
+    /// Display the latest values of each cell
+    fn ui_latest(&self, ui: &mut egui::Ui) {
+        let Some(latest) = self.history().and_then(StatsHistory::latest) else {
+            ui.label("No statistics");
+            return;
+        };
+        if let Some(cpu) = latest.stats.cpu.as_ref().and_then(|cpu| cpu.global) {
+            ui.label(format!("CPU load: {cpu:.1}%"));
+        }
+        egui::Grid::new("cell_stats_latest").striped(true).show(ui, |ui| {
+            ui.strong("Cell");
+            for metric in CellMetric::ALL {
+                ui.strong(metric.to_string());
+            }
+            ui.end_row();
+            for (cell, stats) in &latest.stats.cells {
+                ui.label(cell);
+                for metric in CellMetric::ALL {
+                    ui.label(match metric.value(stats) {
+                        Some(value) => format_metric(metric, value),
+                        None => "-".to_owned(),
+                    });
+                }
+                ui.end_row();
+            }
+        });
+    }
+
+    /// Display the plots of the history
+    fn ui_plots(&self, ui: &mut egui::Ui) {
+        let Some(history) = self.history() else {
+            return;
+        };
+        let cells = history.cells();
+        let plots: [(&str, &[CellMetric]); 3] = [
+            ("Bitrate (bit/s)", &[CellMetric::DlBitrate, CellMetric::UlBitrate]),
+            ("PRB usage (%)", &[CellMetric::DlPrbUsage, CellMetric::UlPrbUsage]),
+            ("UEs", &[CellMetric::UeCount]),
+        ];
+        for (title, metrics) in plots {
+            ui.label(title);
+            Plot::new(("cell_stats_plot", title))
+                .height(PLOT_HEIGHT)
+                .legend(Legend::default())
+                .show(ui, |plot_ui| {
+                    for cell in &cells {
+                        for metric in metrics {
+                            let points = history.cell_series(cell, *metric);
+                            plot_ui.line(Line::new(points).name(format!("Cell {cell} {metric}")));
+                        }
+                    }
+                });
+        }
+        ui.label("CPU load (%)");
+        Plot::new("cell_stats_plot_cpu")
+            .height(PLOT_HEIGHT)
+            .include_y(0.0)
+            .include_y(100.0)
+            .show(ui, |plot_ui| {
+                plot_ui.line(Line::new(history.cpu_series()).name("CPU"));
+            });
+    }
+}
+
+/// Format the value of a metric
+fn format_metric(metric: CellMetric, value: f64) -> String {
+    match metric {
+        CellMetric::DlBitrate | CellMetric::UlBitrate => format_bitrate(value),
+        CellMetric::DlPrbUsage | CellMetric::UlPrbUsage => format!("{value:.1}%"),
+        CellMetric::UeCount => format!("{value:.1}"),
+    }
+}
+
+impl super::PanelController for CellStats {
+    fn name(&self) -> &'static str {
+        "Cell stats"
+    }
+
+    fn window_title(&self) -> &'static str {
+        "Cell statistics"
+    }
+
+    fn clear(&mut self) {
+        self.histories.clear();
+        self.cached_message_ids.clear();
+        self.selected_source = None;
+        self.last_request_ms = 0;
+    }
+
+    fn show(&mut self, ctx: &egui::Context, open: &mut bool, data: &mut Data) -> Result<(), TramexError> {
+        if *open {
+            self.poll(ctx, data);
+        }
+        self.update_histories(data);
+        egui::Window::new(self.window_title())
+            .default_width(560.0)
+            .default_height(500.0)
+            .resizable(true)
+            .open(open)
+            .show(ctx, |ui| {
+                self.ui_controls(ui, data);
+                ui.separator();
+                self.ui_latest(ui);
+                ui.separator();
+                egui::ScrollArea::vertical()
+                    .id_salt("scroll_area_cell_stats")
+                    .show(ui, |ui| self.ui_plots(ui));
+            });
+        Ok(())
+    }
+}
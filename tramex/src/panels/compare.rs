@@ -0,0 +1,303 @@
+//! Compare panel
+use crate::format_timestamp;
+use eframe::egui;
+use tramex_tools::{
+    analysis::compare::{DiffKind, TraceDiff},
+    data::{AdditionalInfos, Data, Trace},
+    errors::TramexError,
+    hexdump,
+};
+
+/// Number of bytes per row of the byte diff
+const BYTES_PER_ROW: usize = 16;
+
+/// Color of the fields only in the second message
+const ADDED_COLOR: egui::Color32 = egui::Color32::from_rgb(80, 200, 120);
+
+/// Color of the fields only in the first message
+const REMOVED_COLOR: egui::Color32 = egui::Color32::from_rgb(230, 90, 90);
+
+/// Color of the fields with different values and of the different bytes
+const CHANGED_COLOR: egui::Color32 = egui::Color32::from_rgb(255, 210, 70);
+
+/// Message compared
+#[derive(Clone)]
+struct Compared {
+    /// Index of the trace, None if it is no longer shown
+    index: Option<usize>,
+
+    /// Trace
+    trace: Trace,
+}
+
+/// Compare two messages
+#[derive(Default)]
+pub struct ComparePanel {
+    /// First message
+    left: Option<Compared>,
+
+    /// Second message
+    right: Option<Compared>,
+
+    /// Differences of the two messages (None if one is missing)
+    diff: Option<TraceDiff>,
+
+    /// Show the fields with the same value
+    show_same: bool,
+
+    /// Revision of the events when the indexes of the messages were checked
+    cached_revision: u64,
+}
+
+/// Name of a trace
+fn trace_title(index: Option<usize>, trace: &Trace) -> String {
+    let AdditionalInfos::RRCInfos(infos) = &trace.additional_infos;
+    format!(
+        "#{} {} {:?} {} {}",
+        index.map_or_else(|| "-".to_owned(), |index| (index + 1).to_string()),
+        format_timestamp(trace.timestamp),
+        trace.layer,
+        infos.canal,
+        infos.canal_msg
+    )
+}
+
+impl ComparePanel {
+    /// Create a new ComparePanel
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Compare the two messages again
+    fn update_diff(&mut self) {
+        self.diff = match (&self.left, &self.right) {
+            (Some(left), Some(right)) => Some(TraceDiff::new(&left.trace, &right.trace)),
+            _ => None,
+        };
+    }
+
+    /// Find the messages again when the events were not only appended, their indexes changed
+    fn update_indexes(&mut self, data: &Data) {
+        if self.cached_revision == data.revision {
+            return;
+        }
+        for compared in [&mut self.left, &mut self.right].into_iter().flatten() {
+            compared.index = data.position(&compared.trace);
+        }
+        self.cached_revision = data.revision;
+    }
+
+    /// Index of the next message with the same name as the first message
+    fn next_same_message(&self, data: &Data) -> Option<usize> {
+        let left = self.left.as_ref()?;
+        let AdditionalInfos::RRCInfos(wanted) = &left.trace.additional_infos;
+        let left_index = left.index?;
+        let start = self
+            .right
+            .as_ref()
+            .and_then(|right| right.index)
+            .map_or(left_index, |right_index| right_index.max(left_index));
+        data.events.iter().enumerate().skip(start + 1).find_map(|(index, trace)| {
+            let AdditionalInfos::RRCInfos(infos) = &trace.additional_infos;
+            (infos.canal == wanted.canal && infos.canal_msg == wanted.canal_msg).then_some(index)
+        })
+    }
+
+    /// Display the messages compared and the buttons to choose them
+    fn ui_choice(&mut self, ui: &mut egui::Ui, data: &mut Data) {
+        let current = data.get_current_trace().map(|trace| Compared {
+            index: Some(data.current_index),
+            trace: trace.clone(),
+        });
+        let mut changed = false;
+        for (label, is_left) in [("A", true), ("B", false)] {
+            ui.horizontal(|ui| {
+                ui.strong(format!("{label}:"));
+                let compared = if is_left { &mut self.left } else { &mut self.right };
+                match compared {
+                    Some(compared) => match compared.index {
+                        Some(index) => {
+                            if ui
+                                .link(trace_title(compared.index, &compared.trace))
+                                .on_hover_text("Go to this message")
+                                .clicked()
+                            {
+                                data.current_index = index;
+                            }
+                        }
+                        None => {
+                            ui.label(trace_title(None, &compared.trace))
+                                .on_hover_text("This message is no longer shown");
+                        }
+                    },
+                    None => {
+                        ui.weak("No message");
+                    }
+                };
+                if ui
+                    .add_enabled(current.is_some(), egui::Button::new("Current message"))
+                    .clicked()
+                {
+                    *compared = current.clone();
+                    changed = true;
+                }
+            });
+        }
+        ui.horizontal(|ui| {
+            let next = self.next_same_message(data);
+            if ui
+                .add_enabled(next.is_some(), egui::Button::new("B: next same message"))
+                .on_hover_text("Compare with the next message of the same kind as A, such as the next SIB2")
+                .clicked()
+            {
+                if let Some(index) = next {
+                    self.right = data.events.get(index).map(|trace| Compared {
+                        index: Some(index),
+                        trace: trace.clone(),
+                    });
+                    changed = true;
+                }
+            }
+            if ui.button("Swap").clicked() {
+                std::mem::swap(&mut self.left, &mut self.right);
+                changed = true;
+            }
+        });
+        if changed {
+            self.update_diff();
+        }
+    }
+
+    /// Display the differences of the decoded bodies
+    fn ui_fields(ui: &mut egui::Ui, diff: &TraceDiff, show_same: &mut bool) {
+        ui.horizontal(|ui| {
+            ui.colored_label(CHANGED_COLOR, format!("{} changed", diff.count(DiffKind::Changed)));
+            ui.colored_label(ADDED_COLOR, format!("{} added", diff.count(DiffKind::Added)));
+            ui.colored_label(REMOVED_COLOR, format!("{} removed", diff.count(DiffKind::Removed)));
+            ui.label(format!("{} same", diff.count(DiffKind::Same)));
+            ui.checkbox(show_same, "Show same fields");
+        });
+        if diff.fields.is_empty() {
+            ui.label("No decoded body to compare");
+            return;
+        }
+        egui::ScrollArea::both()
+            .id_salt("compare_fields")
+            .max_height(300.0)
+            .auto_shrink([false, true])
+            .show(ui, |ui| {
+                egui::Grid::new("compare_fields_grid").striped(true).show(ui, |ui| {
+                    ui.strong("Field");
+                    ui.strong("A");
+                    ui.strong("B");
+                    ui.end_row();
+                    for field in &diff.fields {
+                        let color = match field.kind {
+                            DiffKind::Same if !*show_same => continue,
+                            DiffKind::Same => ui.visuals().weak_text_color(),
+                            DiffKind::Added => ADDED_COLOR,
+                            DiffKind::Removed => REMOVED_COLOR,
+                            DiffKind::Changed => CHANGED_COLOR,
+                        };
+                        ui.label(egui::RichText::new(&field.path).color(color).monospace());
+                        ui.monospace(field.left.as_deref().unwrap_or("-"));
+                        ui.monospace(field.right.as_deref().unwrap_or("-"));
+                        ui.end_row();
+                    }
+                });
+            });
+    }
+
+    /// Display the payloads side by side, with the different bytes highlighted
+    fn ui_bytes(ui: &mut egui::Ui, diff: &TraceDiff, left: &[u8], right: &[u8]) {
+        ui.label(format!(
+            "{} different bytes, A has {} bytes and B has {} bytes",
+            diff.different_bytes(),
+            diff.sizes.0,
+            diff.sizes.1
+        ));
+        let nb_rows = left.len().max(right.len()).div_ceil(BYTES_PER_ROW);
+        let row_height = ui.text_style_height(&egui::TextStyle::Monospace);
+        egui::ScrollArea::vertical()
+            .id_salt("compare_bytes")
+            .max_height(200.0)
+            .auto_shrink([false, true])
+            .show_rows(ui, row_height, nb_rows, |ui, rows| {
+                for row in rows {
+                    let start = row * BYTES_PER_ROW;
+                    ui.horizontal(|ui| {
+                        ui.spacing_mut().item_spacing.x = 0.0;
+                        ui.label(egui::RichText::new(format!("{start:04x}:  ")).monospace().weak());
+                        for (bytes, separator) in [(left, "   "), (right, "")] {
+                            for index in start..start + BYTES_PER_ROW {
+                                let text = match bytes.get(index) {
+                                    Some(byte) => format!("{byte:02x} "),
+                                    None => "   ".to_owned(),
+                                };
+                                let mut text = egui::RichText::new(text).monospace();
+                                if bytes.get(index).is_some() && diff.bytes.iter().any(|range| range.contains(&index)) {
+                                    text = text.background_color(CHANGED_COLOR).color(egui::Color32::BLACK);
+                                }
+                                ui.label(text);
+                            }
+                            ui.label(egui::RichText::new(separator).monospace());
+                        }
+                    });
+                }
+            });
+        if ui.button("Copy payloads as hex").clicked() {
+            let text = format!("A: {}\nB: {}", hexdump::to_hex(left), hexdump::to_hex(right));
+            ui.output_mut(|o| o.copied_text = text);
+        }
+    }
+}
+
+impl super::PanelController for ComparePanel {
+    fn name(&self) -> &'static str {
+        "Compare"
+    }
+
+    fn window_title(&self) -> &'static str {
+        "Compare messages"
+    }
+
+    fn clear(&mut self) {
+        self.left = None;
+        self.right = None;
+        self.diff = None;
+    }
+
+    fn show(&mut self, ctx: &egui::Context, open: &mut bool, data: &mut Data) -> Result<(), TramexError> {
+        egui::Window::new(self.window_title())
+            .default_width(560.0)
+            .resizable(true)
+            .open(open)
+            .show(ctx, |ui| {
+                self.update_indexes(data);
+                self.ui_choice(ui, data);
+                let (Some(diff), Some(left), Some(right)) = (&self.diff, &self.left, &self.right) else {
+                    ui.label("Choose two messages to compare, such as two SIB2 from different eNB builds");
+                    return;
+                };
+                ui.separator();
+                if diff.is_identical() {
+                    ui.label("The messages are identical");
+                }
+                if ui
+                    .button("Copy report")
+                    .on_hover_text("Copy the differences as text")
+                    .clicked()
+                {
+                    let report = diff.report(&left.trace, &right.trace);
+                    ui.output_mut(|o| o.copied_text = report);
+                }
+                egui::CollapsingHeader::new("Decoded body")
+                    .default_open(true)
+                    .show(ui, |ui| Self::ui_fields(ui, diff, &mut self.show_same));
+                egui::CollapsingHeader::new("Bytes")
+                    .default_open(true)
+                    .show(ui, |ui| Self::ui_bytes(ui, diff, &left.trace.hexa, &right.trace.hexa));
+            });
+        Ok(())
+    }
+}
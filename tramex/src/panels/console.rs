@@ -0,0 +1,224 @@
+//! Remote API console panel
+use std::collections::VecDeque;
+
+use eframe::egui;
+use tramex_tools::{
+    data::Data,
+    errors::{ErrorCode, TramexError},
+    interface::remote_api::{Exchange, KNOWN_MESSAGES, MAX_EXCHANGES, Request},
+    tramex_error,
+};
+
+/// Console to send requests to the remote API
+pub struct Console {
+    /// Name of the message to send
+    message: String,
+
+    /// Parameters of the message (JSON object)
+    params: String,
+
+    /// Source the request is sent to (the first one available if None)
+    target: Option<String>,
+
+    /// Show only the exchanges sent from the console
+    only_console: bool,
+
+    /// Message IDs of the requests sent from the console, at most as many as the exchanges kept by the remote API
+    sent_ids: VecDeque<u64>,
+
+    /// Exchanges up to this message ID are hidden, the history was cleared after them
+    cleared_id: Option<u64>,
+}
+
+impl Default for Console {
+    fn default() -> Self {
+        Self {
+            message: KNOWN_MESSAGES[0].to_owned(),
+            params: "{}".to_owned(),
+            target: None,
+            only_console: true,
+            sent_ids: VecDeque::new(),
+            cleared_id: None,
+        }
+    }
+}
+
+impl Console {
+    /// Create a new Console
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Build the request from the message and the parameters
+    /// # Errors
+    /// Return an error if the parameters are not a JSON object
+    fn build_request(&self) -> Result<Request, TramexError> {
+        let mut request = Request::new(self.message.trim());
+        if let Some(target) = &self.target {
+            request = request.to(target);
+        }
+        let params = self.params.trim();
+        if params.is_empty() {
+            return Ok(request);
+        }
+        match serde_json::from_str::<serde_json::Value>(params) {
+            Ok(serde_json::Value::Object(params)) => {
+                request.params = params;
+                Ok(request)
+            }
+            Ok(_) => Err(tramex_error!(
+                "The parameters must be a JSON object".to_owned(),
+                ErrorCode::RequestError
+            )),
+            Err(err) => Err(tramex_error!(format!("Invalid parameters: {err}"), ErrorCode::RequestError)),
+        }
+    }
+
+    /// Display the request editor
+    fn ui_request(&mut self, ui: &mut egui::Ui, data: &mut Data) -> Result<(), TramexError> {
+        let mut result = Ok(());
+        ui.horizontal(|ui| {
+            ui.label("Message:");
+            egui::ComboBox::from_id_salt("console_message")
+                .selected_text(&self.message)
+                .show_ui(ui, |ui| {
+                    for message in KNOWN_MESSAGES {
+                        ui.selectable_value(&mut self.message, message.to_owned(), message);
+                    }
+                });
+            ui.add(egui::TextEdit::singleline(&mut self.message).desired_width(100.0));
+        });
+        if self
+            .target
+            .as_ref()
+            .is_some_and(|target| !data.remote.sources().any(|source| source == target))
+        {
+            self.target = None;
+        }
+        ui.horizontal(|ui| {
+            ui.label("Send to:");
+            egui::ComboBox::from_id_salt("console_target")
+                .selected_text(self.target.as_deref().unwrap_or("First available"))
+                .show_ui(ui, |ui| {
+                    ui.selectable_value(&mut self.target, None, "First available");
+                    for source in data.remote.sources() {
+                        ui.selectable_value(&mut self.target, Some(source.clone()), source);
+                    }
+                });
+        });
+        ui.label("Parameters (JSON object):");
+        ui.add(
+            egui::TextEdit::multiline(&mut self.params)
+                .code_editor()
+                .desired_rows(3)
+                .desired_width(f32::INFINITY),
+        );
+        ui.horizontal(|ui| {
+            ui.add_enabled_ui(data.remote.is_connected(), |ui| {
+                if ui.button("Send").clicked() {
+                    match self.build_request() {
+                        Ok(request) => {
+                            if let Some(message_id) = data.remote.request(request) {
+                                if self.sent_ids.len() >= MAX_EXCHANGES {
+                                    self.sent_ids.pop_front();
+                                }
+                                self.sent_ids.push_back(message_id);
+                            }
+                        }
+                        Err(err) => result = Err(err),
+                    }
+                }
+            });
+            if ui
+                .button("Clear history")
+                .on_hover_text("Hide the requests already sent, the other panels keep their responses")
+                .clicked()
+            {
+                self.sent_ids.clear();
+                self.cleared_id = data.remote.exchanges.iter().map(|exchange| exchange.message_id).max();
+            }
+            ui.checkbox(&mut self.only_console, "Only requests sent from the console");
+        });
+        if !data.remote.is_connected() {
+            ui.label("The remote API is only available with a websocket connection");
+        }
+        result
+    }
+
+    /// Display the exchanges, the latest first
+    fn ui_history(&self, ui: &mut egui::Ui, data: &Data) {
+        egui::ScrollArea::vertical()
+            .id_salt("scroll_area_console")
+            .auto_shrink([false, false])
+            .show(ui, |ui| {
+                for exchange in data.remote.exchanges.iter().rev() {
+                    if self.cleared_id.is_some_and(|cleared_id| exchange.message_id <= cleared_id)
+                        || (self.only_console && !self.sent_ids.contains(&exchange.message_id))
+                    {
+                        continue;
+                    }
+                    ui_exchange(ui, exchange);
+                }
+            });
+    }
+}
+
+/// Display one exchange
+fn ui_exchange(ui: &mut egui::Ui, exchange: &Exchange) {
+    let status = match (&exchange.response, exchange.error(), exchange.latency_ms()) {
+        (None, _, _) => "pending".to_owned(),
+        (Some(_), Some(error), _) => format!("error: {error}"),
+        (Some(_), None, Some(latency)) => format!("{latency} ms"),
+        (Some(_), None, None) => "ok".to_owned(),
+    };
+    let title = format!(
+        "#{} {} to {} ({})",
+        exchange.message_id, exchange.request.message, exchange.source, status
+    );
+    egui::CollapsingHeader::new(title)
+        .id_salt(("console_exchange", exchange.message_id))
+        .show(ui, |ui| {
+            ui.label("Request:");
+            ui_json(ui, &exchange.request.to_json(exchange.message_id));
+            if let Some(response) = &exchange.response {
+                ui.label("Response:");
+                ui_json(ui, response);
+            }
+        });
+}
+
+/// Display a pretty-printed JSON value
+fn ui_json(ui: &mut egui::Ui, value: &serde_json::Value) {
+    let text = serde_json::to_string_pretty(value).unwrap_or_else(|_| value.to_string());
+    ui.add(egui::Label::new(egui::RichText::new(text).monospace()).selectable(true));
+}
+
+impl super::PanelController for Console {
+    fn name(&self) -> &'static str {
+        "Console"
+    }
+
+    fn window_title(&self) -> &'static str {
+        "Remote API console"
+    }
+
+    fn clear(&mut self) {
+        self.sent_ids.clear();
+        self.cleared_id = None;
+    }
+
+    fn show(&mut self, ctx: &egui::Context, open: &mut bool, data: &mut Data) -> Result<(), TramexError> {
+        let mut result = Ok(());
+        egui::Window::new(self.window_title())
+            .default_width(480.0)
+            .default_height(400.0)
+            .resizable(true)
+            .open(open)
+            .show(ctx, |ui| {
+                result = self.ui_request(ui, data);
+                ui.separator();
+                self.ui_history(ui, data);
+            });
+        result
+    }
+}
@@ -0,0 +1,299 @@
+//! Hex viewer panel
+use eframe::egui;
+use std::ops::Range;
+use tramex_tools::{
+    data::Data,
+    errors::TramexError,
+    hexdump::{self, CopyFormat},
+};
+
+/// Widths of the rows (bytes)
+const WIDTHS: [usize; 3] = [8, 16, 32];
+
+/// Maximum number of bytes displayed in the bit view
+const MAX_BIT_BYTES: usize = 16;
+
+/// Color of the bytes found by the search
+const SEARCH_COLOR: egui::Color32 = egui::Color32::from_rgb(255, 210, 70);
+
+/// Hex viewer
+pub struct HexViewer {
+    /// Index of the trace displayed
+    trace_index: Option<usize>,
+
+    /// Revision of the events when the trace was read
+    cached_revision: u64,
+
+    /// Payload of the trace
+    hexa: Vec<u8>,
+
+    /// Bytes of the payload found by the search
+    found: Vec<Range<usize>>,
+
+    /// Number of bytes per row
+    width: usize,
+
+    /// Selected bytes
+    selection: Option<Range<usize>>,
+
+    /// Byte clicked first, the selection is extended from it with shift
+    anchor: usize,
+
+    /// Selected bits in the bit view
+    bits: Range<usize>,
+
+    /// Format of the copied bytes
+    copy_format: CopyFormat,
+}
+
+impl Default for HexViewer {
+    fn default() -> Self {
+        Self {
+            trace_index: None,
+            cached_revision: 0,
+            hexa: vec![],
+            found: vec![],
+            width: hexdump::DEFAULT_WIDTH,
+            selection: None,
+            anchor: 0,
+            bits: 0..0,
+            copy_format: CopyFormat::default(),
+        }
+    }
+}
+
+impl HexViewer {
+    /// Create a new HexViewer
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Read the current trace if it changed
+    fn update_trace(&mut self, data: &Data) {
+        if self.trace_index != Some(data.current_index) || self.cached_revision != data.revision {
+            let trace = data.get_current_trace();
+            let hexa = trace.map(|trace| trace.hexa.clone()).unwrap_or_default();
+            if hexa != self.hexa {
+                self.selection = None;
+                self.anchor = 0;
+                self.bits = 0..0;
+            }
+            self.hexa = hexa;
+            self.trace_index = Some(data.current_index);
+            self.cached_revision = data.revision;
+        }
+        self.found = match (&data.search, data.get_current_trace()) {
+            (Some(search), Some(trace)) => search.find_in_bytes(&trace.hexa),
+            _ => vec![],
+        };
+    }
+
+    /// Select a range of bytes and their bits
+    fn select_bytes(&mut self, bytes: Range<usize>) {
+        self.bits = bytes.start * 8..bytes.end * 8;
+        self.selection = Some(bytes);
+    }
+
+    /// Background of a byte: selected or found by the search
+    fn byte_background(&self, ui: &egui::Ui, index: usize) -> Option<egui::Color32> {
+        if self.selection.as_ref().is_some_and(|selection| selection.contains(&index)) {
+            return Some(ui.visuals().selection.bg_fill);
+        }
+        if self.found.iter().any(|range| range.contains(&index)) {
+            return Some(SEARCH_COLOR);
+        }
+        None
+    }
+
+    /// Text of a byte with its background
+    fn byte_text(&self, ui: &egui::Ui, index: usize, text: String) -> egui::RichText {
+        let text = egui::RichText::new(text).monospace();
+        match self.byte_background(ui, index) {
+            Some(color) => text.background_color(color).color(egui::Color32::BLACK),
+            None => text,
+        }
+    }
+
+    /// Handle a click on a byte, shift extends the selection
+    fn click_byte(&mut self, ui: &egui::Ui, index: usize) {
+        let extend = ui.input(|i| i.modifiers.shift) && self.selection.is_some();
+        if !extend {
+            self.anchor = index;
+        }
+        self.select_bytes(self.anchor.min(index)..self.anchor.max(index) + 1);
+    }
+
+    /// Display the options and the copy buttons
+    fn ui_options(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.label(format!("{} bytes", self.hexa.len()));
+            egui::ComboBox::from_label("Width")
+                .selected_text(self.width.to_string())
+                .show_ui(ui, |ui| {
+                    for width in WIDTHS {
+                        ui.selectable_value(&mut self.width, width, width.to_string());
+                    }
+                });
+            egui::ComboBox::from_label("Copy as")
+                .selected_text(self.copy_format.label())
+                .show_ui(ui, |ui| {
+                    for format in CopyFormat::ALL {
+                        ui.selectable_value(&mut self.copy_format, format, format.label());
+                    }
+                });
+            let bytes = match &self.selection {
+                Some(selection) => self.hexa.get(selection.clone()).unwrap_or_default(),
+                None => &self.hexa,
+            };
+            let label = match self.selection {
+                Some(_) => "Copy selection",
+                None => "Copy all",
+            };
+            if ui.button(label).clicked() {
+                let text = self.copy_format.format(bytes);
+                ui.output_mut(|o| o.copied_text = text);
+            }
+            if self.selection.is_some() && ui.button("Clear selection").clicked() {
+                self.selection = None;
+                self.bits = 0..0;
+            }
+        });
+    }
+
+    /// Display the offset, hexadecimal and ASCII columns
+    fn ui_dump(&mut self, ui: &mut egui::Ui) {
+        let row_height = ui.text_style_height(&egui::TextStyle::Monospace);
+        let nb_rows = self.hexa.len().div_ceil(self.width);
+        let mut clicked = None;
+        egui::ScrollArea::vertical()
+            .id_salt("hex_viewer_dump")
+            .max_height(300.0)
+            .auto_shrink([false, true])
+            .show_rows(ui, row_height, nb_rows, |ui, visible| {
+                for row in visible {
+                    let start = row * self.width;
+                    let end = (start + self.width).min(self.hexa.len());
+                    ui.horizontal(|ui| {
+                        ui.spacing_mut().item_spacing.x = 0.0;
+                        ui.label(egui::RichText::new(format!("{start:04x}:  ")).monospace().weak());
+                        for index in start..start + self.width {
+                            let text = match self.hexa.get(index) {
+                                Some(byte) => self.byte_text(ui, index, format!("{byte:02x}")),
+                                None => egui::RichText::new("  ").monospace(),
+                            };
+                            if ui.add(egui::Label::new(text).sense(egui::Sense::click())).clicked() && index < end {
+                                clicked = Some(index);
+                            }
+                            ui.label(egui::RichText::new(" ").monospace());
+                        }
+                        ui.label(egui::RichText::new(" ").monospace());
+                        for index in start..end {
+                            let text = self.byte_text(ui, index, hexdump::ascii_char(self.hexa[index]).to_string());
+                            if ui.add(egui::Label::new(text).sense(egui::Sense::click())).clicked() {
+                                clicked = Some(index);
+                            }
+                        }
+                    });
+                }
+            });
+        if let Some(index) = clicked {
+            self.click_byte(ui, index);
+        }
+    }
+
+    /// Display the bits of the selected bytes, and the value of the selected bits
+    fn ui_bits(&mut self, ui: &mut egui::Ui) {
+        let Some(selection) = self.selection.clone() else {
+            ui.label("Select bytes to see their bits, shift click to extend the selection");
+            return;
+        };
+        let shown = selection.start..selection.end.min(selection.start + MAX_BIT_BYTES);
+        let mut clicked = None;
+        for index in shown.clone() {
+            ui.horizontal(|ui| {
+                ui.spacing_mut().item_spacing.x = 0.0;
+                ui.label(egui::RichText::new(format!("{index:04x}:  ")).monospace().weak());
+                for bit in index * 8..index * 8 + 8 {
+                    let set = hexdump::bit(&self.hexa, bit).unwrap_or_default();
+                    let mut text = egui::RichText::new(if set { "1 " } else { "0 " }).monospace();
+                    if self.bits.contains(&bit) {
+                        text = text.background_color(ui.visuals().selection.bg_fill);
+                    }
+                    let response = ui
+                        .add(egui::Label::new(text).sense(egui::Sense::click()))
+                        .on_hover_text(format!("Bit {bit}"));
+                    if response.clicked() {
+                        clicked = Some(bit);
+                    }
+                }
+            });
+        }
+        if shown.end < selection.end {
+            ui.label(format!("{} more bytes", selection.end - shown.end));
+        }
+        if let Some(bit) = clicked {
+            self.bits = match ui.input(|i| i.modifiers.shift) {
+                true => self.bits.start.min(bit)..self.bits.start.max(bit) + 1,
+                false => bit..bit + 1,
+            };
+        }
+        let max = self.hexa.len() * 8;
+        let (mut start, mut length) = (self.bits.start, self.bits.len());
+        ui.horizontal(|ui| {
+            ui.label("Bits from");
+            ui.add(egui::DragValue::new(&mut start).range(0..=max));
+            ui.label("length");
+            ui.add(egui::DragValue::new(&mut length).range(0..=64));
+        });
+        self.bits = start..(start + length).min(max);
+        match (
+            hexdump::read_bits(&self.hexa, self.bits.clone()),
+            hexdump::bits_string(&self.hexa, self.bits.clone()),
+        ) {
+            (Some(value), Some(bits)) if !self.bits.is_empty() => {
+                ui.monospace(format!("{bits} = {value} (0x{value:x})"));
+            }
+            _ => {
+                ui.label("Select up to 64 bits to read their value");
+            }
+        }
+    }
+}
+
+impl super::PanelController for HexViewer {
+    fn name(&self) -> &'static str {
+        "Hex viewer"
+    }
+
+    fn window_title(&self) -> &'static str {
+        "Hex viewer"
+    }
+
+    fn clear(&mut self) {
+        self.trace_index = None;
+        self.hexa.clear();
+        self.found.clear();
+        self.selection = None;
+        self.bits = 0..0;
+    }
+
+    fn show(&mut self, ctx: &egui::Context, open: &mut bool, data: &mut Data) -> Result<(), TramexError> {
+        self.update_trace(data);
+        egui::Window::new(self.window_title())
+            .default_width(560.0)
+            .resizable(true)
+            .open(open)
+            .show(ctx, |ui| {
+                if self.hexa.is_empty() {
+                    ui.label("No payload for this message");
+                    return;
+                }
+                self.ui_options(ui);
+                ui.separator();
+                self.ui_dump(ui);
+                ui.separator();
+                ui.collapsing("Bits", |ui| self.ui_bits(ui));
+            });
+        Ok(())
+    }
+}
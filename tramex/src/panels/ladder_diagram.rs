@@ -0,0 +1,258 @@
+//! Message sequence (ladder) diagram panel
+use crate::format_timestamp;
+use eframe::egui;
+use tramex_tools::{
+    data::{AdditionalInfos, Data, Trace, TraceFilter},
+    errors::TramexError,
+    interface::{layer::Layer, types::Direction},
+};
+
+/// Height of one message row
+const ROW_HEIGHT: f32 = 26.0;
+
+/// Width of the timestamp column
+const TIME_WIDTH: f32 = 90.0;
+
+/// Network elements shown as lifelines
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Node {
+    /// User equipment
+    UE,
+
+    /// Base station
+    ENB,
+
+    /// Core network
+    MME,
+}
+
+impl Node {
+    /// All the nodes, in display order
+    const ALL: [Node; 3] = [Node::UE, Node::ENB, Node::MME];
+
+    /// Horizontal position of the lifeline, as a fraction of the lanes width
+    fn position(&self) -> f32 {
+        match self {
+            Node::UE => 0.1,
+            Node::ENB => 0.5,
+            Node::MME => 0.9,
+        }
+    }
+
+    /// Name of the node
+    fn label(&self) -> &'static str {
+        match self {
+            Node::UE => "UE",
+            Node::ENB => "eNB",
+            Node::MME => "MME",
+        }
+    }
+}
+
+/// Get the source and destination nodes of a trace, None if it can't be drawn
+pub fn trace_endpoints(trace: &Trace) -> Option<(Node, Node)> {
+    let AdditionalInfos::RRCInfos(infos) = &trace.additional_infos;
+    match (&trace.layer, &infos.direction) {
+        (Layer::RRC, Direction::UL) => Some((Node::UE, Node::ENB)),
+        (Layer::RRC, Direction::DL) => Some((Node::ENB, Node::UE)),
+        (Layer::NAS, Direction::UL) => Some((Node::UE, Node::MME)),
+        (Layer::NAS, Direction::DL) => Some((Node::MME, Node::UE)),
+        (Layer::S1AP | Layer::NGAP, Direction::TO) => Some((Node::ENB, Node::MME)),
+        (Layer::S1AP | Layer::NGAP, Direction::FROM) => Some((Node::MME, Node::ENB)),
+        _ => None,
+    }
+}
+
+/// Ladder diagram
+#[derive(Default)]
+pub struct LadderDiagram {
+    /// Indexes of the events drawn in the diagram
+    rows: Vec<usize>,
+
+    /// Number of events used to compute `rows`
+    cached_len: usize,
+
+    /// Revision of the events used to compute `rows`
+    cached_revision: u64,
+
+    /// Hide broadcast messages (without UE)
+    hide_broadcast: bool,
+
+    /// Value of `hide_broadcast` used to compute `rows`
+    cached_hide_broadcast: bool,
+
+    /// Shared filter used to compute `rows`
+    cached_data_filter: TraceFilter,
+
+    /// Last current index seen
+    current_index: usize,
+}
+
+impl LadderDiagram {
+    /// Create a new LadderDiagram
+    pub fn new() -> Self {
+        Self {
+            hide_broadcast: true,
+            cached_hide_broadcast: true,
+            ..Default::default()
+        }
+    }
+
+    /// Update the rows if the events or the options changed
+    fn update_rows(&mut self, data: &Data) {
+        if self.cached_hide_broadcast != self.hide_broadcast
+            || self.cached_data_filter != data.filter
+            || self.cached_revision != data.revision
+            || data.events.len() < self.cached_len
+        {
+            self.rows.clear();
+            self.cached_len = 0;
+        }
+        for (idx, trace) in data.events.iter().enumerate().skip(self.cached_len) {
+            if trace_endpoints(trace).is_none() || !data.filter.matches(trace) {
+                continue;
+            }
+            let AdditionalInfos::RRCInfos(infos) = &trace.additional_infos;
+            if self.hide_broadcast && infos.ue_id.is_none() {
+                continue;
+            }
+            self.rows.push(idx);
+        }
+        self.cached_len = data.events.len();
+        self.cached_revision = data.revision;
+        self.cached_hide_broadcast = self.hide_broadcast;
+        self.cached_data_filter = data.filter.clone();
+    }
+
+    /// Draw the header with the name of each node
+    fn ui_header(&self, ui: &mut egui::Ui) {
+        let (rect, _) = ui.allocate_exact_size(egui::vec2(ui.available_width(), ROW_HEIGHT), egui::Sense::hover());
+        let painter = ui.painter_at(rect);
+        let lanes = lanes_rect(rect);
+        for node in Node::ALL {
+            painter.text(
+                egui::pos2(node_x(&lanes, node), rect.center().y),
+                egui::Align2::CENTER_CENTER,
+                node.label(),
+                egui::FontId::proportional(16.0),
+                ui.visuals().strong_text_color(),
+            );
+        }
+    }
+
+    /// Draw one message row, return true if clicked
+    fn ui_row(ui: &mut egui::Ui, idx: usize, trace: &Trace, selected: bool) -> bool {
+        let (rect, response) = ui.allocate_exact_size(egui::vec2(ui.available_width(), ROW_HEIGHT), egui::Sense::click());
+        let painter = ui.painter_at(rect);
+        let visuals = ui.visuals();
+        if selected {
+            painter.rect_filled(rect, 0.0, visuals.selection.bg_fill);
+        } else if response.hovered() {
+            painter.rect_filled(rect, 0.0, visuals.widgets.hovered.weak_bg_fill);
+        }
+        let lanes = lanes_rect(rect);
+        let lifeline = egui::Stroke::new(1.0, visuals.weak_text_color());
+        for node in Node::ALL {
+            let x = node_x(&lanes, node);
+            painter.line_segment([egui::pos2(x, rect.top()), egui::pos2(x, rect.bottom())], lifeline);
+        }
+        painter.text(
+            egui::pos2(rect.left() + 4.0, rect.center().y),
+            egui::Align2::LEFT_CENTER,
+            format_timestamp(trace.timestamp),
+            egui::FontId::monospace(11.0),
+            visuals.text_color(),
+        );
+        if let Some((from, to)) = trace_endpoints(trace) {
+            let AdditionalInfos::RRCInfos(infos) = &trace.additional_infos;
+            let y = rect.bottom() - 6.0;
+            let start = egui::pos2(node_x(&lanes, from), y);
+            let end = egui::pos2(node_x(&lanes, to), y);
+            let color = match infos.direction {
+                Direction::UL | Direction::TO => egui::Color32::from_rgb(68, 143, 255),
+                Direction::DL | Direction::FROM => egui::Color32::from_rgb(90, 200, 100),
+                Direction::NONE => egui::Color32::GRAY,
+            };
+            painter.arrow(start, end - start, egui::Stroke::new(1.5, color));
+            painter.text(
+                egui::pos2((start.x + end.x) / 2.0, y - 2.0),
+                egui::Align2::CENTER_BOTTOM,
+                &infos.canal_msg,
+                egui::FontId::proportional(12.0),
+                visuals.text_color(),
+            );
+        }
+        response
+            .on_hover_text(format!("#{} {:?}", idx + 1, trace.additional_infos))
+            .clicked()
+    }
+}
+
+/// Get the part of a row used by the lifelines
+fn lanes_rect(rect: egui::Rect) -> egui::Rect {
+    egui::Rect::from_min_max(egui::pos2(rect.left() + TIME_WIDTH, rect.top()), rect.max)
+}
+
+/// Get the horizontal position of a node lifeline
+fn node_x(lanes: &egui::Rect, node: Node) -> f32 {
+    lanes.left() + lanes.width() * node.position()
+}
+
+impl super::PanelController for LadderDiagram {
+    fn name(&self) -> &'static str {
+        "Ladder diagram"
+    }
+
+    fn window_title(&self) -> &'static str {
+        "Message sequence diagram"
+    }
+
+    fn clear(&mut self) {
+        self.rows.clear();
+        self.cached_len = 0;
+        self.current_index = 0;
+    }
+
+    fn show(&mut self, ctx: &egui::Context, open: &mut bool, data: &mut Data) -> Result<(), TramexError> {
+        self.update_rows(data);
+        let scroll_to_current = self.current_index != data.current_index;
+        egui::Window::new(self.window_title())
+            .default_width(480.0)
+            .default_height(480.0)
+            .resizable(true)
+            .open(open)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.checkbox(&mut self.hide_broadcast, "Hide broadcast messages");
+                    ui.label(format!("{} messages", self.rows.len()));
+                });
+                ui.separator();
+                self.ui_header(ui);
+                let mut scroll_area = egui::ScrollArea::vertical()
+                    .id_salt("scroll_area_ladder")
+                    .auto_shrink([false, false]);
+                if scroll_to_current {
+                    if let Some(position) = self.rows.iter().position(|idx| *idx == data.current_index) {
+                        let spacing = ui.spacing().item_spacing.y;
+                        scroll_area = scroll_area.vertical_scroll_offset(position as f32 * (ROW_HEIGHT + spacing));
+                    }
+                }
+                let mut clicked = None;
+                scroll_area.show_rows(ui, ROW_HEIGHT, self.rows.len(), |ui, row_range| {
+                    for row in row_range {
+                        let idx = self.rows[row];
+                        if let Some(trace) = data.events.get(idx) {
+                            if Self::ui_row(ui, idx, trace, idx == data.current_index) {
+                                clicked = Some(idx);
+                            }
+                        }
+                    }
+                });
+                if let Some(idx) = clicked {
+                    data.current_index = idx;
+                }
+            });
+        self.current_index = data.current_index;
+        Ok(())
+    }
+}
@@ -0,0 +1,387 @@
+//! Message list panel
+use crate::format_timestamp;
+use eframe::egui;
+use tramex_tools::{
+    data::{AdditionalInfos, Bookmark, Data, Trace, TraceFilter},
+    errors::TramexError,
+    interface::{layer::Layer, types::Direction},
+};
+
+/// Filters applied on the message list
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct ListFilter {
+    /// Layer to keep (all if None)
+    pub layer: Option<Layer>,
+
+    /// Direction to keep (all if None)
+    pub direction: Option<Direction>,
+
+    /// Channel to keep (all if None)
+    pub channel: Option<String>,
+
+    /// Source to keep (all if None)
+    pub source: Option<String>,
+
+    /// UE identifier to keep (all if empty)
+    pub ue_id: String,
+
+    /// Free text to search in the message name and text
+    pub text: String,
+
+    /// Keep only the bookmarked messages
+    pub bookmarked: bool,
+}
+
+impl ListFilter {
+    /// Check if a trace matches the filter
+    pub fn matches(&self, trace: &Trace) -> bool {
+        if let Some(layer) = &self.layer {
+            if &trace.layer != layer {
+                return false;
+            }
+        }
+        let AdditionalInfos::RRCInfos(infos) = &trace.additional_infos;
+        if let Some(direction) = &self.direction {
+            if &infos.direction != direction {
+                return false;
+            }
+        }
+        if let Some(channel) = &self.channel {
+            if &infos.canal != channel {
+                return false;
+            }
+        }
+        if self.source.is_some() && trace.source != self.source {
+            return false;
+        }
+        if self.bookmarked && trace.bookmark.is_none() {
+            return false;
+        }
+        let ue_filter = self.ue_id.trim();
+        if !ue_filter.is_empty() {
+            match (infos.ue_id, ue_filter.parse::<u64>()) {
+                (Some(ue_id), Ok(wanted)) if ue_id == wanted => {}
+                _ => return false,
+            }
+        }
+        let text_filter = self.text.trim().to_lowercase();
+        if !text_filter.is_empty() {
+            let in_msg = infos.canal_msg.to_lowercase().contains(&text_filter);
+            let in_text = match &trace.text {
+                Some(lines) => lines.iter().any(|line| line.to_lowercase().contains(&text_filter)),
+                None => false,
+            };
+            if !in_msg && !in_text {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+/// Message list
+#[derive(Default)]
+pub struct MessageList {
+    /// Filters set by the user
+    filter: ListFilter,
+
+    /// Filters used to compute `filtered`
+    cached_filter: ListFilter,
+
+    /// Shared filter used to compute `filtered`
+    cached_data_filter: TraceFilter,
+
+    /// Number of events used to compute `filtered`
+    cached_len: usize,
+
+    /// Revision of the events used to compute `filtered`
+    cached_revision: u64,
+
+    /// Revision of the bookmarks used to compute `filtered`
+    cached_bookmarks_revision: u64,
+
+    /// Indexes of the events matching the filter
+    filtered: Vec<usize>,
+
+    /// Layers seen in the events
+    layers: Vec<Layer>,
+
+    /// Channels seen in the events
+    channels: Vec<String>,
+
+    /// Sources seen in the events
+    sources: Vec<String>,
+
+    /// Last current index seen
+    current_index: usize,
+
+    /// Scroll to the current message when it changes
+    follow: bool,
+}
+
+impl MessageList {
+    /// Create a new MessageList
+    pub fn new() -> Self {
+        Self {
+            follow: true,
+            ..Default::default()
+        }
+    }
+
+    /// Update the filtered indexes if the events or the filter changed
+    fn update_filtered(&mut self, data: &Data) {
+        // the bookmarks only change the filtered indexes when the bookmarked messages are kept
+        let bookmarks_changed = self.filter.bookmarked && self.cached_bookmarks_revision != data.bookmarks_revision;
+        if self.cached_len == data.events.len()
+            && self.cached_revision == data.revision
+            && !bookmarks_changed
+            && self.cached_filter == self.filter
+            && self.cached_data_filter == data.filter
+        {
+            return;
+        }
+        if self.cached_filter != self.filter
+            || self.cached_data_filter != data.filter
+            || self.cached_revision != data.revision
+            || bookmarks_changed
+            || data.events.len() < self.cached_len
+        {
+            self.filtered.clear();
+            self.cached_len = 0;
+        }
+        for (idx, trace) in data.events.iter().enumerate().skip(self.cached_len) {
+            if !self.layers.contains(&trace.layer) {
+                self.layers.push(trace.layer.clone());
+            }
+            let AdditionalInfos::RRCInfos(infos) = &trace.additional_infos;
+            if !self.channels.contains(&infos.canal) {
+                self.channels.push(infos.canal.clone());
+            }
+            if let Some(source) = &trace.source {
+                if !self.sources.contains(source) {
+                    self.sources.push(source.clone());
+                }
+            }
+            if self.filter.matches(trace) && data.filter.matches(trace) {
+                self.filtered.push(idx);
+            }
+        }
+        self.cached_len = data.events.len();
+        self.cached_revision = data.revision;
+        self.cached_bookmarks_revision = data.bookmarks_revision;
+        self.cached_filter = self.filter.clone();
+        self.cached_data_filter = data.filter.clone();
+    }
+
+    /// Display the filters
+    fn ui_filters(&mut self, ui: &mut egui::Ui, data: &mut Data) {
+        ui.horizontal_wrapped(|ui| {
+            egui::ComboBox::from_label("Layer")
+                .selected_text(option_text(&self.filter.layer))
+                .show_ui(ui, |ui| {
+                    ui.selectable_value(&mut self.filter.layer, None, "All");
+                    for layer in &self.layers {
+                        ui.selectable_value(&mut self.filter.layer, Some(layer.clone()), format!("{layer:?}"));
+                    }
+                });
+            egui::ComboBox::from_label("Direction")
+                .selected_text(option_text(&self.filter.direction))
+                .show_ui(ui, |ui| {
+                    ui.selectable_value(&mut self.filter.direction, None, "All");
+                    for direction in [Direction::UL, Direction::DL, Direction::FROM, Direction::TO, Direction::NONE] {
+                        let text = format!("{direction:?}");
+                        ui.selectable_value(&mut self.filter.direction, Some(direction), text);
+                    }
+                });
+            egui::ComboBox::from_label("Channel")
+                .selected_text(self.filter.channel.as_deref().unwrap_or("All"))
+                .show_ui(ui, |ui| {
+                    ui.selectable_value(&mut self.filter.channel, None, "All");
+                    for channel in &self.channels {
+                        ui.selectable_value(&mut self.filter.channel, Some(channel.clone()), channel);
+                    }
+                });
+            if self.sources.len() > 1 {
+                egui::ComboBox::from_label("Source")
+                    .selected_text(self.filter.source.as_deref().unwrap_or("All"))
+                    .show_ui(ui, |ui| {
+                        ui.selectable_value(&mut self.filter.source, None, "All");
+                        for source in &self.sources {
+                            ui.selectable_value(&mut self.filter.source, Some(source.clone()), source);
+                        }
+                    });
+            }
+        });
+        ui.horizontal(|ui| {
+            ui.label("UE:");
+            ui.add(egui::TextEdit::singleline(&mut self.filter.ue_id).desired_width(40.0));
+            ui.label("Search:");
+            ui.add(egui::TextEdit::singleline(&mut self.filter.text).desired_width(120.0));
+            ui.checkbox(&mut self.filter.bookmarked, "Bookmarks only");
+            if ui.button("Reset").clicked() {
+                self.filter = ListFilter::default();
+            }
+        });
+        ui.horizontal(|ui| {
+            ui.checkbox(&mut self.follow, "Follow current message");
+            ui.label(format!("{} / {} messages", self.filtered.len(), self.cached_len));
+            if ui
+                .button("Copy bookmarks")
+                .on_hover_text("Copy the bookmarked messages with their notes and tags (CSV)")
+                .clicked()
+            {
+                ui.output_mut(|o| o.copied_text = data.export_bookmarks());
+            }
+        });
+        if let Some((start, end)) = data.filter.time_range {
+            ui.horizontal(|ui| {
+                ui.label(format!("Time range: {} - {}", format_timestamp(start), format_timestamp(end)));
+                if ui.button("Clear").clicked() {
+                    data.filter.time_range = None;
+                }
+            });
+        }
+        if let Some(ue_id) = data.filter.ue_id {
+            ui.horizontal(|ui| {
+                ui.label(format!("UE: {ue_id}"));
+                if ui.button("Show all UEs").clicked() {
+                    data.filter.ue_id = None;
+                }
+            });
+        }
+    }
+
+    /// Display the list of messages
+    fn ui_list(&mut self, ui: &mut egui::Ui, data: &mut Data, scroll_to_current: bool) {
+        let text_style = egui::TextStyle::Monospace;
+        let row_height = ui.text_style_height(&text_style);
+        ui.label(
+            egui::RichText::new(format!(
+                "  {:>6} {:<12} {:<5} {:<4} {:<9} {:<4} Message",
+                "#", "Time", "Layer", "Dir", "Channel", "UE"
+            ))
+            .text_style(text_style.clone())
+            .strong(),
+        );
+        ui.separator();
+        let mut scroll_area = egui::ScrollArea::vertical()
+            .id_salt("scroll_area_message_list")
+            .auto_shrink([false, false]);
+        if scroll_to_current {
+            if let Some(position) = self.filtered.iter().position(|idx| *idx == data.current_index) {
+                let spacing = ui.spacing().item_spacing.y;
+                scroll_area = scroll_area.vertical_scroll_offset(position as f32 * (row_height + spacing));
+            }
+        }
+        let mut clicked = None;
+        let mut toggled = None;
+        scroll_area.show_rows(ui, row_height, self.filtered.len(), |ui, row_range| {
+            for row in row_range {
+                let idx = self.filtered[row];
+                let Some(trace) = data.events.get(idx) else {
+                    continue;
+                };
+                let text = egui::RichText::new(format_trace_row(idx, trace)).text_style(text_style.clone());
+                let mut response = ui.selectable_label(idx == data.current_index, text);
+                if let Some(bookmark) = &trace.bookmark {
+                    if !bookmark.note.is_empty() || !bookmark.tags.is_empty() {
+                        response = response.on_hover_text(bookmark_text(bookmark));
+                    }
+                }
+                if response.clicked() {
+                    clicked = Some(idx);
+                }
+                response.context_menu(|ui| {
+                    let text = if trace.bookmark.is_some() {
+                        "Remove bookmark"
+                    } else {
+                        "Bookmark"
+                    };
+                    if ui.button(text).clicked() {
+                        toggled = Some(idx);
+                        ui.close_menu();
+                    }
+                });
+            }
+        });
+        if let Some(idx) = clicked {
+            data.current_index = idx;
+        }
+        if let Some(idx) = toggled {
+            data.toggle_bookmark(idx);
+        }
+    }
+}
+
+/// Display an optional value, "All" if None
+fn option_text<T: core::fmt::Debug>(value: &Option<T>) -> String {
+    match value {
+        Some(v) => format!("{v:?}"),
+        None => "All".to_owned(),
+    }
+}
+
+/// Format one row of the message list
+fn format_trace_row(idx: usize, trace: &Trace) -> String {
+    let AdditionalInfos::RRCInfos(infos) = &trace.additional_infos;
+    let ue_id = match infos.ue_id {
+        Some(ue_id) => ue_id.to_string(),
+        None => "-".to_owned(),
+    };
+    format!(
+        "{} {:>6} {:<12} {:<5} {:<4} {:<9} {:<4} {}",
+        if trace.bookmark.is_some() { "★" } else { " " },
+        idx + 1,
+        format_timestamp(trace.timestamp),
+        format!("{:?}", trace.layer),
+        format!("{:?}", infos.direction),
+        infos.canal,
+        ue_id,
+        infos.canal_msg
+    )
+}
+
+/// Display the tags and the note of a bookmark
+pub fn bookmark_text(bookmark: &Bookmark) -> String {
+    match (bookmark.tags.is_empty(), bookmark.note.is_empty()) {
+        (false, false) => format!("[{}] {}", bookmark.tags_text(), bookmark.note),
+        (false, true) => format!("[{}]", bookmark.tags_text()),
+        _ => bookmark.note.clone(),
+    }
+}
+
+impl super::PanelController for MessageList {
+    fn name(&self) -> &'static str {
+        "Message list"
+    }
+
+    fn window_title(&self) -> &'static str {
+        "Message list"
+    }
+
+    fn clear(&mut self) {
+        self.filtered.clear();
+        self.layers.clear();
+        self.channels.clear();
+        self.sources.clear();
+        self.cached_len = 0;
+        self.current_index = 0;
+    }
+
+    fn show(&mut self, ctx: &egui::Context, open: &mut bool, data: &mut Data) -> Result<(), TramexError> {
+        self.update_filtered(data);
+        let scroll_to_current = self.follow && self.current_index != data.current_index;
+        egui::Window::new(self.window_title())
+            .default_width(560.0)
+            .default_height(400.0)
+            .resizable(true)
+            .open(open)
+            .show(ctx, |ui| {
+                self.ui_filters(ui, data);
+                ui.separator();
+                self.ui_list(ui, data, scroll_to_current);
+            });
+        self.current_index = data.current_index;
+        Ok(())
+    }
+}
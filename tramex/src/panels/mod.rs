@@ -1,9 +1,19 @@
 //! Module: panels
 
+pub mod cell_stats;
+pub mod compare;
+pub mod console;
+pub mod hex_viewer;
+pub mod ladder_diagram;
 pub mod logical_channels;
+pub mod message_list;
 pub mod panel_message;
+pub mod procedures;
 pub mod rrc_status;
+pub mod search;
+pub mod timeline;
 pub mod trame_manager;
+pub mod ue_list;
 
 pub mod functions_panels;
 
@@ -4,6 +4,7 @@ use eframe::egui;
 use tramex_tools::{
     data::{Data, Trace},
     errors::TramexError,
+    search::Search,
 };
 
 /// Message box
@@ -23,6 +24,15 @@ pub struct MessageBox {
 
     /// save text
     save_text: Vec<String>,
+
+    /// Tags of the bookmark being edited, separated by commas
+    tags_text: String,
+
+    /// Index of the trace of `tags_text`
+    tags_index: Option<usize>,
+
+    /// Search of the other panels, its matches are highlighted
+    search: Option<Search>,
 }
 
 impl MessageBox {
@@ -30,6 +40,42 @@ impl MessageBox {
     pub fn new() -> Self {
         Self { ..Default::default() }
     }
+
+    /// Display the bookmark of the current trace, with its note and tags
+    fn ui_bookmark(&mut self, ui: &mut egui::Ui, data: &mut Data) {
+        let index = data.current_index;
+        let Some(trace) = data.events.get_mut(index) else {
+            return;
+        };
+        let Some(bookmark) = &mut trace.bookmark else {
+            if ui.button("☆ Bookmark").on_hover_text("Bookmark this message").clicked() {
+                data.toggle_bookmark(index);
+            }
+            self.tags_index = None;
+            return;
+        };
+        if self.tags_index != Some(index) {
+            self.tags_text = bookmark.tags_text();
+            self.tags_index = Some(index);
+        }
+        ui.horizontal(|ui| {
+            ui.label("Tags:");
+            if ui
+                .add(egui::TextEdit::singleline(&mut self.tags_text).hint_text("tag1, tag2"))
+                .changed()
+            {
+                bookmark.set_tags(&self.tags_text);
+            }
+        });
+        ui.add(
+            egui::TextEdit::multiline(&mut bookmark.note)
+                .hint_text("Note")
+                .desired_rows(2),
+        );
+        if ui.button("★ Remove bookmark").clicked() {
+            data.toggle_bookmark(index);
+        }
+    }
 }
 
 impl super::PanelController for MessageBox {
@@ -46,6 +92,7 @@ impl super::PanelController for MessageBox {
         self.events_len = 0;
         self.current_index = 0;
         self.save_text = Vec::new();
+        self.tags_index = None;
     }
 
     fn show(&mut self, ctx: &egui::Context, open: &mut bool, data: &mut Data) -> Result<(), TramexError> {
@@ -75,6 +122,7 @@ impl super::PanelController for MessageBox {
         }
         self.events_len = data.events.len();
         self.current_index = data.current_index;
+        self.search = data.search.clone();
         egui::Window::new(self.window_title())
             .default_width(320.0)
             .default_height(480.0)
@@ -83,6 +131,10 @@ impl super::PanelController for MessageBox {
             .show(ctx, |ui| {
                 use super::PanelView as _;
                 self.ui(ui);
+                if self.current_trace.is_some() {
+                    ui.separator();
+                    self.ui_bookmark(ui, data);
+                }
             });
         Ok(())
     }
@@ -98,7 +150,7 @@ impl super::PanelView for MessageBox {
 
         if let Some(one_trace) = &self.current_trace {
             ui.label(format!("Current msg index: {}", self.current_index + 1));
-            display_log(ui, one_trace, self.show_full, &self.save_text);
+            display_log(ui, one_trace, self.show_full, &self.save_text, self.search.as_ref());
         }
     }
 }
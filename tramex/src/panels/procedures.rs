@@ -0,0 +1,171 @@
+//! Procedures and KPIs panel
+use crate::format_timestamp;
+use eframe::egui;
+use tramex_tools::{
+    analysis::procedures::{ProcedureAnalyzer, ProcedureKpi, ProcedureOutcome},
+    data::Data,
+    errors::TramexError,
+};
+
+/// Procedures panel
+#[derive(Default)]
+pub struct ProceduresPanel {
+    /// Procedure analyzer
+    analyzer: ProcedureAnalyzer,
+
+    /// KPIs computed from the analyzer
+    kpis: Vec<ProcedureKpi>,
+
+    /// Number of events used to compute `kpis`
+    cached_len: usize,
+
+    /// Revision of the events used to compute `kpis`
+    cached_revision: u64,
+
+    /// Show only the procedures that did not succeed
+    only_failed: bool,
+}
+
+impl ProceduresPanel {
+    /// Create a new ProceduresPanel
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Display the KPIs
+    fn ui_kpis(&self, ui: &mut egui::Ui) {
+        if self.kpis.is_empty() {
+            ui.label("No procedure found");
+            return;
+        }
+        egui::Grid::new("procedures_kpis").striped(true).show(ui, |ui| {
+            for header in [
+                "Procedure",
+                "Total",
+                "Success",
+                "Failure",
+                "Incomplete",
+                "Rate",
+                "p50",
+                "p90",
+                "p99",
+            ] {
+                ui.strong(header);
+            }
+            ui.end_row();
+            for kpi in &self.kpis {
+                ui.label(kpi.kind.to_string());
+                ui.label(kpi.total.to_string());
+                ui.label(kpi.success.to_string());
+                ui.label(kpi.failure.to_string());
+                ui.label(kpi.incomplete.to_string());
+                ui.label(match kpi.success_rate {
+                    Some(rate) => format!("{:.1}%", rate * 100.0),
+                    None => "-".to_owned(),
+                });
+                for latency in [kpi.latency_p50, kpi.latency_p90, kpi.latency_p99] {
+                    ui.label(match latency {
+                        Some(latency) => format!("{latency} ms"),
+                        None => "-".to_owned(),
+                    });
+                }
+                ui.end_row();
+            }
+        });
+    }
+
+    /// Display the list of procedures
+    fn ui_procedures(&self, ui: &mut egui::Ui, data: &mut Data) {
+        let rows: Vec<usize> = self
+            .analyzer
+            .procedures
+            .iter()
+            .enumerate()
+            .filter(|(_, procedure)| !self.only_failed || procedure.outcome != ProcedureOutcome::Success)
+            .filter(|(_, procedure)| data.filter.ue_id.is_none_or(|ue_id| procedure.ue_id == ue_id))
+            .map(|(idx, _)| idx)
+            .collect();
+        let text_style = egui::TextStyle::Monospace;
+        let row_height = ui.text_style_height(&text_style);
+        let mut clicked = None;
+        egui::ScrollArea::vertical()
+            .id_salt("scroll_area_procedures")
+            .auto_shrink([false, false])
+            .show_rows(ui, row_height, rows.len(), |ui, row_range| {
+                for row in row_range {
+                    let procedure = &self.analyzer.procedures[rows[row]];
+                    let duration = match procedure.duration() {
+                        Some(duration) => format!("{duration} ms"),
+                        None => "-".to_owned(),
+                    };
+                    let text = format!(
+                        "{} UE {:<4} {:<28} {:<10} {}",
+                        format_timestamp(procedure.start_timestamp),
+                        procedure.ue_id,
+                        procedure.kind.to_string(),
+                        procedure.outcome.to_string(),
+                        duration
+                    );
+                    let color = match procedure.outcome {
+                        ProcedureOutcome::Success => ui.visuals().text_color(),
+                        ProcedureOutcome::Failure => egui::Color32::from_rgb(255, 84, 84),
+                        ProcedureOutcome::Incomplete => egui::Color32::from_rgb(255, 181, 68),
+                    };
+                    let selected = (procedure.start_index..=procedure.end_index.unwrap_or(procedure.start_index))
+                        .contains(&data.current_index);
+                    let label = egui::RichText::new(text).text_style(text_style.clone()).color(color);
+                    if ui
+                        .selectable_label(selected, label)
+                        .on_hover_text("Click to go to the first message of the procedure")
+                        .clicked()
+                    {
+                        clicked = Some(procedure.start_index);
+                    }
+                }
+            });
+        if let Some(idx) = clicked {
+            data.current_index = idx;
+        }
+    }
+}
+
+impl super::PanelController for ProceduresPanel {
+    fn name(&self) -> &'static str {
+        "Procedures"
+    }
+
+    fn window_title(&self) -> &'static str {
+        "Procedures and KPIs"
+    }
+
+    fn clear(&mut self) {
+        self.analyzer.clear();
+        self.kpis.clear();
+        self.cached_len = 0;
+    }
+
+    fn show(&mut self, ctx: &egui::Context, open: &mut bool, data: &mut Data) -> Result<(), TramexError> {
+        if self.cached_revision != data.revision {
+            self.analyzer.clear();
+            self.cached_len = 0;
+            self.cached_revision = data.revision;
+        }
+        if self.cached_len != data.events.len() {
+            self.analyzer.analyze(&data.events);
+            self.kpis = self.analyzer.kpis();
+            self.cached_len = data.events.len();
+        }
+        egui::Window::new(self.window_title())
+            .default_width(560.0)
+            .default_height(400.0)
+            .resizable(true)
+            .open(open)
+            .show(ctx, |ui| {
+                self.ui_kpis(ui);
+                ui.separator();
+                ui.checkbox(&mut self.only_failed, "Only failed and incomplete procedures");
+                self.ui_procedures(ui, data);
+            });
+        Ok(())
+    }
+}
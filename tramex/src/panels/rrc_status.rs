@@ -4,6 +4,8 @@ use super::functions_panels::ArrowDirection;
 use super::functions_panels::CustomLabelColor;
 use super::functions_panels::make_arrow;
 use super::functions_panels::make_label;
+use std::collections::BTreeMap;
+use tramex_tools::analysis::rrc_state::{RrcState, changed_ue_id, rrc_states_at};
 use tramex_tools::data::AdditionalInfos;
 use tramex_tools::data::Data;
 use tramex_tools::errors::TramexError;
@@ -14,6 +16,11 @@ fn make_label_hover(ui: &mut egui::Ui, label: &str, show: bool, color: CustomLab
     make_label(ui, label, show, color);
 }
 
+/// Make a label for a RRC state, highlighted if it is the current state
+fn make_state_label(ui: &mut egui::Ui, label_state: RrcState, state: Option<RrcState>, color: CustomLabelColor) {
+    make_label_hover(ui, &label_state.to_string(), state == Some(label_state), color);
+}
+
 /// Panel to display the RRC status
 #[derive(Default)]
 pub struct LinkPanel {
@@ -29,11 +36,17 @@ pub struct LinkPanel {
     /// Current index
     current_index: usize,
 
+    /// Revision of the events used to compute `states`
+    cached_revision: u64,
+
     /// Font id
     font_id: egui::FontId,
 
-    /// Is connected
-    is_connected: bool,
+    /// RRC state of each UE at the current index
+    states: BTreeMap<u64, RrcState>,
+
+    /// UE of the current message
+    current_ue: Option<u64>,
 }
 
 impl LinkPanel {
@@ -45,21 +58,57 @@ impl LinkPanel {
             canal: None,
             canal_msg: None,
             current_index: 0,
-            is_connected: false,
+            cached_revision: 0,
+            states: BTreeMap::new(),
+            current_ue: None,
         }
     }
 
+    /// Get the RRC state of the UE of the current message
+    fn current_state(&self) -> Option<RrcState> {
+        self.current_ue.and_then(|ue_id| self.states.get(&ue_id).copied())
+    }
+
     /// Display the control of the link
     pub fn ui_control(&self, ui: &mut egui::Ui) {
+        let state = self.current_state();
         ui.vertical_centered_justified(|ui| {
-            make_label_hover(ui, "CONNECTED", self.is_connected, CustomLabelColor::Green);
+            match self.current_ue {
+                Some(ue_id) => ui.label(format!("UE {ue_id}")),
+                None => ui.label("No UE for this message"),
+            };
+            make_state_label(ui, RrcState::Connected, state, CustomLabelColor::Green);
+            make_state_label(ui, RrcState::Connecting, state, CustomLabelColor::Orange);
+            make_state_label(ui, RrcState::Reestablishing, state, CustomLabelColor::Orange);
         });
     }
 
     /// Display the idle state of the LTE
     pub fn ui_idle_lte(&self, ui: &mut egui::Ui) {
+        let state = self.current_state();
         ui.vertical_centered_justified(|ui| {
-            make_label_hover(ui, "IDLE", !self.is_connected, CustomLabelColor::Red);
+            make_state_label(ui, RrcState::Inactive, state, CustomLabelColor::Blue);
+            make_state_label(ui, RrcState::Idle, state, CustomLabelColor::Red);
+        });
+    }
+
+    /// Display the state of every UE
+    pub fn ui_all_ues(&self, ui: &mut egui::Ui) {
+        if self.states.is_empty() {
+            ui.label("No UE seen yet");
+            return;
+        }
+        egui::Grid::new("rrc_status_ues").striped(true).show(ui, |ui| {
+            for (ue_id, state) in &self.states {
+                if Some(*ue_id) == self.current_ue {
+                    ui.strong(format!("UE {ue_id}"));
+                    ui.strong(state.to_string());
+                } else {
+                    ui.label(format!("UE {ue_id}"));
+                    ui.label(state.to_string());
+                }
+                ui.end_row();
+            }
         });
     }
 
@@ -117,36 +166,28 @@ impl super::PanelController for LinkPanel {
         self.canal_msg = None;
         self.direction = None;
         self.current_index = 0;
-        self.is_connected = false;
+        self.cached_revision = 0;
+        self.states.clear();
+        self.current_ue = None;
     }
 
     fn show(&mut self, ctx: &egui::Context, open: &mut bool, data: &mut Data) -> Result<(), TramexError> {
-        if data.is_different_index(self.current_index) {
+        // the events before the current index change when the events are reordered or removed
+        if data.is_different_index(self.current_index) || self.cached_revision != data.revision {
             if let Some(one_trace) = data.get_current_trace() {
                 match &one_trace.additional_infos {
                     AdditionalInfos::RRCInfos(infos) => {
-                        if self.current_index < data.current_index {
-                            match (self.is_connected, self.canal_msg.as_deref()) {
-                                (true, Some("RRC connection release")) => self.is_connected = false,
-                                (false, Some("RRC connection setup complete")) => self.is_connected = true,
-                                _ => {}
-                            }
-                        } else {
-                            match (self.is_connected, self.canal_msg.as_deref()) {
-                                (true, Some("RRC connection setup complete")) => self.is_connected = false,
-                                (false, Some("RRC connection release")) => self.is_connected = true,
-                                _ => {}
-                            }
-                        }
-
+                        // the state of a UE which changed of ID is given under its new ID
+                        self.current_ue = changed_ue_id(one_trace).or(infos.ue_id);
                         self.canal = Some(infos.canal.to_owned());
                         self.canal_msg = Some(infos.canal_msg.to_owned());
                         self.direction = Some(infos.direction.clone());
                     }
                 }
             }
-
+            self.states = rrc_states_at(&data.events, data.current_index);
             self.current_index = data.current_index;
+            self.cached_revision = data.revision;
         }
         egui::Window::new(self.window_title())
             .default_width(160.0)
@@ -168,5 +209,7 @@ impl super::PanelView for LinkPanel {
         self.ui_content(ui);
         ui.separator();
         self.ui_idle_lte(ui);
+        ui.separator();
+        self.ui_all_ues(ui);
     }
 }
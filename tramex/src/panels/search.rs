@@ -0,0 +1,160 @@
+//! Search panel
+use eframe::egui;
+use tramex_tools::{
+    data::Data,
+    errors::TramexError,
+    search::{Search, SearchMode, SearchQuery, next_match, previous_match},
+};
+
+/// Search
+#[derive(Default)]
+pub struct SearchPanel {
+    /// Search entered by the user
+    query: SearchQuery,
+
+    /// Search used to compute `matching` (None if not compiled yet)
+    cached_query: Option<SearchQuery>,
+
+    /// Revision of the events used to compute `matching`
+    cached_revision: u64,
+
+    /// Number of events used to compute `matching`
+    cached_len: usize,
+
+    /// Indexes of the events matching the search
+    matching: Vec<usize>,
+
+    /// Error of the pattern
+    error: Option<String>,
+}
+
+impl SearchPanel {
+    /// Create a new SearchPanel
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Compile the search if it changed and share it with the other panels, then update the matching events
+    fn update_search(&mut self, data: &mut Data) {
+        if self.cached_query.as_ref() != Some(&self.query) {
+            self.cached_query = Some(self.query.clone());
+            self.matching.clear();
+            self.cached_len = 0;
+            data.search = None;
+            self.error = None;
+            if !self.query.pattern.trim().is_empty() {
+                match Search::new(&self.query) {
+                    Ok(search) => data.search = Some(search),
+                    Err(err) => self.error = Some(err.message),
+                }
+            }
+        }
+        let Some(search) = &data.search else {
+            return;
+        };
+        if self.cached_revision != data.revision || data.events.len() < self.cached_len {
+            self.matching.clear();
+            self.cached_len = 0;
+        }
+        for (index, trace) in data.events.iter().enumerate().skip(self.cached_len) {
+            if search.matches(trace) {
+                self.matching.push(index);
+            }
+        }
+        self.cached_len = data.events.len();
+        self.cached_revision = data.revision;
+    }
+
+    /// Display the pattern and the options
+    fn ui_query(&mut self, ui: &mut egui::Ui) -> bool {
+        let mut submitted = false;
+        ui.horizontal(|ui| {
+            let hint = match self.query.mode {
+                SearchMode::Hex => "0a 1b ?? 3c",
+                _ => "Search",
+            };
+            let response = ui.add(egui::TextEdit::singleline(&mut self.query.pattern).hint_text(hint));
+            submitted = response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+            if submitted {
+                response.request_focus();
+            }
+        });
+        ui.horizontal(|ui| {
+            ui.radio_value(&mut self.query.mode, SearchMode::Text, "Text");
+            ui.radio_value(&mut self.query.mode, SearchMode::Regex, "Regex");
+            ui.radio_value(&mut self.query.mode, SearchMode::Hex, "Hex bytes");
+        });
+        ui.add_enabled_ui(self.query.mode != SearchMode::Hex, |ui| {
+            ui.horizontal(|ui| {
+                ui.checkbox(&mut self.query.case_sensitive, "Match case");
+                ui.checkbox(&mut self.query.whole_word, "Whole word");
+                ui.checkbox(&mut self.query.in_message, "Message");
+                ui.checkbox(&mut self.query.in_text, "Text");
+            });
+        });
+        submitted
+    }
+
+    /// Display the number of matches and the buttons to move between them
+    fn ui_navigation(&mut self, ui: &mut egui::Ui, data: &mut Data, submitted: bool) {
+        if data.search.is_none() {
+            return;
+        }
+        let position = self.matching.binary_search(&data.current_index).ok();
+        let (mut previous, mut next) = (false, submitted);
+        ui.horizontal(|ui| {
+            match position {
+                Some(position) => ui.label(format!("Match {} of {}", position + 1, self.matching.len())),
+                None => ui.label(format!("{} matching messages", self.matching.len())),
+            };
+            ui.add_enabled_ui(!self.matching.is_empty(), |ui| {
+                previous = ui.button("Previous").on_hover_text("Find the previous match").clicked();
+                next |= ui.button("Next").on_hover_text("Find the next match (Enter)").clicked();
+            });
+        });
+        let moved = match (previous, next) {
+            (true, _) => previous_match(&self.matching, data.current_index),
+            (false, true) => next_match(&self.matching, data.current_index),
+            _ => None,
+        };
+        if let Some(index) = moved {
+            data.current_index = index;
+        }
+    }
+}
+
+impl super::PanelController for SearchPanel {
+    fn name(&self) -> &'static str {
+        "Search"
+    }
+
+    fn window_title(&self) -> &'static str {
+        "Search"
+    }
+
+    fn clear(&mut self) {
+        self.matching.clear();
+        self.cached_len = 0;
+        self.cached_query = None;
+    }
+
+    fn show(&mut self, ctx: &egui::Context, open: &mut bool, data: &mut Data) -> Result<(), TramexError> {
+        egui::Window::new(self.window_title())
+            .default_width(360.0)
+            .resizable(true)
+            .open(open)
+            .show(ctx, |ui| {
+                let submitted = self.ui_query(ui);
+                self.update_search(data);
+                if let Some(error) = &self.error {
+                    ui.colored_label(egui::Color32::RED, error);
+                }
+                self.ui_navigation(ui, data, submitted);
+            });
+        if !*open {
+            data.search = None;
+            self.cached_query = None;
+        }
+        Ok(())
+    }
+}
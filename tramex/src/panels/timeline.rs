@@ -0,0 +1,372 @@
+//! Timeline panel
+use crate::format_timestamp;
+use eframe::egui;
+use tramex_tools::{
+    data::{AdditionalInfos, Data, Trace},
+    errors::TramexError,
+    interface::{layer::Layer, types::Direction},
+};
+
+/// Height of one lane
+const LANE_HEIGHT: f32 = 22.0;
+
+/// Width of the lane names column
+const LABEL_WIDTH: f32 = 90.0;
+
+/// Height of the time axis
+const AXIS_HEIGHT: f32 = 18.0;
+
+/// Minimum visible duration (milliseconds)
+const MIN_SPAN: f64 = 10.0;
+
+/// Lane of the timeline
+#[derive(Debug, Clone, PartialEq)]
+struct Lane {
+    /// Layer of the lane
+    layer: Layer,
+
+    /// UE of the lane (None if lanes are not split by UE or for broadcast messages)
+    ue_id: Option<u64>,
+}
+
+impl Lane {
+    /// Name of the lane
+    fn label(&self) -> String {
+        match self.ue_id {
+            Some(ue_id) => format!("{:?} UE {}", self.layer, ue_id),
+            None => format!("{:?}", self.layer),
+        }
+    }
+}
+
+/// Timeline
+#[derive(Default)]
+pub struct Timeline {
+    /// Lanes of the timeline
+    lanes: Vec<Lane>,
+
+    /// Lane index of each event
+    event_lanes: Vec<usize>,
+
+    /// Smallest timestamp of the events
+    min_timestamp: u64,
+
+    /// Biggest timestamp of the events
+    max_timestamp: u64,
+
+    /// Visible time range (None to fit all the events)
+    view: Option<(f64, f64)>,
+
+    /// Split the lanes by UE
+    per_ue: bool,
+
+    /// Value of `per_ue` used to compute the lanes
+    cached_per_ue: bool,
+
+    /// Revision of the events used to compute the lanes
+    cached_revision: u64,
+
+    /// Start of the time range being brushed
+    brush_start: Option<f64>,
+}
+
+impl Timeline {
+    /// Create a new Timeline
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Update the lanes if the events or the options changed
+    fn update_lanes(&mut self, data: &Data) {
+        if self.cached_per_ue != self.per_ue
+            || self.cached_revision != data.revision
+            || data.events.len() < self.event_lanes.len()
+        {
+            self.lanes.clear();
+            self.event_lanes.clear();
+        }
+        if self.event_lanes.is_empty() {
+            self.min_timestamp = u64::MAX;
+            self.max_timestamp = 0;
+        }
+        for trace in data.events.iter().skip(self.event_lanes.len()) {
+            let lane = self.lane_of(trace);
+            let lane_idx = match self.lanes.iter().position(|one_lane| one_lane == &lane) {
+                Some(lane_idx) => lane_idx,
+                None => {
+                    self.lanes.push(lane);
+                    self.lanes.len() - 1
+                }
+            };
+            self.event_lanes.push(lane_idx);
+            self.min_timestamp = self.min_timestamp.min(trace.timestamp);
+            self.max_timestamp = self.max_timestamp.max(trace.timestamp);
+        }
+        self.cached_per_ue = self.per_ue;
+        self.cached_revision = data.revision;
+    }
+
+    /// Get the lane of a trace
+    fn lane_of(&self, trace: &Trace) -> Lane {
+        let AdditionalInfos::RRCInfos(infos) = &trace.additional_infos;
+        Lane {
+            layer: trace.layer.clone(),
+            ue_id: if self.per_ue { infos.ue_id } else { None },
+        }
+    }
+
+    /// Get the visible time range
+    fn view_range(&self) -> (f64, f64) {
+        match self.view {
+            Some(view) => view,
+            None => {
+                let margin = ((self.max_timestamp - self.min_timestamp) as f64 * 0.02).max(MIN_SPAN / 2.0);
+                (self.min_timestamp as f64 - margin, self.max_timestamp as f64 + margin)
+            }
+        }
+    }
+
+    /// Display the controls
+    fn ui_controls(&mut self, ui: &mut egui::Ui, data: &mut Data) {
+        ui.horizontal(|ui| {
+            ui.checkbox(&mut self.per_ue, "Lanes per UE");
+            if ui.button("Fit").on_hover_text("Show all the events").clicked() {
+                self.view = None;
+            }
+            ui.add_enabled_ui(data.filter.time_range.is_some(), |ui| {
+                if ui.button("Clear selection").clicked() {
+                    data.filter.time_range = None;
+                }
+            });
+        });
+        ui.label("Scroll to zoom, right drag to pan, left drag to select a time range, click to select a message");
+        if let Some((start, end)) = data.filter.time_range {
+            ui.label(format!(
+                "Selected range: {} - {} ({} ms), the messages out of it are dimmed",
+                format_timestamp(start),
+                format_timestamp(end),
+                end - start
+            ));
+        }
+        if let Some(ue_id) = data.filter.ue_id {
+            ui.label(format!("Only the messages of UE {ue_id} are shown"));
+        }
+    }
+
+    /// Display the timeline
+    fn ui_timeline(&mut self, ui: &mut egui::Ui, data: &mut Data) {
+        let height = AXIS_HEIGHT + LANE_HEIGHT * self.lanes.len().max(1) as f32;
+        let (rect, response) =
+            ui.allocate_exact_size(egui::vec2(ui.available_width(), height), egui::Sense::click_and_drag());
+        let painter = ui.painter_at(rect);
+        let visuals = ui.visuals().clone();
+        let plot = egui::Rect::from_min_max(egui::pos2(rect.left() + LABEL_WIDTH, rect.top() + AXIS_HEIGHT), rect.max);
+        let (start, end) = self.view_range();
+        let to_x = |timestamp: f64| plot.left() + ((timestamp - start) / (end - start)) as f32 * plot.width();
+        let to_time = |x: f32| start + ((x - plot.left()) / plot.width()) as f64 * (end - start);
+
+        // selected time range
+        if let Some((range_start, range_end)) = data.filter.time_range {
+            let selection = egui::Rect::from_x_y_ranges(to_x(range_start as f64)..=to_x(range_end as f64), plot.y_range());
+            painter.rect_filled(selection, 0.0, visuals.selection.bg_fill.gamma_multiply(0.4));
+        }
+
+        // lanes
+        for (lane_idx, lane) in self.lanes.iter().enumerate() {
+            let y = plot.top() + LANE_HEIGHT * lane_idx as f32;
+            if lane_idx % 2 == 1 {
+                let lane_rect = egui::Rect::from_min_size(egui::pos2(plot.left(), y), egui::vec2(plot.width(), LANE_HEIGHT));
+                painter.rect_filled(lane_rect, 0.0, visuals.faint_bg_color);
+            }
+            painter.text(
+                egui::pos2(rect.left() + 4.0, y + LANE_HEIGHT / 2.0),
+                egui::Align2::LEFT_CENTER,
+                lane.label(),
+                egui::FontId::monospace(11.0),
+                visuals.text_color(),
+            );
+        }
+
+        // time axis
+        let nb_ticks = ((plot.width() / 120.0) as usize).max(1);
+        for tick in 0..=nb_ticks {
+            let x = plot.left() + plot.width() * tick as f32 / nb_ticks as f32;
+            let timestamp = to_time(x).max(0.0) as u64;
+            painter.line_segment(
+                [egui::pos2(x, plot.top() - 4.0), egui::pos2(x, plot.bottom())],
+                egui::Stroke::new(0.5, visuals.weak_text_color()),
+            );
+            painter.text(
+                egui::pos2(x, rect.top()),
+                egui::Align2::CENTER_TOP,
+                format_timestamp(timestamp),
+                egui::FontId::monospace(10.0),
+                visuals.weak_text_color(),
+            );
+        }
+
+        // events
+        for (idx, trace) in data.events.iter().enumerate() {
+            let x = to_x(trace.timestamp as f64);
+            if x < plot.left() || x > plot.right() {
+                continue;
+            }
+            if !data.filter.matches_ue(trace) {
+                continue;
+            }
+            let Some(lane_idx) = self.event_lanes.get(idx) else {
+                continue;
+            };
+            let y = plot.top() + LANE_HEIGHT * *lane_idx as f32;
+            let AdditionalInfos::RRCInfos(infos) = &trace.additional_infos;
+            let mut color = match infos.direction {
+                Direction::UL | Direction::TO => egui::Color32::from_rgb(68, 143, 255),
+                Direction::DL | Direction::FROM => egui::Color32::from_rgb(90, 200, 100),
+                Direction::NONE => egui::Color32::GRAY,
+            };
+            // the messages out of the selected range stay visible to change the range
+            if !data.filter.matches_time(trace) {
+                color = color.gamma_multiply(0.3);
+            }
+            painter.line_segment(
+                [egui::pos2(x, y + 3.0), egui::pos2(x, y + LANE_HEIGHT - 3.0)],
+                egui::Stroke::new(1.5, color),
+            );
+        }
+
+        // bookmarks
+        let bookmark_color = egui::Color32::from_rgb(255, 190, 40);
+        for (_, trace, _) in data.bookmarks() {
+            let x = to_x(trace.timestamp as f64);
+            if x < plot.left() || x > plot.right() {
+                continue;
+            }
+            painter.line_segment(
+                [egui::pos2(x, plot.top()), egui::pos2(x, plot.bottom())],
+                egui::Stroke::new(1.0, bookmark_color.gamma_multiply(0.6)),
+            );
+            let marker = vec![
+                egui::pos2(x - 4.0, plot.top() - 6.0),
+                egui::pos2(x + 4.0, plot.top() - 6.0),
+                egui::pos2(x, plot.top()),
+            ];
+            painter.add(egui::Shape::convex_polygon(marker, bookmark_color, egui::Stroke::NONE));
+        }
+
+        // current message
+        if let Some(trace) = data.get_current_trace() {
+            let x = to_x(trace.timestamp as f64);
+            painter.line_segment(
+                [egui::pos2(x, plot.top()), egui::pos2(x, plot.bottom())],
+                egui::Stroke::new(1.5, egui::Color32::from_rgb(255, 84, 84)),
+            );
+        }
+
+        self.handle_input(ui, &response, data, plot, (start, end));
+    }
+
+    /// Handle zoom, pan, brushing and click
+    fn handle_input(
+        &mut self,
+        ui: &egui::Ui,
+        response: &egui::Response,
+        data: &mut Data,
+        plot: egui::Rect,
+        (start, end): (f64, f64),
+    ) {
+        let to_time = |x: f32| start + ((x - plot.left()) / plot.width()) as f64 * (end - start);
+        let pointer = response.hover_pos();
+
+        // zoom around the pointer
+        if let Some(pos) = pointer {
+            let scroll = ui.input(|i| i.smooth_scroll_delta.y);
+            if scroll != 0.0 {
+                let factor = (-scroll as f64 / 200.0).exp();
+                let center = to_time(pos.x);
+                let new_start = center - (center - start) * factor;
+                let new_end = center + (end - center) * factor;
+                if new_end - new_start >= MIN_SPAN {
+                    self.view = Some((new_start, new_end));
+                }
+            }
+        }
+
+        // pan
+        if response.dragged_by(egui::PointerButton::Secondary) {
+            let shift = -(response.drag_delta().x / plot.width()) as f64 * (end - start);
+            self.view = Some((start + shift, end + shift));
+        }
+
+        // brush
+        if response.drag_started_by(egui::PointerButton::Primary) {
+            self.brush_start = response.interact_pointer_pos().map(|pos| to_time(pos.x));
+        }
+        if let (Some(brush_start), Some(pos)) = (self.brush_start, response.interact_pointer_pos()) {
+            if response.dragged_by(egui::PointerButton::Primary) || response.drag_stopped() {
+                let brush_end = to_time(pos.x);
+                let range_start = brush_start.min(brush_end).max(0.0) as u64;
+                let range_end = brush_start.max(brush_end).max(0.0) as u64;
+                data.filter.time_range = Some((range_start, range_end));
+            }
+        }
+        if response.drag_stopped() {
+            self.brush_start = None;
+        }
+
+        // select the nearest message
+        if response.clicked() {
+            if let Some(pos) = response.interact_pointer_pos() {
+                let lane_idx = ((pos.y - plot.top()) / LANE_HEIGHT).floor() as usize;
+                let clicked_time = to_time(pos.x);
+                let max_distance = (end - start) / plot.width() as f64 * 6.0;
+                let nearest = data
+                    .events
+                    .iter()
+                    .enumerate()
+                    .filter(|(idx, trace)| self.event_lanes.get(*idx) == Some(&lane_idx) && data.filter.matches_ue(trace))
+                    .map(|(idx, trace)| (idx, (trace.timestamp as f64 - clicked_time).abs()))
+                    .filter(|(_, distance)| *distance <= max_distance)
+                    .min_by(|a, b| a.1.total_cmp(&b.1));
+                if let Some((idx, _)) = nearest {
+                    data.current_index = idx;
+                }
+            }
+        }
+    }
+}
+
+impl super::PanelController for Timeline {
+    fn name(&self) -> &'static str {
+        "Timeline"
+    }
+
+    fn window_title(&self) -> &'static str {
+        "Timeline"
+    }
+
+    fn clear(&mut self) {
+        self.lanes.clear();
+        self.event_lanes.clear();
+        self.view = None;
+        self.brush_start = None;
+    }
+
+    fn show(&mut self, ctx: &egui::Context, open: &mut bool, data: &mut Data) -> Result<(), TramexError> {
+        self.update_lanes(data);
+        egui::Window::new(self.window_title())
+            .default_width(640.0)
+            .default_height(200.0)
+            .resizable(true)
+            .open(open)
+            .show(ctx, |ui| {
+                self.ui_controls(ui, data);
+                ui.separator();
+                if data.events.is_empty() {
+                    ui.label("No events");
+                    return;
+                }
+                self.ui_timeline(ui, data);
+            });
+        Ok(())
+    }
+}
@@ -1,7 +1,8 @@
 //! TrameManager
 use eframe::egui;
 use tramex_tools::data::Data;
-use tramex_tools::interface::layer::{LayerLogLevel, Layers};
+use tramex_tools::interface::layer::{LayerConfig, LayerLogLevel, Layers, LayersPreset};
+use tramex_tools::interface::remote_api::Request;
 
 #[derive(serde::Deserialize, serde::Serialize)]
 /// TrameManager
@@ -10,6 +11,14 @@ pub struct TrameManager {
     pub layers_list: Layers,
     /// boolean to get more log
     pub should_get_more_log: bool,
+
+    /// Presets saved by the user
+    #[serde(default)]
+    pub presets: Vec<LayersPreset>,
+
+    /// Name of the preset to save
+    #[serde(skip)]
+    preset_name: String,
 }
 
 impl TrameManager {
@@ -18,6 +27,8 @@ impl TrameManager {
         Self {
             layers_list: Layers::new_optiniated(),
             should_get_more_log: false,
+            presets: Vec::new(),
+            preset_name: String::new(),
         }
     }
 }
@@ -30,25 +41,87 @@ impl Default for TrameManager {
 
 impl TrameManager {
     /// Show the options
-    pub fn show_options(&mut self, ui: &mut egui::Ui) {
+    ///
+    /// The traces loaded are filtered again when the layers change.
+    pub fn show_options(&mut self, ui: &mut egui::Ui, data: &mut Data) {
+        let previous_layers = self.layers_list.clone();
         ui.collapsing("Options", |ui| {
-            checkbox(ui, &mut self.layers_list.phy, "PHY");
-            checkbox(ui, &mut self.layers_list.mac, "MAC");
-            checkbox(ui, &mut self.layers_list.rlc, "RLC");
-            checkbox(ui, &mut self.layers_list.pdcp, "PDCP");
-            checkbox(ui, &mut self.layers_list.rrc, "RRC");
-            checkbox(ui, &mut self.layers_list.nas, "NAS");
-            checkbox(ui, &mut self.layers_list.s72, "S72");
-            checkbox(ui, &mut self.layers_list.s1ap, "S1AP");
-            checkbox(ui, &mut self.layers_list.ngap, "NGAP");
-            checkbox(ui, &mut self.layers_list.gtpu, "GTPU");
-            checkbox(ui, &mut self.layers_list.x2ap, "X2AP");
-            checkbox(ui, &mut self.layers_list.xnap, "XnAP");
-            checkbox(ui, &mut self.layers_list.m2ap, "M2AP");
-            checkbox(ui, &mut self.layers_list.lppa, "LPPa");
-            checkbox(ui, &mut self.layers_list.nrppa, "NRPPa");
-            checkbox(ui, &mut self.layers_list.trx, "TRX");
+            self.show_presets(ui, data);
+            ui.separator();
+            egui::Grid::new("layers_options").num_columns(4).striped(true).show(ui, |ui| {
+                ui.label("Layer");
+                ui.label("Level");
+                ui.label("Max size");
+                ui.label("Payload");
+                ui.end_row();
+                for (name, config) in self.layers_list.iter_mut() {
+                    layer_options(ui, config, name);
+                    ui.end_row();
+                }
+            });
+        });
+        if self.layers_list != previous_layers {
+            data.filter_layers(&self.layers_list);
+        }
+    }
+
+    /// Show the presets of the log configuration and the button to send it to the servers
+    fn show_presets(&mut self, ui: &mut egui::Ui, data: &mut Data) {
+        let selected = LayersPreset::builtin()
+            .into_iter()
+            .chain(self.presets.iter().cloned())
+            .find(|preset| preset.layers == self.layers_list)
+            .map(|preset| preset.name)
+            .unwrap_or_else(|| "Custom".to_owned());
+        egui::ComboBox::from_label("Preset")
+            .selected_text(selected)
+            .show_ui(ui, |ui| {
+                for preset in LayersPreset::builtin().iter().chain(self.presets.iter()) {
+                    if ui.selectable_label(preset.layers == self.layers_list, &preset.name).clicked() {
+                        self.layers_list = preset.layers.clone();
+                    }
+                }
+            });
+        ui.horizontal(|ui| {
+            ui.add(
+                egui::TextEdit::singleline(&mut self.preset_name)
+                    .hint_text("Preset name")
+                    .desired_width(120.0),
+            );
+            let name = self.preset_name.trim().to_owned();
+            let builtin = LayersPreset::builtin().iter().any(|preset| preset.name == name);
+            if ui
+                .add_enabled(!name.is_empty() && !builtin, egui::Button::new("Save"))
+                .on_hover_text("Save the configuration as a preset, replacing the preset with the same name")
+                .clicked()
+            {
+                self.presets.retain(|preset| preset.name != name);
+                self.presets.push(LayersPreset::new(&name, self.layers_list.clone()));
+                self.preset_name.clear();
+            }
         });
+        let mut removed = None;
+        for (index, preset) in self.presets.iter().enumerate() {
+            ui.horizontal(|ui| {
+                ui.label(&preset.name);
+                if ui.small_button("✖").on_hover_text("Delete the preset").clicked() {
+                    removed = Some(index);
+                }
+            });
+        }
+        if let Some(index) = removed {
+            self.presets.remove(index);
+        }
+        let sources: Vec<String> = data.remote.sources().cloned().collect();
+        if ui
+            .add_enabled(!sources.is_empty(), egui::Button::new("Apply on server"))
+            .on_hover_text("Send the log configuration to the servers (log_set)")
+            .clicked()
+        {
+            for source in sources {
+                data.remote.request(Request::log_set(&self.layers_list).to(&source));
+            }
+        }
     }
 
     /// Show the controls
@@ -88,20 +161,36 @@ impl TrameManager {
                 }
             }
         });
+        let next_bookmark = data.next_bookmark();
+        let previous_bookmark = data.previous_bookmark();
+        ui.add_enabled_ui(next_bookmark.is_some(), |ui| {
+            if ui.button("Bookmark ▶").on_hover_text("Go to the next bookmark").clicked() {
+                if let Some(index) = next_bookmark {
+                    data.current_index = index;
+                }
+            }
+        });
+        ui.add_enabled_ui(previous_bookmark.is_some(), |ui| {
+            if ui.button("◀ Bookmark").on_hover_text("Go to the previous bookmark").clicked() {
+                if let Some(index) = previous_bookmark {
+                    data.current_index = index;
+                }
+            }
+        });
     }
 }
 
-/// Create a checkbox
-fn checkbox(ui: &mut egui::Ui, string: &mut LayerLogLevel, text: &str) {
-    let mut checked = match string {
-        LayerLogLevel::Debug => true,
-        LayerLogLevel::Warn => false,
-    };
-    if ui.checkbox(&mut checked, text).changed() {
-        if checked {
-            *string = LayerLogLevel::Debug;
-        } else {
-            *string = LayerLogLevel::Warn;
-        };
-    };
+/// Show the level, max size and payload of a layer
+fn layer_options(ui: &mut egui::Ui, config: &mut LayerConfig, name: &str) {
+    ui.label(name);
+    egui::ComboBox::from_id_salt(name)
+        .selected_text(config.level.to_string())
+        .show_ui(ui, |ui| {
+            for level in LayerLogLevel::ALL {
+                ui.selectable_value(&mut config.level, level, level.to_string());
+            }
+        });
+    ui.add(egui::DragValue::new(&mut config.max_size).range(0..=u32::MAX).suffix(" B"))
+        .on_hover_text("Maximum size of the hexadecimal dump of a log");
+    ui.checkbox(&mut config.payload, "");
 }
@@ -0,0 +1,203 @@
+//! Live UE list panel
+use crate::format_bitrate;
+use eframe::egui;
+use tramex_tools::{
+    data::Data,
+    errors::TramexError,
+    interface::{
+        remote_api::Request,
+        ue_get::{UeGetResponse, UeInfo},
+        websocket::reconnect::now_ms,
+    },
+};
+
+/// Default delay between two ue_get (milliseconds)
+const DEFAULT_REFRESH_MS: u64 = 1000;
+
+/// List of the UEs connected to the server
+pub struct UeList {
+    /// UEs of the latest ue_get response
+    ues: Vec<UeInfo>,
+
+    /// Message ID of the ue_get response used to compute `ues`
+    cached_message_id: Option<u64>,
+
+    /// Time of the latest ue_get (milliseconds)
+    last_request_ms: i64,
+
+    /// Delay between two ue_get (milliseconds)
+    refresh_ms: u64,
+
+    /// Refresh the list periodically
+    auto_refresh: bool,
+}
+
+impl Default for UeList {
+    fn default() -> Self {
+        Self {
+            ues: vec![],
+            cached_message_id: None,
+            last_request_ms: 0,
+            refresh_ms: DEFAULT_REFRESH_MS,
+            auto_refresh: true,
+        }
+    }
+}
+
+impl UeList {
+    /// Create a new UeList
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Send a ue_get if the refresh delay is elapsed
+    fn poll(&mut self, ctx: &egui::Context, data: &mut Data) {
+        if !self.auto_refresh || !data.remote.is_connected() {
+            return;
+        }
+        let now = now_ms();
+        let elapsed = (now - self.last_request_ms).max(0) as u64;
+        if elapsed >= self.refresh_ms && !data.remote.is_pending("ue_get", now) {
+            self.request(data);
+        }
+        ctx.request_repaint_after(std::time::Duration::from_millis(
+            self.refresh_ms.saturating_sub(elapsed).max(10),
+        ));
+    }
+
+    /// Send a ue_get with the statistics
+    fn request(&mut self, data: &mut Data) {
+        if data.remote.request(Request::new("ue_get").param("stats", true)).is_some() {
+            self.last_request_ms = now_ms();
+        }
+    }
+
+    /// Update the UEs from the latest ue_get response
+    fn update_ues(&mut self, data: &Data) {
+        let Some(exchange) = data.remote.latest_response("ue_get") else {
+            return;
+        };
+        if self.cached_message_id == Some(exchange.message_id) {
+            return;
+        }
+        self.cached_message_id = Some(exchange.message_id);
+        if let Some(response) = exchange.response.as_ref().and_then(UeGetResponse::from_json) {
+            self.ues = response.ue_list;
+        }
+    }
+
+    /// Display the controls
+    fn ui_controls(&mut self, ui: &mut egui::Ui, data: &mut Data) {
+        ui.horizontal(|ui| {
+            ui.checkbox(&mut self.auto_refresh, "Refresh every");
+            ui.add(
+                egui::DragValue::new(&mut self.refresh_ms)
+                    .speed(10.0)
+                    .range(200.0..=60000.0)
+                    .suffix(" ms"),
+            );
+            ui.add_enabled_ui(data.remote.is_connected(), |ui| {
+                if ui.button("Refresh").clicked() {
+                    self.request(data);
+                }
+            });
+        });
+        ui.horizontal(|ui| {
+            match data.filter.ue_id {
+                Some(ue_id) => ui.label(format!("Panels filtered on UE {ue_id}")),
+                None => ui.label("Click on a UE to filter the panels on it"),
+            };
+            ui.add_enabled_ui(data.filter.ue_id.is_some(), |ui| {
+                if ui.button("Show all UEs").clicked() {
+                    data.filter.ue_id = None;
+                }
+            });
+        });
+        if !data.remote.is_connected() {
+            ui.label("The UE list is only available with a websocket connection");
+        }
+    }
+
+    /// Display the UEs
+    fn ui_ues(&self, ui: &mut egui::Ui, data: &mut Data) {
+        if self.ues.is_empty() {
+            ui.label("No UE");
+            return;
+        }
+        egui::ScrollArea::vertical()
+            .id_salt("scroll_area_ue_list")
+            .auto_shrink([false, false])
+            .show(ui, |ui| {
+                egui::Grid::new("ue_list").striped(true).show(ui, |ui| {
+                    for header in ["UE", "RNTI", "Cell", "IMSI / 5G-GUTI", "Bearers", "DL", "UL"] {
+                        ui.strong(header);
+                    }
+                    ui.end_row();
+                    for ue in &self.ues {
+                        let ue_id = ue.ue_id();
+                        let selected = ue_id.is_some() && ue_id == data.filter.ue_id;
+                        let label = match ue_id {
+                            Some(ue_id) => ue_id.to_string(),
+                            None => "-".to_owned(),
+                        };
+                        if ui
+                            .selectable_label(selected, label)
+                            .on_hover_text("Click to filter the panels on this UE")
+                            .clicked()
+                        {
+                            data.filter.ue_id = if selected { None } else { ue_id };
+                        }
+                        ui.label(option_text(ue.rnti()));
+                        ui.label(option_text(ue.cells.first().and_then(|cell| cell.cell_id)));
+                        ui.label(ue.identity().unwrap_or("-"));
+                        ui.label(ue.bearers().join(", "));
+                        ui.label(format_bitrate(ue.dl_bitrate()));
+                        ui.label(format_bitrate(ue.ul_bitrate()));
+                        ui.end_row();
+                    }
+                });
+            });
+    }
+}
+
+/// Display an optional number, "-" if None
+fn option_text(value: Option<u64>) -> String {
+    match value {
+        Some(value) => value.to_string(),
+        None => "-".to_owned(),
+    }
+}
+
+impl super::PanelController for UeList {
+    fn name(&self) -> &'static str {
+        "UE list"
+    }
+
+    fn window_title(&self) -> &'static str {
+        "UE list"
+    }
+
+    fn clear(&mut self) {
+        self.ues.clear();
+        self.cached_message_id = None;
+        self.last_request_ms = 0;
+    }
+
+    fn show(&mut self, ctx: &egui::Context, open: &mut bool, data: &mut Data) -> Result<(), TramexError> {
+        if *open {
+            self.poll(ctx, data);
+        }
+        self.update_ues(data);
+        egui::Window::new(self.window_title())
+            .default_width(560.0)
+            .default_height(300.0)
+            .resizable(true)
+            .open(open)
+            .show(ctx, |ui| {
+                self.ui_controls(ui, data);
+                ui.separator();
+                self.ui_ues(ui, data);
+            });
+        Ok(())
+    }
+}
@@ -1,7 +1,9 @@
 //! This module contains some utility functions used in the application.
 use egui::{Color32, TextFormat, Ui, text::LayoutJob};
 use std::collections::BTreeSet;
-use tramex_tools::data::Trace;
+use tramex_tools::data::{AdditionalInfos, Trace};
+use tramex_tools::hexdump;
+use tramex_tools::search::Search;
 #[cfg(feature = "types_lte_3gpp")]
 use types_lte_3gpp::{
     export::asn1_codecs::{PerCodecData, uper::UperCodec},
@@ -45,11 +47,60 @@ pub fn color_label(job: &mut LayoutJob, ui: &Ui, label: &str, need_color: bool)
     );
 }
 
-/// Display a Trace type
-pub fn display_log(ui: &mut Ui, curr_trace: &Trace, full: bool, _text: &[String]) {
+/// Create a text with the matches of a search highlighted
+pub fn highlighted_text(ui: &Ui, text: &str, ranges: &[std::ops::Range<usize>]) -> LayoutJob {
+    let mut job = LayoutJob::default();
+    let normal = TextFormat {
+        color: ui.visuals().text_color(),
+        ..Default::default()
+    };
+    let highlight = TextFormat {
+        color: Color32::BLACK,
+        background: Color32::from_rgb(255, 210, 70),
+        ..Default::default()
+    };
+    let mut position = 0;
+    for range in ranges {
+        if range.start < position || range.end > text.len() {
+            continue;
+        }
+        job.append(&text[position..range.start], 0.0, normal.clone());
+        job.append(&text[range.clone()], 0.0, highlight.clone());
+        position = range.end;
+    }
+    job.append(&text[position..], 0.0, normal);
+    job
+}
+
+/// Display a Trace type, with the matches of the search highlighted
+pub fn display_log(ui: &mut Ui, curr_trace: &Trace, full: bool, _text: &[String], search: Option<&Search>) {
     ui.label(format!("{:?} at {:?}", &curr_trace.layer, &curr_trace.timestamp));
-    ui.label(format!("{:?}", &curr_trace.additional_infos));
-    ui.label(format!("{:?}", &curr_trace.hexa));
+    let matches = search.map(|search| search.trace_matches(curr_trace)).unwrap_or_default();
+    let AdditionalInfos::RRCInfos(infos) = &curr_trace.additional_infos;
+    let ue = match infos.ue_id {
+        Some(ue_id) => format!("UE {ue_id}"),
+        None => "No UE".to_owned(),
+    };
+    let prefix = match infos.canal.is_empty() {
+        true => format!("{:?} {ue} ", infos.direction),
+        false => format!("{:?} {ue} {}: ", infos.direction, infos.canal),
+    };
+    // the message name ends the text, the matches in it are moved after the prefix
+    let message_ranges: Vec<std::ops::Range<usize>> = matches
+        .message
+        .iter()
+        .map(|range| range.start + prefix.len()..range.end + prefix.len())
+        .collect();
+    let infos_text = format!("{prefix}{}", infos.canal_msg);
+    ui.label(highlighted_text(ui, &infos_text, &message_ranges));
+    ui.label(payload_summary(&curr_trace.hexa));
+    if !matches.hexa.is_empty() {
+        let offsets: Vec<String> = matches.hexa.iter().map(|range| range.start.to_string()).collect();
+        ui.label(format!("Bytes found at offsets {}", offsets.join(", ")));
+    }
+    if !full && !matches.text.is_empty() {
+        ui.label(format!("{} matches in the text, show the full message", matches.text.len()));
+    }
     if full {
         ui.separator();
         match &curr_trace.text {
@@ -59,8 +110,17 @@ pub fn display_log(ui: &mut Ui, curr_trace: &Trace, full: bool, _text: &[String]
                     .max_height(250.0)
                     .auto_shrink([false, true])
                     .show(ui, |ui| {
-                        for elem in vec_text {
-                            ui.label(elem);
+                        for (index, elem) in vec_text.iter().enumerate() {
+                            let ranges: Vec<std::ops::Range<usize>> = matches
+                                .text
+                                .iter()
+                                .filter(|(line, _)| *line == index)
+                                .map(|(_, range)| range.clone())
+                                .collect();
+                            match ranges.is_empty() {
+                                true => ui.label(elem),
+                                false => ui.label(highlighted_text(ui, elem, &ranges)),
+                            };
                         }
                     });
             }
@@ -84,6 +144,18 @@ pub fn display_log(ui: &mut Ui, curr_trace: &Trace, full: bool, _text: &[String]
     }
 }
 
+/// Number of bytes of the payload shown in the current message panel, the hex viewer shows all of them
+const PAYLOAD_PREVIEW: usize = 16;
+
+/// Size and first bytes of a payload
+fn payload_summary(hexa: &[u8]) -> String {
+    match hexa.len() {
+        0 => "No payload".to_owned(),
+        len if len <= PAYLOAD_PREVIEW => format!("{len} bytes: {}", hexdump::to_hex(hexa)),
+        len => format!("{len} bytes: {} …", hexdump::to_hex(&hexa[..PAYLOAD_PREVIEW])),
+    }
+}
+
 /// Decode the hexa value with types_lte_3gpp
 #[cfg(feature = "types_lte_3gpp")]
 pub fn hexe_decoding(curr_trace: &Trace) -> String {
@@ -102,3 +174,24 @@ pub fn hexe_decoding(curr_trace: &Trace) -> String {
         _ => "Not implemented".to_string(),
     }
 }
+
+/// Format a trace timestamp (milliseconds) as `HH:MM:SS.mmm`
+pub fn format_timestamp(timestamp: u64) -> String {
+    let ms_in_day = timestamp % 86_400_000;
+    let hours = ms_in_day / 3_600_000;
+    let minutes = (ms_in_day / 60_000) % 60;
+    let seconds = (ms_in_day / 1000) % 60;
+    let milliseconds = ms_in_day % 1000;
+    format!("{hours:02}:{minutes:02}:{seconds:02}.{milliseconds:03}")
+}
+
+/// Format a bitrate (bit/s) with a readable unit
+pub fn format_bitrate(bitrate: f64) -> String {
+    if bitrate >= 1e6 {
+        format!("{:.2} Mb/s", bitrate / 1e6)
+    } else if bitrate >= 1e3 {
+        format!("{:.1} kb/s", bitrate / 1e3)
+    } else {
+        format!("{bitrate:.0} b/s")
+    }
+}